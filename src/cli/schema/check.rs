@@ -5,17 +5,18 @@ use clap::Parser;
 
 use crate::{config::Config, db::DiffDatabase};
 
-use super::{diff::inspect_backend, Backend};
+use super::{
+    diff::{inspect_backend, inspect_options},
+    Backend,
+};
 
 #[derive(Debug, Parser)]
 pub struct CheckArgs {
-    /// from-backend for the diff operation
-    #[arg(default_value_t = Backend::Schema)]
-    from: Backend,
-    /// to-backend for the diff operation
-    #[arg(default_value_t = Backend::Migrations)]
-    to: Backend,
-    /// swaps the "from" and "to" arguments creating a reverse diff
+    /// backends to compare. All backends are compared to the first one.
+    /// A single backend is compared to the migrations.
+    #[arg(default_values_t = [Backend::Schema, Backend::Migrations], num_args = 1..)]
+    backends: Vec<Backend>,
+    /// reverses the order of the backends creating a reverse diff
     #[arg(long, short)]
     reverse: bool,
     /// check privilege differences (ie. grant/revoke statements)
@@ -24,19 +25,67 @@ pub struct CheckArgs {
     /// don't check privilege differences
     #[arg(long, group = "group_privileges")]
     without_privileges: bool,
+    /// check owner differences
+    #[arg(long, group = "group_owners")]
+    with_owners: bool,
+    /// don't check owner differences
+    #[arg(long, group = "group_owners")]
+    without_owners: bool,
+    /// fail if the column order of a table differs
+    #[arg(long)]
+    strict_column_order: bool,
 }
 
 pub async fn cmd(cfg: &Config, args: &CheckArgs) -> Result<()> {
+    let mut backends = args.backends.clone();
+    if backends.len() == 1 {
+        backends.push(Backend::Migrations);
+    }
+    if args.reverse {
+        backends.reverse();
+    }
     let mut db = DiffDatabase::new(&cfg.database).await?;
     db.create().await?;
-    let from = inspect_backend(cfg, &mut db, args.from).await?;
-    let to = inspect_backend(cfg, &mut db, args.to).await?;
+    let options = inspect_options(cfg, args.with_owners, args.without_owners);
+    let mut inspections = Vec::with_capacity(backends.len());
+    for backend in &backends {
+        inspections.push(inspect_backend(cfg, &mut db, *backend, &options).await?);
+    }
     db.drop().await?;
-    if from == to {
+    let strict = args.strict_column_order || cfg.diff.strict_column_order;
+    let from = &inspections[0];
+    let mut identical = true;
+    for (to_backend, to) in backends.iter().zip(&inspections).skip(1) {
+        let drift = from.column_order_drift(to);
+        for (_, table) in &drift {
+            println!(
+                "Warning: column order of table {}.{} differs: {} != {}",
+                table.schema, table.name, backends[0], to_backend
+            );
+        }
+        let mut summary = from.diff(to).summary();
+        if from == to {
+            // Equal inspections may still differ in constraints which
+            // `tusker diff` validates. Depending on the direction of the
+            // diff either side may be the one needing validation.
+            summary.extend(to.diff(from).summary());
+            if summary.is_empty() && (drift.is_empty() || !strict) {
+                continue;
+            }
+        }
+        identical = false;
+        println!("Schemas differ: {} != {}", backends[0], to_backend);
+        if summary.is_empty() {
+            println!("  No statements can be generated for the differences");
+        }
+        for line in summary {
+            println!("  {}", line);
+        }
+    }
+    if identical {
         println!("Schemas are identical");
         Ok(())
     } else {
-        println!("Schemas differ: {} != {}", args.from, args.to);
         println!("Run `tusker diff` to see the differences");
         exit(1);
     }
@@ -1,14 +1,19 @@
 use anyhow::Result;
 use clap::Parser;
 use tokio::{fs::File, io::AsyncReadExt};
-use tusker_schema::{diff::DiffSql, models::schema::join_sql, Inspection};
+use tusker_migration::sql::{is_transactional, without_concurrently, NO_TRANSACTION_MARKER};
+use tusker_schema::{
+    diff::DiffSql,
+    models::{schema::join_sql, table::Table},
+    InspectOptions, Inspection,
+};
 
 use crate::{
     config::{Config, DatabaseConfig},
     db::DiffDatabase,
 };
 
-use super::Backend;
+use super::{Backend, Format};
 
 #[derive(Debug, Parser)]
 pub struct DiffArgs {
@@ -32,9 +37,41 @@ pub struct DiffArgs {
     /// don't output privilege differences
     #[arg(long, group = "group_privileges")]
     without_privileges: bool,
+    /// output owner differences (ie. alter ... owner to statements)
+    #[arg(long, group = "group_owners")]
+    with_owners: bool,
+    /// don't output owner differences
+    #[arg(long, group = "group_owners")]
+    without_owners: bool,
+    /// add foreign keys and check constraints as NOT VALID, validate
+    /// them using separate statements and mark the migration as
+    /// non-transactional
+    #[arg(long)]
+    online_constraints: bool,
+    /// create and drop indexes concurrently and mark the migration as
+    /// non-transactional
+    #[arg(long)]
+    concurrent_indexes: bool,
+    /// output format
+    #[arg(long, default_value_t = Format::Text)]
+    format: Format,
 }
 
-async fn inspect_sql(db: &DiffDatabase, filename: &str) -> Result<Inspection> {
+pub fn inspect_options(cfg: &Config, with_owners: bool, without_owners: bool) -> InspectOptions {
+    let mut options = cfg.diff.inspect_options();
+    if with_owners {
+        options.owners = true;
+    } else if without_owners {
+        options.owners = false;
+    }
+    options
+}
+
+async fn inspect_sql(
+    db: &DiffDatabase,
+    filename: &str,
+    options: &InspectOptions,
+) -> Result<Inspection> {
     let mut client = db.connect().await?;
     let txn = client.transaction().await?;
     for filename in glob::glob(filename)? {
@@ -42,29 +79,35 @@ async fn inspect_sql(db: &DiffDatabase, filename: &str) -> Result<Inspection> {
         let mut file = File::open(filename).await?;
         let mut contents = vec![];
         file.read_to_end(&mut contents).await?;
-        let sql = String::from_utf8(contents)?;
+        let mut sql = String::from_utf8(contents)?;
+        // Migrations are inspected inside a transaction which doesn't
+        // allow `CONCURRENTLY`. Removing it doesn't change the schema.
+        if !is_transactional(&sql) {
+            sql = without_concurrently(&sql);
+        }
         // FIXME error handling
         txn.simple_query(&sql).await?;
     }
-    let inspection = tusker_schema::inspect(txn.client()).await?;
+    let inspection = tusker_schema::inspect_with(txn.client(), options).await?;
     txn.rollback().await?;
     Ok(inspection)
 }
 
-async fn inspect_db(cfg: &DatabaseConfig) -> Result<Inspection> {
+async fn inspect_db(cfg: &DatabaseConfig, options: &InspectOptions) -> Result<Inspection> {
     let client = cfg.connect().await?;
-    tusker_schema::inspect(&client).await
+    tusker_schema::inspect_with(&client, options).await
 }
 
 pub async fn inspect_backend(
     cfg: &Config,
     db: &mut DiffDatabase,
     backend: Backend,
+    options: &InspectOptions,
 ) -> Result<Inspection> {
     match backend {
-        Backend::Migrations => inspect_sql(db, &cfg.migrations.filename).await,
-        Backend::Schema => inspect_sql(db, &cfg.schema.filename).await,
-        Backend::Database => inspect_db(&cfg.database).await,
+        Backend::Migrations => inspect_sql(db, &cfg.migrations.filename, options).await,
+        Backend::Schema => inspect_sql(db, &cfg.schema.filename, options).await,
+        Backend::Database => inspect_db(&cfg.database, options).await,
     }
 }
 
@@ -78,11 +121,40 @@ pub async fn cmd(cfg: &Config, args: &DiffArgs) -> Result<()> {
     let mut db = DiffDatabase::new(&cfg.database).await?;
     db.create().await?;
 
-    let from = inspect_backend(cfg, &mut db, from).await?;
-    let to = inspect_backend(cfg, &mut db, to).await?;
+    let options = inspect_options(cfg, args.with_owners, args.without_owners);
+    let from = inspect_backend(cfg, &mut db, from, &options).await?;
+    let to = inspect_backend(cfg, &mut db, to, &options).await?;
 
+    let mut diff_options = cfg.diff.diff_options();
+    if args.online_constraints {
+        diff_options.online_constraints = true;
+    }
+    if args.concurrent_indexes {
+        diff_options.concurrent_indexes = true;
+    }
+    diff_options.type_casts = tusker_schema::inspect_type_casts(&db.connect().await?).await?;
     let diff = from.diff(&to);
-    println!("{}", join_sql(diff.sql()));
+    // Column order drift can't be fixed by the migration. It is reported
+    // on stderr so the output only contains the changes.
+    for (a, b) in from.column_order_drift(&to) {
+        eprintln!("Warning: {}", Table::column_order_warning(a, b));
+    }
+    match args.format {
+        Format::Text => {
+            let sql = diff.sql_with(&diff_options);
+            if sql
+                .iter()
+                .any(|(change_type, _)| change_type.is_non_transactional())
+            {
+                println!("{}\n", NO_TRANSACTION_MARKER);
+            }
+            println!("{}", join_sql(sql));
+        }
+        Format::Json => {
+            let changes = diff.changes(&diff_options);
+            println!("{}", serde_json::to_string_pretty(&changes)?);
+        }
+    }
 
     // XXX it would be nice if this was an actual drop guard
     db.drop().await?;
@@ -55,3 +55,21 @@ impl std::fmt::Display for Backend {
             .fmt(f)
     }
 }
+
+#[derive(ValueEnum, Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum Format {
+    /// SQL statements
+    #[default]
+    Text,
+    /// List of changes including the object they belong to
+    Json,
+}
+
+impl std::fmt::Display for Format {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.to_possible_value()
+            .expect("no values are skipped")
+            .get_name()
+            .fmt(f)
+    }
+}
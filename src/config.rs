@@ -1,8 +1,23 @@
-use std::str::FromStr;
+use std::{
+    collections::{BTreeMap, HashMap},
+    str::FromStr,
+    time::Duration,
+};
 
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
 use tokio_postgres::{Client as PgClient, Config as PgConfig, NoTls};
+use tusker_migration::{
+    cli::Options as MigrationOptions,
+    db::Timeouts,
+    lint::{Level, LintOptions, Rule},
+    table::MigrationTable,
+};
+use tusker_schema::{
+    diff::DiffOptions,
+    filter::{ObjectFilter, ObjectPattern},
+    InspectOptions,
+};
 use uzers::get_current_username;
 
 #[derive(Debug, Default, Serialize, Deserialize)]
@@ -55,10 +70,26 @@ impl Config {
             },
             migrations: MigrationsConfig {
                 filename: default_migrations_filename(),
+                lint: Rule::ALL
+                    .into_iter()
+                    .map(|rule| (rule, rule.default_level()))
+                    .collect(),
+                lock_timeout: Some("5s".into()),
+                statement_timeout: Some("0".into()),
+                lock_retries: 0,
+                lock_retry_delay_ms: default_lock_retry_delay_ms(),
+                table: MigrationTable::default(),
             },
             diff: DiffConfig {
                 privileges: default_diff_privileges(),
                 safe: default_diff_safe(),
+                owners: default_diff_owners(),
+                roles: HashMap::new(),
+                online_constraints: false,
+                concurrent_indexes: false,
+                strict_column_order: false,
+                include: Vec::new(),
+                exclude: Vec::new(),
             },
             queries: QueriesConfig {
                 filename: default_queries_filename(),
@@ -113,6 +144,33 @@ pub struct DiffConfig {
     pub safe: bool,
     #[serde(default = "default_diff_privileges")]
     pub privileges: bool,
+    /// Compare object owners (ie. `ALTER ... OWNER TO` statements)
+    #[serde(default = "default_diff_owners")]
+    pub owners: bool,
+    /// Map local role names to the role names used in deployed databases
+    #[serde(default)]
+    pub roles: HashMap<String, String>,
+    /// Add foreign keys and check constraints as `NOT VALID` and
+    /// validate them separately. Migrations using this are marked as
+    /// non-transactional.
+    #[serde(default)]
+    pub online_constraints: bool,
+    /// Create and drop indexes concurrently. Migrations using this are
+    /// marked as non-transactional.
+    #[serde(default)]
+    pub concurrent_indexes: bool,
+    /// Let `tusker check` fail if the column order of a table differs.
+    /// By default this is only reported as a warning.
+    #[serde(default)]
+    pub strict_column_order: bool,
+    /// Only inspect objects matching these `[kind:]schema[.name]` glob
+    /// patterns. All objects are inspected if empty.
+    #[serde(default)]
+    pub include: Vec<ObjectPattern>,
+    /// Never inspect objects matching these `[kind:]schema[.name]` glob
+    /// patterns.
+    #[serde(default)]
+    pub exclude: Vec<ObjectPattern>,
 }
 
 fn default_diff_safe() -> bool {
@@ -123,11 +181,42 @@ fn default_diff_privileges() -> bool {
     true
 }
 
+fn default_diff_owners() -> bool {
+    false
+}
+
 impl Default for DiffConfig {
     fn default() -> Self {
         Self {
             safe: default_diff_safe(),
             privileges: default_diff_privileges(),
+            owners: default_diff_owners(),
+            roles: HashMap::new(),
+            online_constraints: false,
+            concurrent_indexes: false,
+            strict_column_order: false,
+            include: Vec::new(),
+            exclude: Vec::new(),
+        }
+    }
+}
+
+impl DiffConfig {
+    pub fn inspect_options(&self) -> InspectOptions {
+        InspectOptions {
+            owners: self.owners,
+            roles: self.roles.clone(),
+            filter: ObjectFilter {
+                include: self.include.clone(),
+                exclude: self.exclude.clone(),
+            },
+        }
+    }
+    pub fn diff_options(&self) -> DiffOptions {
+        DiffOptions {
+            online_constraints: self.online_constraints,
+            concurrent_indexes: self.concurrent_indexes,
+            ..Default::default()
         }
     }
 }
@@ -154,16 +243,64 @@ impl Default for SchemaConfig {
 pub struct MigrationsConfig {
     #[serde(default = "default_migrations_filename")]
     pub filename: String,
+    /// Levels of the `tusker migration lint` rules. Rules which are not
+    /// listed use their default level.
+    #[serde(default)]
+    pub lint: BTreeMap<Rule, Level>,
+    /// `lock_timeout` set while running a migration, e.g. `"5s"`
+    pub lock_timeout: Option<String>,
+    /// `statement_timeout` set while running a migration, e.g. `"1min"`
+    pub statement_timeout: Option<String>,
+    /// Number of retries of a migration which could not acquire a lock
+    /// within the `lock_timeout`
+    #[serde(default)]
+    pub lock_retries: u32,
+    /// Delay before retrying a migration. It is doubled for every further
+    /// retry.
+    #[serde(default = "default_lock_retry_delay_ms")]
+    pub lock_retry_delay_ms: u64,
+    /// Names and schema of the table storing the migration log
+    #[serde(default)]
+    pub table: MigrationTable,
 }
 
 fn default_migrations_filename() -> String {
     "db/migrations/**/*.sql".into()
 }
 
+fn default_lock_retry_delay_ms() -> u64 {
+    1000
+}
+
 impl Default for MigrationsConfig {
     fn default() -> Self {
         Self {
             filename: default_migrations_filename(),
+            lint: BTreeMap::new(),
+            lock_timeout: None,
+            statement_timeout: None,
+            lock_retries: 0,
+            lock_retry_delay_ms: default_lock_retry_delay_ms(),
+            table: MigrationTable::default(),
+        }
+    }
+}
+
+impl MigrationsConfig {
+    pub fn options(&self) -> MigrationOptions {
+        MigrationOptions {
+            lint: LintOptions {
+                levels: self.lint.clone().into_iter().collect(),
+                lock_timeout: self.lock_timeout.clone(),
+            },
+            timeouts: Timeouts {
+                lock_timeout: self.lock_timeout.clone(),
+                statement_timeout: self.statement_timeout.clone(),
+                lock_retries: self.lock_retries,
+                lock_retry_delay: Duration::from_millis(self.lock_retry_delay_ms),
+            },
+            table: self.table.clone(),
+            version: env!("CARGO_PKG_VERSION").into(),
         }
     }
 }
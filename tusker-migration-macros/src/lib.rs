@@ -0,0 +1,69 @@
+use std::env;
+use std::ffi::OsStr;
+use std::fs::read_dir;
+use std::path::{Path, PathBuf};
+
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::LitStr;
+
+fn migration_paths(dir: &Path) -> std::io::Result<Vec<PathBuf>> {
+    let mut paths = Vec::new();
+    for entry in read_dir(dir)? {
+        let path = entry?.path();
+        if path.extension() == Some(OsStr::new("sql")) {
+            paths.push(path);
+        }
+    }
+    paths.sort();
+    Ok(paths)
+}
+
+/// Embed the `.sql` files of a migrations directory into the binary. The
+/// directory is relative to the `Cargo.toml` of the crate using the
+/// macro. The result is a `&'static [EmbeddedMigration]`.
+///
+/// Changes of the files cause a rebuild but adding or removing files
+/// doesn't. Touch the source file calling the macro in that case.
+#[proc_macro]
+pub fn embed_migrations(input: TokenStream) -> TokenStream {
+    let dir: LitStr = match syn::parse(input) {
+        Ok(dir) => dir,
+        Err(e) => return e.to_compile_error().into(),
+    };
+    let Ok(manifest_dir) = env::var("CARGO_MANIFEST_DIR") else {
+        return syn::Error::new(dir.span(), "CARGO_MANIFEST_DIR not set")
+            .to_compile_error()
+            .into();
+    };
+    let path = Path::new(&manifest_dir).join(dir.value());
+    let paths = match migration_paths(&path) {
+        Ok(paths) => paths,
+        Err(e) => {
+            let message = format!(
+                "Unable to read migrations directory {:?}: {}",
+                path.display(),
+                e
+            );
+            return syn::Error::new(dir.span(), message)
+                .to_compile_error()
+                .into();
+        }
+    };
+    let migrations = paths.iter().map(|path| {
+        let filename = path.file_name().unwrap().to_string_lossy();
+        let path = path.to_string_lossy();
+        quote! {
+            ::tusker_migration::file::EmbeddedMigration {
+                filename: #filename,
+                sql: include_str!(#path),
+            }
+        }
+    });
+    quote! {
+        &[
+            #( #migrations ),*
+        ]
+    }
+    .into()
+}
@@ -1,7 +1,7 @@
 SELECT number,
     name,
     hash
-FROM migration
+FROM {migration}
 WHERE now() <@ validity
     AND operation != 'delete'
 ORDER BY number;
@@ -1,10 +1,10 @@
 WITH closed AS (
-    UPDATE migration
+    UPDATE {migration}
     SET validity = tstzrange(lower(validity), now())
     WHERE now() <@ validity
-      AND migration.number = $1
+      AND number = $1
     RETURNING number, name
 )
-INSERT INTO migration (number, name, hash, operation)
-SELECT number, name, NULL, 'delete'
+INSERT INTO {migration} (number, name, hash, operation, hostname, version, comment)
+SELECT number, name, NULL, 'delete', $2, $3, $4
 FROM closed;
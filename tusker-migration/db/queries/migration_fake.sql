@@ -1,2 +1,2 @@
-INSERT INTO migration (number, name, hash, operation)
-VALUES ($1, $2, $3, 'fake');
+INSERT INTO {migration} (number, name, hash, operation, hostname, version, comment)
+VALUES ($1, $2, $3, 'fake', $4, $5, $6);
@@ -1,2 +1,2 @@
-INSERT INTO migration (number, name, hash, operation)
-VALUES ($1, $2, $3, 'apply');
+INSERT INTO {migration} (number, name, hash, operation, duration, hostname, version, comment)
+VALUES ($1, $2, $3, 'apply', make_interval(secs => $4), $5, $6, $7);
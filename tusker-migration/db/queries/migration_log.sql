@@ -1,6 +1,11 @@
 SELECT number,
     name,
     lower(validity) AS timestamp,
-    operation::text
-FROM migration
+    operation::text,
+    extract(epoch FROM duration)::float8 AS duration,
+    username,
+    hostname,
+    version,
+    comment
+FROM {migration}
 ORDER BY timestamp;
@@ -1,8 +1,10 @@
 WITH closed AS (
-    UPDATE migration
+    UPDATE {migration}
     SET validity = tstzrange(lower(validity), now())
     WHERE now() <@ validity
-      AND migration.number = $1
+      AND number = $1
+    RETURNING number
 )
-INSERT INTO migration (number, name, hash, operation)
-VALUES ($1, $2, $3, 'update');
+INSERT INTO {migration} (number, name, hash, operation, hostname, version, comment)
+SELECT number, $2, $3, 'update', $4, $5, $6
+FROM closed;
@@ -1,14 +1,19 @@
 use std::io::Write;
-use std::path::{Path, PathBuf};
+use std::path::PathBuf;
 
 use clap::{Args, Subcommand};
 use termcolor::{Color, ColorChoice, ColorSpec, StandardStream, WriteColor};
 use tokio_postgres::Config;
 
-use crate::db::Database;
+use crate::db::{Database, LogInfo, TableUpgrade, Timeouts};
 use crate::error::Error;
 use crate::file::load_migration_files;
-use crate::models::{combine_migrations, Migration, MigrationStatus};
+use crate::lint::{lint, Level, LintOptions};
+use crate::migrator::{
+    migration_table_exists, pending, pending_table_upgrades, prepare_table, upgrade_table, Migrator,
+};
+use crate::models::MigrationStatus;
+use crate::table::MigrationTable;
 
 #[derive(Debug, Args)]
 #[clap(about = "Manage database migrations")]
@@ -17,6 +22,17 @@ pub struct Command {
     subcommand: Subcommands,
 }
 
+/// Settings which are not passed as arguments but read from the
+/// configuration file
+#[derive(Debug, Clone, Default)]
+pub struct Options {
+    pub lint: LintOptions,
+    pub timeouts: Timeouts,
+    pub table: MigrationTable,
+    /// Version stored in the migration log
+    pub version: String,
+}
+
 #[derive(Debug, Args)]
 pub struct MigrationArgs {
     #[clap(
@@ -42,6 +58,9 @@ pub enum Subcommands {
 
     Check(MigrationArgs),
 
+    #[clap(about = "Check migration files for risky operations")]
+    Lint(MigrationArgs),
+
     #[clap(about = "Fix database migration")]
     Fix(FixArgs),
 }
@@ -62,6 +81,8 @@ pub struct RunArgs {
         help = "Number of the migration to be run. If no number is provided all outstanding migrations are run."
     )]
     number: Option<i32>,
+    #[clap(long, help = "Comment stored in the migration log")]
+    comment: Option<String>,
 }
 
 #[derive(Debug, Args)]
@@ -76,6 +97,8 @@ pub struct FixArgs {
     migrations_dir: PathBuf,
     #[clap(value_name = "NUMBER")]
     number: i32,
+    #[clap(long, help = "Comment stored in the migration log")]
+    comment: Option<String>,
 }
 
 struct Colors {
@@ -122,40 +145,29 @@ impl Colors {
     }
 }
 
-async fn migration_table_exists(db: &Database) -> Result<bool, Error> {
-    db.migration_table_exists()
-        .await
-        .map_err(|e| Error::Pg("Checking status table failed".into(), e))
-}
-
-async fn load_migrations(db: &Database, dir: &Path) -> Result<Vec<Migration>, Error> {
-    let migration_files = load_migration_files(dir)?;
-    let db_migrations = match migration_table_exists(db).await? {
-        true => db
-            .get_migrations()
-            .await
-            .map_err(|e| Error::Pg("Unable to load already applied migrations".into(), e))?,
-        false => Vec::new(),
-    };
-    Ok(combine_migrations(&migration_files, &db_migrations))
-}
-
-pub async fn cmd(pg_config: &Config, cmd: &Command) -> Result<(), Error> {
+pub async fn cmd(pg_config: &Config, options: &Options, cmd: &Command) -> Result<(), Error> {
     match &cmd.subcommand {
-        Subcommands::Status(args) => status(pg_config, args).await?,
-        Subcommands::Log => log(pg_config).await?,
-        Subcommands::Check(args) => check(pg_config, args).await?,
-        Subcommands::Run(args) => run(pg_config, args).await?,
-        Subcommands::Fix(args) => fix(pg_config, args).await?,
+        Subcommands::Status(args) => status(pg_config, options, args).await?,
+        Subcommands::Log => log(pg_config, options).await?,
+        Subcommands::Check(args) => check(pg_config, options, args).await?,
+        Subcommands::Lint(args) => lint_migrations(&options.lint, args)?,
+        Subcommands::Run(args) => run(pg_config, options, args).await?,
+        Subcommands::Fix(args) => fix(pg_config, options, args).await?,
     }
     Ok(())
 }
 
-pub async fn status(pg_config: &Config, args: &MigrationArgs) -> Result<(), Error> {
+pub async fn status(
+    pg_config: &Config,
+    options: &Options,
+    args: &MigrationArgs,
+) -> Result<(), Error> {
     let mut stdout = StandardStream::stdout(ColorChoice::Always);
     let colors = Colors::new();
-    let db = Database::connect(pg_config).await?;
-    let migrations = load_migrations(&db, &args.migrations_dir).await?;
+    let db = Database::connect(pg_config, options.table.clone()).await?;
+    let migrations = Migrator::from_dir(&args.migrations_dir)?
+        .status(&db)
+        .await?;
     if !migration_table_exists(&db).await? {
         writeln!(
             &mut stdout,
@@ -216,21 +228,35 @@ pub async fn status(pg_config: &Config, args: &MigrationArgs) -> Result<(), Erro
     Ok(())
 }
 
-pub async fn log(pg_config: &Config) -> Result<(), Error> {
+pub async fn log(pg_config: &Config, options: &Options) -> Result<(), Error> {
     let mut stdout = StandardStream::stdout(ColorChoice::Always);
-    let db = Database::connect(pg_config).await?;
+    let db = Database::connect(pg_config, options.table.clone()).await?;
     let colors = Colors::new();
+    // Upgrading the table is left to `run` and `fix` which hold the lock
+    for upgrade in pending_table_upgrades(&db).await? {
+        match upgrade {
+            TableUpgrade::ChangedConstraint(constraint) => eprintln!(
+                "The constraint of the migration table will be changed to {} by the next run.",
+                constraint
+            ),
+            _ => {
+                return Err(Error::Misc(
+                    "The migration table is outdated. Run the migrations to upgrade it.".into(),
+                ))
+            }
+        }
+    }
     let log = db
         .get_migration_log()
         .await
         .map_err(|e| Error::Pg("Error fetching migration log".into(), e))?;
     writeln!(
         &mut stdout,
-        "Timestamp                         Operation    # Name                            "
+        "Timestamp                         Operation    # Name                             Duration  User@Host               Version"
     )?;
     writeln!(
         &mut stdout,
-        "---------------------------------------------------------------------------------"
+        "-------------------------------------------------------------------------------------------------------------------------"
     )?;
     for log_entry in log {
         write!(stdout, "{} ", log_entry.timestamp)?;
@@ -253,17 +279,44 @@ pub async fn log(pg_config: &Config) -> Result<(), Error> {
         stdout.reset()?;
         write!(stdout, "{:04} ", log_entry.number)?;
         stdout.set_color(&colors.bold)?;
-        writeln!(stdout, "{} ", log_entry.name)?;
+        write!(stdout, "{:32} ", log_entry.name)?;
         stdout.reset()?;
+        let duration = log_entry
+            .duration
+            .map(|d| format!("{:.3}s", d.as_secs_f64()))
+            .unwrap_or_default();
+        let user = match (&log_entry.username, &log_entry.hostname) {
+            (Some(username), Some(hostname)) => format!("{}@{}", username, hostname),
+            (Some(username), None) => username.clone(),
+            (None, Some(hostname)) => format!("@{}", hostname),
+            (None, None) => String::new(),
+        };
+        write!(
+            stdout,
+            "{:>9} {:23} {}",
+            duration,
+            user,
+            log_entry.version.as_deref().unwrap_or_default()
+        )?;
+        writeln!(stdout)?;
+        if !log_entry.comment.is_empty() {
+            writeln!(stdout, "    {}", log_entry.comment)?;
+        }
     }
     Ok(())
 }
 
-pub async fn check(pg_config: &Config, args: &MigrationArgs) -> Result<(), Error> {
+pub async fn check(
+    pg_config: &Config,
+    options: &Options,
+    args: &MigrationArgs,
+) -> Result<(), Error> {
     let mut stdout = StandardStream::stdout(ColorChoice::Always);
-    let db = Database::connect(pg_config).await?;
+    let db = Database::connect(pg_config, options.table.clone()).await?;
     let colors = Colors::new();
-    let migrations = load_migrations(&db, &args.migrations_dir).await?;
+    let migrations = Migrator::from_dir(&args.migrations_dir)?
+        .status(&db)
+        .await?;
     for migration in migrations {
         if let MigrationStatus::Ok(_, _) = migration.get_status() {
             continue;
@@ -283,51 +336,73 @@ pub async fn check(pg_config: &Config, args: &MigrationArgs) -> Result<(), Error
     Ok(())
 }
 
-pub async fn run(pg_config: &Config, args: &RunArgs) -> Result<(), Error> {
+pub fn lint_migrations(options: &LintOptions, args: &MigrationArgs) -> Result<(), Error> {
     let mut stdout = StandardStream::stdout(ColorChoice::Always);
-    let db = Database::connect(pg_config).await?;
     let colors = Colors::new();
-    let migrations = load_migrations(&db, &args.migrations_dir).await?;
-    if !migration_table_exists(&db).await? {
-        writeln!(stdout, "Creating migration table...")?;
-        db.init()
-            .await
-            .map_err(|e| Error::Pg("Unable to create migration table".into(), e))?;
+    let mut migration_files = load_migration_files(&args.migrations_dir)?;
+    migration_files.sort_by_key(|m| m.number);
+    let mut errors = 0;
+    for migration_file in migration_files {
+        let sql = migration_file.read()?;
+        for finding in lint(&sql, options) {
+            stdout.set_color(&colors.bold)?;
+            write!(
+                stdout,
+                "{}:{}: ",
+                migration_file.path.display(),
+                finding.line
+            )?;
+            match finding.level {
+                Level::Error => {
+                    errors += 1;
+                    stdout.set_color(&colors.error)?;
+                }
+                _ => stdout.set_color(&colors.modified)?,
+            }
+            write!(stdout, "{}[{}]", finding.level, finding.rule)?;
+            stdout.reset()?;
+            writeln!(stdout, ": {}", finding.message)?;
+        }
+    }
+    if errors > 0 {
+        writeln!(stdout, "{} error(s) found", errors)?;
+        std::process::exit(1);
+    }
+    stdout.set_color(&colors.ok)?;
+    writeln!(stdout, "No errors found")?;
+    stdout.reset()?;
+    Ok(())
+}
+
+pub async fn run(pg_config: &Config, options: &Options, args: &RunArgs) -> Result<(), Error> {
+    let mut stdout = StandardStream::stdout(ColorChoice::Always);
+    let db = Database::connect(pg_config, options.table.clone()).await?;
+    let colors = Colors::new();
+    let migrator = Migrator {
+        timeouts: options.timeouts.clone(),
+        ..Migrator::from_dir(&args.migrations_dir)?
+    };
+    // The lock is released once the connection is closed.
+    db.lock()
+        .await
+        .map_err(|e| Error::Pg("Unable to lock migration table".into(), e))?;
+    for upgrade in prepare_table(&db).await? {
+        writeln!(stdout, "{}", upgrade)?;
     }
-    // FIXME make sure there are no modified or missing migration files first (!)
+    let migrations = migrator.status(&db).await?;
+    let info = LogInfo::new(&options.version, args.comment.as_deref());
     // FIXME add support for running only a specific migration
     //let number = matches.value_of("number").map(|s| s.parse::<i32>());
     //println!("NUMBER={:?}", number);
-    for migration in migrations.iter() {
-        match migration.get_status() {
-            MigrationStatus::Ok(_, _) => {}
-            MigrationStatus::Mismatch(_, _) => {
-                return Err(Error::Misc(
-                    "Migration file mismatch found. See `status` for more details".into(),
-                ));
-            }
-            MigrationStatus::NotApplied(migration_file) => {
-                write!(stdout, "Applying migration {}: ", migration_file.number)?;
-                stdout.set_color(&colors.bold)?;
-                write!(stdout, "{}", migration_file.name)?;
-                stdout.reset()?;
-                writeln!(stdout)?;
-                let sql = migration_file.read()?;
-                db.apply_migration(migration_file, sql.as_str())
-                    .await
-                    .map_err(|e| {
-                        Error::Pg(
-                            format!("Applying migration file {:?} failed", migration_file.path),
-                            e,
-                        )
-                    })?;
-            }
-            MigrationStatus::FileMissing(_) => {
-                return Err(Error::Misc(
-                    "Migration file missing. See `status` for more details".into(),
-                ));
-            }
-        }
+    for migration_file in pending(&migrations)? {
+        write!(stdout, "Applying migration {}: ", migration_file.number)?;
+        stdout.set_color(&colors.bold)?;
+        write!(stdout, "{}", migration_file.name)?;
+        stdout.reset()?;
+        writeln!(stdout)?;
+        migrator
+            .apply_with(&db, migration_file, &info, &|retry| eprintln!("{}", retry))
+            .await?;
     }
     stdout.set_color(&colors.ok)?;
     writeln!(stdout, "Done.")?;
@@ -335,9 +410,19 @@ pub async fn run(pg_config: &Config, args: &RunArgs) -> Result<(), Error> {
     Ok(())
 }
 
-pub async fn fix(pg_config: &Config, args: &FixArgs) -> Result<(), Error> {
-    let db = Database::connect(pg_config).await?;
-    let migrations = load_migrations(&db, &args.migrations_dir).await?;
+pub async fn fix(pg_config: &Config, options: &Options, args: &FixArgs) -> Result<(), Error> {
+    let db = Database::connect(pg_config, options.table.clone()).await?;
+    // The lock is released once the connection is closed.
+    db.lock()
+        .await
+        .map_err(|e| Error::Pg("Unable to lock migration table".into(), e))?;
+    for upgrade in upgrade_table(&db).await? {
+        println!("{}", upgrade);
+    }
+    let info = LogInfo::new(&options.version, args.comment.as_deref());
+    let migrations = Migrator::from_dir(&args.migrations_dir)?
+        .status(&db)
+        .await?;
     let index = migrations.binary_search_by_key(&args.number, |m| m.number);
     let Ok(index) = index else {
         return Err(Error::Misc(format!(
@@ -354,18 +439,18 @@ pub async fn fix(pg_config: &Config, args: &FixArgs) -> Result<(), Error> {
             )));
         }
         MigrationStatus::Mismatch(migration_file, _) => {
-            db.update_migration(migration_file)
+            db.update_migration(migration_file, &info)
                 .await
                 .map_err(|e| Error::Pg("Fixing migration failed".into(), e))?;
         }
         MigrationStatus::NotApplied(migration_file) => {
             // XXX should this be a separate command?
-            db.fake_migration(migration_file)
+            db.fake_migration(migration_file, &info)
                 .await
                 .map_err(|e| Error::Pg("Fixing migration failed".into(), e))?;
         }
         MigrationStatus::FileMissing(migration_file) => {
-            db.remove_migration(migration_file.number)
+            db.remove_migration(migration_file.number, &info)
                 .await
                 .map_err(|e| Error::Pg("Fixing migration failed".into(), e))?;
         }
@@ -1,46 +1,328 @@
 use std::error::Error as StdError;
+use std::fmt;
+use std::time::{Duration, Instant};
 
 use time::OffsetDateTime;
 use tokio_postgres::{
-    error::{DbError, ErrorPosition as PgErrorPosition},
+    error::{DbError, ErrorPosition as PgErrorPosition, SqlState},
     Error as PgError,
 };
-use tusker_query::query;
+use tusker_query::{FromRow, Query};
 
 use crate::error::Error;
 use crate::file::MigrationFile;
 use crate::queries;
+use crate::sql::{has_transaction_control, is_concurrent, is_transactional, split_statements};
+use crate::table::{quote_ident, MigrationTable, ValidityConstraint};
 
 pub struct Database {
     pub client: tokio_postgres::Client,
+    pub table: MigrationTable,
+}
+
+/// Timeouts used while applying migrations. Without them a migration
+/// waiting for a lock blocks all other queries on the table.
+#[derive(Debug, Clone, Default)]
+pub struct Timeouts {
+    /// Value of `lock_timeout`, e.g. `5s`
+    pub lock_timeout: Option<String>,
+    /// Value of `statement_timeout`, e.g. `1min`
+    pub statement_timeout: Option<String>,
+    /// Number of retries if a lock could not be acquired within the
+    /// `lock_timeout`
+    pub lock_retries: u32,
+    /// Delay before the first retry. It is doubled for every further retry.
+    pub lock_retry_delay: Duration,
+}
+
+/// Information stored along with every entry of the migration log
+#[derive(Debug, Clone, Default)]
+pub struct LogInfo {
+    /// Host name of the machine running the migrations
+    pub hostname: String,
+    /// Version of the application running the migrations, e.g. the
+    /// version of tusker or of a service using the `Migrator`
+    pub version: String,
+    pub comment: String,
+}
+
+impl LogInfo {
+    pub fn new(version: &str, comment: Option<&str>) -> Self {
+        Self {
+            hostname: whoami::hostname().unwrap_or_default(),
+            version: version.into(),
+            comment: comment.unwrap_or_default().into(),
+        }
+    }
+}
+
+impl Timeouts {
+    /// `SET` statements for the configured timeouts. `SET LOCAL` only
+    /// affects the current transaction.
+    fn set_sql(&self, local: bool) -> String {
+        [
+            ("lock_timeout", &self.lock_timeout),
+            ("statement_timeout", &self.statement_timeout),
+        ]
+        .into_iter()
+        .filter_map(|(name, value)| {
+            value.as_ref().map(|value| {
+                format!(
+                    "SET {}{} = '{}'; ",
+                    if local { "LOCAL " } else { "" },
+                    name,
+                    value.replace('\'', "''")
+                )
+            })
+        })
+        .collect()
+    }
+}
+
+/// Retry of a migration which failed to acquire a lock within the
+/// `lock_timeout`
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct LockRetry {
+    /// Number of this retry starting at 1
+    pub retry: u32,
+    pub lock_retries: u32,
+    /// Time waited before retrying
+    pub delay: Duration,
+}
+
+impl fmt::Display for LockRetry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Lock not available. Retry {} of {} in {:?}...",
+            self.retry, self.lock_retries, self.delay
+        )
+    }
+}
+
+/// Change made to the migration table before running migrations
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum TableUpgrade {
+    Created,
+    /// The table was moved from the given schema to the configured
+    /// location
+    Moved(String),
+    /// Columns added by later versions were missing
+    AddedColumns,
+    ChangedConstraint(ValidityConstraint),
+}
+
+impl fmt::Display for TableUpgrade {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Created => write!(f, "Created migration table"),
+            Self::Moved(schema) => write!(f, "Moved migration table from {}.migration", schema),
+            Self::AddedColumns => write!(f, "Added missing columns to migration table"),
+            Self::ChangedConstraint(constraint) => write!(
+                f,
+                "Changed the constraint of the migration table to {}",
+                constraint
+            ),
+        }
+    }
 }
 
 impl Database {
-    pub async fn connect(pg_config: &tokio_postgres::Config) -> Result<Database, Error> {
+    pub fn new(client: tokio_postgres::Client, table: MigrationTable) -> Database {
+        Database { client, table }
+    }
+    pub async fn connect(
+        pg_config: &tokio_postgres::Config,
+        table: MigrationTable,
+    ) -> Result<Database, Error> {
         let (client, connection) = pg_config
             .connect(tokio_postgres::NoTls)
             .await
             .map_err(|e| Error::Pg("Unable to connect to database".into(), e))?;
         tokio::spawn(connection);
-        Ok(Database { client })
+        Ok(Database::new(client, table))
+    }
+    /// Run a query after replacing the names of the migration table
+    async fn query<Q: Query>(&self, query: Q) -> Result<Vec<Q::Row>, PgError> {
+        let stmt = self.client.prepare(&self.table.format(Q::SQL)).await?;
+        let rows = self.client.query(&stmt, &query.as_params()).await?;
+        Ok(rows.into_iter().map(Q::Row::from_row).collect())
     }
     pub async fn migration_table_exists(&self) -> Result<bool, PgError> {
-        let stmt = self
+        let row = self
+            .client
+            .query_one(
+                "SELECT to_regclass($1)::bigint",
+                &[&self.table.qualified_name()],
+            )
+            .await?;
+        Ok(row.get::<_, Option<i64>>(0).is_some())
+    }
+    /// Wait until no other session runs migrations using the same
+    /// migration table. The session level advisory lock is held until
+    /// `unlock` is called or the connection is closed.
+    pub async fn lock(&self) -> Result<(), PgError> {
+        self.client
+            .execute(
+                "SELECT pg_advisory_lock(hashtext($1))",
+                &[&self.table.qualified_name()],
+            )
+            .await
+            .map(|_| ())
+    }
+    pub async fn unlock(&self) -> Result<(), PgError> {
+        self.client
+            .execute(
+                "SELECT pg_advisory_unlock(hashtext($1))",
+                &[&self.table.qualified_name()],
+            )
+            .await
+            .map(|_| ())
+    }
+    /// Schema of a migration table using the default names which was
+    /// created before the names were configurable
+    pub async fn legacy_table_schema(&self) -> Result<Option<String>, PgError> {
+        let default = MigrationTable::default();
+        let row = self
             .client
-            .prepare("SELECT to_regclass('migration')::bigint")
+            .query_opt(
+                "SELECT n.nspname::text FROM pg_class c JOIN pg_namespace n ON n.oid = c.relnamespace WHERE c.oid = to_regclass($1) AND to_regtype(quote_ident(n.nspname) || '.' || $2) IS NOT NULL",
+                &[&default.qualified_name(), &quote_ident(&default.operation_type)],
+            )
             .await?;
-        let result = self.client.query(&stmt, &[]).await?;
-        Ok(match result.first() {
-            Some(row) => row.get::<_, Option<i64>>(0).is_some(),
-            None => false,
+        Ok(row.map(|row| row.get(0)))
+    }
+    /// Move the migration table with the default names from the given
+    /// schema to the configured location
+    pub async fn relocate(&self, legacy_schema: &str) -> Result<(), PgError> {
+        let legacy = MigrationTable {
+            schema: Some(legacy_schema.into()),
+            ..Default::default()
+        };
+        let schema = self.table.schema.as_deref().unwrap_or(legacy_schema);
+        let mut sql = String::from("BEGIN;\n");
+        if schema != legacy_schema {
+            sql += &format!(
+                "CREATE SCHEMA IF NOT EXISTS {schema};\n\
+                ALTER TABLE {table} SET SCHEMA {schema};\n\
+                ALTER TYPE {operation_type} SET SCHEMA {schema};\n",
+                schema = quote_ident(schema),
+                table = legacy.qualified_name(),
+                operation_type = legacy.qualified_operation_type(),
+            );
+        }
+        let moved = MigrationTable {
+            schema: Some(schema.into()),
+            ..Default::default()
+        };
+        for (object, old, new) in [
+            ("INDEX", &moved.index, &self.table.index),
+            ("TABLE", &moved.name, &self.table.name),
+            ("TYPE", &moved.operation_type, &self.table.operation_type),
+        ] {
+            if old != new {
+                sql += &format!(
+                    "ALTER {} {}.{} RENAME TO {};\n",
+                    object,
+                    quote_ident(schema),
+                    quote_ident(old),
+                    quote_ident(new)
+                );
+            }
+        }
+        sql += "COMMIT;";
+        self.client.simple_query(&sql).await.map(|_| ())
+    }
+    pub async fn btree_gist_installed(&self) -> Result<bool, PgError> {
+        let row = self
+            .client
+            .query_one(
+                "SELECT EXISTS (SELECT FROM pg_extension WHERE extname = 'btree_gist')",
+                &[],
+            )
+            .await?;
+        Ok(row.get(0))
+    }
+    /// Name of the exclusion constraint of the migration table
+    async fn exclusion_constraint(&self) -> Result<Option<String>, PgError> {
+        let row = self
+            .client
+            .query_opt(
+                "SELECT conname::text FROM pg_constraint WHERE conrelid = to_regclass($1) AND contype = 'x'",
+                &[&self.table.qualified_name()],
+            )
+            .await?;
+        Ok(row.map(|row| row.get(0)))
+    }
+    /// Constraint used by the existing migration table
+    pub async fn validity_constraint(&self) -> Result<ValidityConstraint, PgError> {
+        Ok(match self.exclusion_constraint().await? {
+            Some(_) => ValidityConstraint::Exclusion,
+            None => ValidityConstraint::UniqueIndex,
+        })
+    }
+    fn validity_constraint_sql(&self, constraint: ValidityConstraint) -> String {
+        self.table.format(match constraint {
+            ValidityConstraint::Exclusion => include_str!("exclusion.sql"),
+            ValidityConstraint::Auto | ValidityConstraint::UniqueIndex => {
+                include_str!("unique_index.sql")
+            }
         })
     }
     pub async fn init(&self) -> Result<(), PgError> {
-        let sql = include_str!("init.sql");
-        self.client.simple_query(sql).await.map(|_| ())
+        let constraint = match self.table.constraint {
+            ValidityConstraint::Auto if self.btree_gist_installed().await? => {
+                ValidityConstraint::Exclusion
+            }
+            ValidityConstraint::Auto => ValidityConstraint::UniqueIndex,
+            constraint => constraint,
+        };
+        let mut sql = String::from("BEGIN;\n");
+        if let Some(schema) = &self.table.schema {
+            sql += &format!("CREATE SCHEMA IF NOT EXISTS {};\n", quote_ident(schema));
+        }
+        sql += &self.table.format(include_str!("init.sql"));
+        sql += &self.validity_constraint_sql(constraint);
+        sql += "COMMIT;";
+        self.client.simple_query(&sql).await.map(|_| ())
+    }
+    /// Replace the exclusion constraint by a unique index or vice versa
+    pub async fn change_validity_constraint(
+        &self,
+        constraint: ValidityConstraint,
+    ) -> Result<(), PgError> {
+        let mut sql = String::from("BEGIN;\n");
+        if let Some(name) = self.exclusion_constraint().await? {
+            sql += &format!(
+                "ALTER TABLE {} DROP CONSTRAINT {};\n",
+                self.table.qualified_name(),
+                quote_ident(&name)
+            );
+        }
+        sql += &format!("DROP INDEX IF EXISTS {};\n", self.table.qualified_index());
+        sql += &self.validity_constraint_sql(constraint);
+        sql += "COMMIT;";
+        self.client.simple_query(&sql).await.map(|_| ())
+    }
+    /// Check if the migration table was created by an older version and
+    /// lacks some columns
+    pub async fn migration_table_outdated(&self) -> Result<bool, PgError> {
+        let row = self
+            .client
+            .query_one(
+                "SELECT count(*) FROM pg_attribute WHERE attrelid = to_regclass($1) AND attname = 'version' AND NOT attisdropped",
+                &[&self.table.qualified_name()],
+            )
+            .await?;
+        Ok(row.get::<_, i64>(0) == 0)
+    }
+    pub async fn upgrade(&self) -> Result<(), PgError> {
+        let sql = self.table.format(include_str!("upgrade.sql"));
+        self.client.simple_query(&sql).await.map(|_| ())
     }
     pub async fn get_migrations(&self) -> Result<Vec<DbMigration>, PgError> {
-        Ok(query(&self.client, queries::MigrationCurrent {})
+        Ok(self
+            .query(queries::MigrationCurrent {})
             .await?
             .iter()
             .map(|row| DbMigration {
@@ -51,7 +333,8 @@ impl Database {
             .collect())
     }
     pub async fn get_migration_log(&self) -> Result<Vec<DbMigrationLog>, PgError> {
-        Ok(query(&self.client, queries::MigrationLog {})
+        Ok(self
+            .query(queries::MigrationLog {})
             .await?
             .iter()
             .map(|row| DbMigrationLog {
@@ -59,58 +342,181 @@ impl Database {
                 name: row.name.clone(),
                 timestamp: row.timestamp,
                 operation: row.operation.clone(),
+                duration: row.duration.map(Duration::from_secs_f64),
+                username: row.username.clone(),
+                hostname: row.hostname.clone(),
+                version: row.version.clone(),
+                comment: row.comment.clone(),
             })
             .collect())
     }
-    pub async fn update_migration(&self, migration_file: &MigrationFile) -> Result<(), PgError> {
-        query(
-            &self.client,
-            queries::MigrationUpdate {
-                number: migration_file.number,
-                name: &migration_file.name,
-                hash: &migration_file.hash,
-            },
-        )
+    pub async fn update_migration(
+        &self,
+        migration_file: &MigrationFile,
+        info: &LogInfo,
+    ) -> Result<(), PgError> {
+        self.query(queries::MigrationUpdate {
+            number: migration_file.number,
+            name: &migration_file.name,
+            hash: &migration_file.hash,
+            hostname: &info.hostname,
+            version: &info.version,
+            comment: &info.comment,
+        })
         .await?;
         Ok(())
     }
+    /// Run SQL and retry it up to `lock_retries` times if a lock could
+    /// not be acquired in time. The SQL must be safe to be run again after
+    /// failing. Statements using `CONCURRENTLY` are never retried: a
+    /// failing `CREATE INDEX CONCURRENTLY` leaves an invalid index behind
+    /// which makes the retry fail, too. Returns the number of retries.
+    async fn execute_with_retry(
+        &self,
+        sql: &str,
+        lock_retries: u32,
+        timeouts: &Timeouts,
+        on_retry: &(dyn Fn(&LockRetry) + Sync),
+    ) -> Result<u32, PgError> {
+        let lock_retries = match is_concurrent(sql) {
+            true => 0,
+            false => lock_retries,
+        };
+        let mut retry = 0;
+        loop {
+            match self.client.simple_query(sql).await {
+                Err(e)
+                    if retry < lock_retries && e.code() == Some(&SqlState::LOCK_NOT_AVAILABLE) =>
+                {
+                    let delay = timeouts.lock_retry_delay.saturating_mul(1 << retry.min(16));
+                    retry += 1;
+                    on_retry(&LockRetry {
+                        retry,
+                        lock_retries,
+                        delay,
+                    });
+                    tokio::time::sleep(delay).await;
+                }
+                result => return result.map(|_| retry),
+            }
+        }
+    }
+    /// Run the statements of a non-transactional migration one by one
+    async fn execute_statements(
+        &self,
+        sql: &str,
+        lock_retries: u32,
+        timeouts: &Timeouts,
+        on_retry: &(dyn Fn(&LockRetry) + Sync),
+    ) -> Result<u32, PgError> {
+        let mut retries = 0;
+        for statement in split_statements(sql) {
+            retries += self
+                .execute_with_retry(statement, lock_retries, timeouts, on_retry)
+                .await?;
+        }
+        Ok(retries)
+    }
+    /// Apply a migration and add it to the migration log. `on_retry` is
+    /// called before a statement is retried after failing to acquire a
+    /// lock. Returns the duration and the number of retries.
+    ///
+    /// Migrations containing their own transaction control statements
+    /// like `BEGIN` and `COMMIT` are never retried as statements committed
+    /// before the failure would be run again. A failed transaction block
+    /// is rolled back so the session stays usable.
     pub async fn apply_migration(
         &self,
         migration_file: &MigrationFile,
         sql: &str,
-    ) -> Result<(), PgError> {
-        self.client.simple_query(sql).await.map(|_| ())?;
+        timeouts: &Timeouts,
+        info: &LogInfo,
+        on_retry: &(dyn Fn(&LockRetry) + Sync),
+    ) -> Result<(Duration, u32), PgError> {
+        let start = Instant::now();
+        let transaction_control = has_transaction_control(sql);
+        let lock_retries = match transaction_control {
+            true => 0,
+            false => timeouts.lock_retries,
+        };
+        let transactional = is_transactional(sql);
+        let session_sql = match transactional {
+            true => String::new(),
+            false => timeouts.set_sql(false),
+        };
+        let result = if transactional {
+            // The statements are run as one implicit transaction which
+            // the `SET LOCAL` statements are part of.
+            let sql = format!("{}{}", timeouts.set_sql(true), sql);
+            self.execute_with_retry(&sql, lock_retries, timeouts, on_retry)
+                .await
+        } else {
+            // Every statement is run in its own implicit transaction. A
+            // failing statement leaves the previous ones applied. Thus
+            // the timeouts are set for the session and only the failing
+            // statement is retried.
+            if !session_sql.is_empty() {
+                self.client.simple_query(&session_sql).await?;
+            }
+            self.execute_statements(sql, lock_retries, timeouts, on_retry)
+                .await
+        };
+        if result.is_err() && transaction_control {
+            // An error inside of an explicit transaction block leaves it
+            // aborted. Its own error is of no interest.
+            let _ = self.client.simple_query("ROLLBACK").await;
+        }
+        // The session is reused afterwards, so the timeouts are reset
+        // even if a statement failed.
+        if !session_sql.is_empty() {
+            self.client
+                .simple_query("RESET lock_timeout; RESET statement_timeout")
+                .await?;
+        }
+        let retries = result?;
+        let duration = start.elapsed();
         // log that migration has been run
-        query(
-            &self.client,
-            queries::MigrationInsert {
-                number: migration_file.number,
-                name: &migration_file.name,
-                hash: &migration_file.hash,
-            },
-        )
-        .await
-        .map(|_| ())
+        self.query(queries::MigrationInsert {
+            number: migration_file.number,
+            name: &migration_file.name,
+            hash: &migration_file.hash,
+            duration: duration.as_secs_f64(),
+            hostname: &info.hostname,
+            version: &info.version,
+            comment: &info.comment,
+        })
+        .await?;
+        Ok((duration, retries))
     }
-    pub async fn fake_migration(&self, migration_file: &MigrationFile) -> Result<(), PgError> {
-        query(
-            &self.client,
-            queries::MigrationFake {
-                number: migration_file.number,
-                name: &migration_file.name,
-                hash: &migration_file.hash,
-            },
-        )
+    pub async fn fake_migration(
+        &self,
+        migration_file: &MigrationFile,
+        info: &LogInfo,
+    ) -> Result<(), PgError> {
+        self.query(queries::MigrationFake {
+            number: migration_file.number,
+            name: &migration_file.name,
+            hash: &migration_file.hash,
+            hostname: &info.hostname,
+            version: &info.version,
+            comment: &info.comment,
+        })
         .await
         .map(|_| ())
     }
-    pub async fn remove_migration(&self, number: i32) -> Result<(), PgError> {
-        query(&self.client, queries::MigrationDelete { number }).await?;
+    pub async fn remove_migration(&self, number: i32, info: &LogInfo) -> Result<(), PgError> {
+        self.query(queries::MigrationDelete {
+            number,
+            hostname: &info.hostname,
+            version: &info.version,
+            comment: &info.comment,
+        })
+        .await?;
         Ok(())
     }
 }
 
-#[derive(Clone)]
+#[derive(Debug, Clone)]
 pub struct DbMigration {
     pub number: i32,
     pub name: String,
@@ -118,12 +524,19 @@ pub struct DbMigration {
     //applied: std::time::
 }
 
-#[derive(Clone)]
+#[derive(Debug, Clone)]
 pub struct DbMigrationLog {
     pub number: i32,
     pub name: String,
     pub timestamp: OffsetDateTime,
     pub operation: String,
+    /// Time it took to apply the migration
+    pub duration: Option<Duration>,
+    /// Database role which ran the operation
+    pub username: Option<String>,
+    pub hostname: Option<String>,
+    pub version: Option<String>,
+    pub comment: String,
 }
 
 pub fn to_sql_error(error: PgError, sql: &str) -> Error {
@@ -8,6 +8,10 @@ pub enum Error {
     Misc(String),
     Pg(String, PgError),
     Sql(String),
+    /// The file of an applied migration was changed or renamed
+    Mismatch(i32),
+    /// The file of an applied migration does no longer exist
+    FileMissing(i32),
 }
 
 impl fmt::Display for Error {
@@ -20,6 +24,14 @@ impl fmt::Display for Error {
                 Error::Misc(m) => m.to_string(),
                 Error::Pg(m, e) => format!("{}: {}", m, e),
                 Error::Sql(m) => m.to_string(),
+                Error::Mismatch(number) => format!(
+                    "Migration file mismatch found for migration {}. See `status` for more details",
+                    number
+                ),
+                Error::FileMissing(number) => format!(
+                    "Migration file missing for migration {}. See `status` for more details",
+                    number
+                ),
             }
         )
     }
@@ -0,0 +1,6 @@
+CREATE EXTENSION IF NOT EXISTS btree_gist;
+
+ALTER TABLE {migration}
+    ADD EXCLUDE USING GIST ("number" WITH =, "validity" WITH &&);
+
+CREATE INDEX {validity_idx} ON {migration} USING GIST ("number", "validity");
@@ -8,39 +8,69 @@ use sha2::{Digest, Sha512};
 
 use crate::error::Error;
 
-#[derive(Clone)]
+#[derive(Debug, Clone)]
 pub struct MigrationFile {
     pub path: PathBuf,
     pub number: i32,
     pub name: String,
     pub hash: Vec<u8>,
+    /// SQL of migrations compiled into the binary. Other migrations are
+    /// read from `path`.
+    pub sql: Option<&'static str>,
+}
+
+/// Migration compiled into the binary. A list of them is created by
+/// the `embed_migrations!` macro.
+#[derive(Debug, Clone, Copy)]
+pub struct EmbeddedMigration {
+    pub filename: &'static str,
+    pub sql: &'static str,
 }
 
 impl MigrationFile {
     pub fn from_path(path: &Path) -> Result<MigrationFile, Error> {
-        let file_stem = path
-            .file_stem()
-            .map(|name| name.to_string_lossy().into())
-            .ok_or_else(|| Error::Misc(format!("Invalid filename: {}", path.display())))?;
-        let (number, name) = parse_filename(file_stem)
-            .map_err(|m| Error::Misc(format!("Invalid filename {:?}: {}", path.display(), m)))?;
+        let (number, name) = parse_path(path)?;
         Ok(MigrationFile {
             path: PathBuf::from(&path),
             number,
             name,
             hash: calculate_hash(path)?,
+            sql: None,
+        })
+    }
+    pub fn from_embedded(migration: &EmbeddedMigration) -> Result<MigrationFile, Error> {
+        let path = PathBuf::from(migration.filename);
+        let (number, name) = parse_path(&path)?;
+        Ok(MigrationFile {
+            path,
+            number,
+            name,
+            hash: Sha512::digest(migration.sql.as_bytes()).to_vec(),
+            sql: Some(migration.sql),
         })
     }
     pub fn open(&self) -> io::Result<File> {
         File::open(&self.path)
     }
     pub fn read(&self) -> io::Result<String> {
+        if let Some(sql) = self.sql {
+            return Ok(sql.into());
+        }
         let mut sql = String::new();
         self.open()?.read_to_string(&mut sql)?;
         Ok(sql)
     }
 }
 
+fn parse_path(path: &Path) -> Result<(i32, String), Error> {
+    let file_stem = path
+        .file_stem()
+        .map(|name| name.to_string_lossy().into())
+        .ok_or_else(|| Error::Misc(format!("Invalid filename: {}", path.display())))?;
+    parse_filename(file_stem)
+        .map_err(|m| Error::Misc(format!("Invalid filename {:?}: {}", path.display(), m)))
+}
+
 fn parse_filename(filename: String) -> Result<(i32, String), String> {
     let v: Vec<&str> = filename.splitn(2, '_').collect();
     let number = v
@@ -70,9 +100,21 @@ fn calculate_hash(path: &Path) -> Result<Vec<u8>, Error> {
     Ok(hasher.finalize().to_vec())
 }
 
+fn check_numbers(migrations: &[MigrationFile]) -> Result<(), Error> {
+    let mut number_set: HashSet<i32> = HashSet::new();
+    for migration_file in migrations {
+        if !number_set.insert(migration_file.number) {
+            return Err(Error::Misc(format!(
+                "Migration folder contains multiple files for number {}",
+                migration_file.number
+            )));
+        }
+    }
+    Ok(())
+}
+
 pub fn load_migration_files(path: &Path) -> Result<Vec<MigrationFile>, Error> {
     let mut migrations: Vec<MigrationFile> = Vec::new();
-    let mut number_set: HashSet<i32> = HashSet::new();
     let dir_entries = read_dir(path).map_err(|e| {
         Error::Io(
             format!("Unable to read migrations directory {:?}", path.display()),
@@ -86,15 +128,19 @@ pub fn load_migration_files(path: &Path) -> Result<Vec<MigrationFile>, Error> {
             // skip files with an other extension than .sql
             continue;
         }
-        let migration_file = MigrationFile::from_path(&entry.path())?;
-        if number_set.contains(&migration_file.number) {
-            return Err(Error::Misc(format!(
-                "Migration folder contains multiple files for number {}",
-                migration_file.number
-            )));
-        }
-        number_set.insert(migration_file.number);
-        migrations.push(migration_file);
+        migrations.push(MigrationFile::from_path(&entry.path())?);
     }
+    check_numbers(&migrations)?;
+    Ok(migrations)
+}
+
+pub fn load_embedded_migrations(
+    embedded: &[EmbeddedMigration],
+) -> Result<Vec<MigrationFile>, Error> {
+    let migrations = embedded
+        .iter()
+        .map(MigrationFile::from_embedded)
+        .collect::<Result<Vec<_>, _>>()?;
+    check_numbers(&migrations)?;
     Ok(migrations)
 }
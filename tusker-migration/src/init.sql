@@ -1,24 +1,19 @@
-CREATE EXTENSION IF NOT EXISTS btree_gist;
-
-BEGIN;
-
-CREATE TYPE migration_operation AS ENUM (
+CREATE TYPE {migration_operation} AS ENUM (
     'apply',
     'fake',
     'update',
     'delete'
 );
 
-CREATE TABLE IF NOT EXISTS "migration" (
+CREATE TABLE IF NOT EXISTS {migration} (
     "number" INTEGER NOT NULL,
     "name" text NOT NULL DEFAULT '',
     "hash" bytea,
     "validity" tstzrange NOT NULL DEFAULT tstzrange(now(), NULL),
-    "operation" migration_operation NOT NULL DEFAULT 'apply',
+    "operation" {migration_operation} NOT NULL DEFAULT 'apply',
     "comment" text NOT NULL DEFAULT '',
-    EXCLUDE USING GIST ("number" WITH =, "validity" WITH &&)
+    "duration" interval,
+    "username" text DEFAULT current_user,
+    "hostname" text,
+    "version" text
 );
-
-CREATE INDEX "validity_idx" ON "migration" USING GIST ("number", "validity");
-
-END;
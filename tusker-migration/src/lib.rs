@@ -2,5 +2,11 @@ pub mod cli;
 pub mod db;
 pub mod error;
 pub mod file;
+pub mod lint;
+pub mod migrator;
 pub mod models;
 pub mod queries;
+pub mod sql;
+pub mod table;
+
+pub use tusker_migration_macros::embed_migrations;
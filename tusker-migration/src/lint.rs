@@ -0,0 +1,739 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+use sqlparser::ast::{
+    AlterColumnOperation, AlterTableOperation, ColumnOption, ObjectName, Statement,
+};
+use sqlparser::dialect::PostgreSqlDialect;
+use sqlparser::parser::Parser;
+
+use crate::sql::{split_statements, tokenize, Token};
+
+/// Comment suppressing rules for the statement it precedes, e.g.
+/// `-- tusker:lint-ignore confirm_drop_column`. Without rule names all rules are
+/// suppressed.
+pub const IGNORE_MARKER: &str = "-- tusker:lint-ignore";
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Ord, PartialOrd, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Rule {
+    /// `ADD COLUMN ... NOT NULL` without a default fails for tables
+    /// containing rows
+    NotNullWithoutDefault,
+    /// `CREATE INDEX` without `CONCURRENTLY` blocks writes until the
+    /// index is built
+    NonConcurrentIndex,
+    /// `ALTER COLUMN ... TYPE` usually rewrites the table while holding
+    /// an `ACCESS EXCLUSIVE` lock
+    AlterColumnType,
+    /// `ALTER COLUMN ... SET NOT NULL` scans the whole table while
+    /// holding an `ACCESS EXCLUSIVE` lock unless a validated `CHECK`
+    /// constraint proves the column contains no `NULL` values
+    SetNotNull,
+    /// Dropped columns break application code still referencing them.
+    /// The code can't be checked, so every `DROP COLUMN` is reported and
+    /// must be confirmed using `-- tusker:lint-ignore confirm_drop_column`
+    /// once no deployed code uses the column anymore.
+    ConfirmDropColumn,
+    /// Statements waiting for a lock block all other queries on the table
+    /// unless `lock_timeout` is set
+    MissingLockTimeout,
+    /// A statement which is checked by the other rules could not be
+    /// parsed. The rules are applied based on its keywords instead which
+    /// may miss some cases.
+    ParseError,
+}
+
+impl Rule {
+    pub const ALL: [Self; 7] = [
+        Self::NotNullWithoutDefault,
+        Self::NonConcurrentIndex,
+        Self::AlterColumnType,
+        Self::SetNotNull,
+        Self::ConfirmDropColumn,
+        Self::MissingLockTimeout,
+        Self::ParseError,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::NotNullWithoutDefault => "not_null_without_default",
+            Self::NonConcurrentIndex => "non_concurrent_index",
+            Self::AlterColumnType => "alter_column_type",
+            Self::SetNotNull => "set_not_null",
+            Self::ConfirmDropColumn => "confirm_drop_column",
+            Self::MissingLockTimeout => "missing_lock_timeout",
+            Self::ParseError => "parse_error",
+        }
+    }
+
+    pub fn default_level(&self) -> Level {
+        match self {
+            Self::NotNullWithoutDefault | Self::NonConcurrentIndex | Self::ConfirmDropColumn => {
+                Level::Error
+            }
+            Self::AlterColumnType
+            | Self::SetNotNull
+            | Self::MissingLockTimeout
+            | Self::ParseError => Level::Warning,
+        }
+    }
+}
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Rule {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|rule| rule.as_str() == s)
+            .ok_or_else(|| format!("Unknown lint rule: {}", s))
+    }
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Level {
+    Off,
+    Warning,
+    Error,
+}
+
+impl fmt::Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Off => "off",
+            Self::Warning => "warning",
+            Self::Error => "error",
+        })
+    }
+}
+
+/// Levels of the lint rules. Rules not contained in `levels` use their
+/// default level.
+#[derive(Debug, Clone, Default)]
+pub struct LintOptions {
+    pub levels: HashMap<Rule, Level>,
+    /// `lock_timeout` set before running the migrations, e.g. `5s`.
+    /// Migrations don't need to set it themselves then.
+    pub lock_timeout: Option<String>,
+}
+
+impl LintOptions {
+    pub fn level(&self, rule: Rule) -> Level {
+        self.levels
+            .get(&rule)
+            .copied()
+            .unwrap_or_else(|| rule.default_level())
+    }
+}
+
+/// Check if a `lock_timeout` value disables the timeout
+fn disables_timeout(value: &str) -> bool {
+    value == "0" || value == "'0'" || value.eq_ignore_ascii_case("DEFAULT")
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Finding {
+    pub rule: Rule,
+    pub level: Level,
+    /// Line of the statement starting at 1
+    pub line: usize,
+    pub message: String,
+}
+
+/// Rules suppressed by the leading comments of a statement
+fn ignored_rules(statement: &str) -> Option<HashSet<Rule>> {
+    let mut ignored = None;
+    for line in statement.lines().map(str::trim) {
+        if !line.starts_with("--") {
+            break;
+        }
+        if let Some(rules) = line.strip_prefix(IGNORE_MARKER) {
+            ignored.get_or_insert_with(HashSet::new).extend(
+                rules
+                    .split([',', ' '])
+                    .filter_map(|r| r.parse::<Rule>().ok()),
+            );
+        }
+    }
+    ignored
+}
+
+/// Line number of the first line of the statement which is not a comment
+fn line_number(sql: &str, statement: &str) -> usize {
+    let offset = statement.as_ptr() as usize - sql.as_ptr() as usize;
+    let comment_lines = statement
+        .lines()
+        .take_while(|line| line.trim().starts_with("--"))
+        .count();
+    sql[..offset].matches('\n').count() + comment_lines + 1
+}
+
+/// Parts of a statement the rules are interested in
+#[derive(Debug, Clone, Eq, PartialEq)]
+enum CheckedStatement {
+    CreateTable(String),
+    /// Whether the `lock_timeout` is enabled afterwards
+    SetLockTimeout(bool),
+    CreateIndex {
+        table: String,
+        concurrently: bool,
+    },
+    AlterTable {
+        table: String,
+        operations: Vec<AlterOperation>,
+    },
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+enum AlterOperation {
+    AddColumn {
+        column: String,
+        not_null: bool,
+        default: bool,
+    },
+    AlterType(String),
+    SetNotNull(String),
+    DropColumn(String),
+    Other,
+}
+
+fn table_name(name: &ObjectName) -> String {
+    name.0
+        .last()
+        .map(|ident| match ident.quote_style {
+            Some(_) => ident.value.clone(),
+            None => ident.value.to_lowercase(),
+        })
+        .unwrap_or_default()
+}
+
+fn parsed_statement(statement: &Statement) -> Option<CheckedStatement> {
+    match statement {
+        Statement::CreateTable(create) => {
+            Some(CheckedStatement::CreateTable(table_name(&create.name)))
+        }
+        Statement::SetVariable {
+            variables, value, ..
+        } if variables.to_string().eq_ignore_ascii_case("lock_timeout") => {
+            Some(CheckedStatement::SetLockTimeout(
+                !value.iter().any(|v| disables_timeout(&v.to_string())),
+            ))
+        }
+        Statement::CreateIndex(index) => Some(CheckedStatement::CreateIndex {
+            table: table_name(&index.table_name),
+            concurrently: index.concurrently,
+        }),
+        Statement::AlterTable {
+            name, operations, ..
+        } => Some(CheckedStatement::AlterTable {
+            table: table_name(name),
+            operations: operations.iter().map(parsed_operation).collect(),
+        }),
+        _ => None,
+    }
+}
+
+fn parsed_operation(operation: &AlterTableOperation) -> AlterOperation {
+    match operation {
+        AlterTableOperation::AddColumn { column_def, .. } => AlterOperation::AddColumn {
+            column: column_def.name.value.clone(),
+            not_null: column_def
+                .options
+                .iter()
+                .any(|o| matches!(o.option, ColumnOption::NotNull)),
+            default: column_def.options.iter().any(|o| {
+                matches!(
+                    o.option,
+                    ColumnOption::Default(_) | ColumnOption::Generated { .. }
+                )
+            }),
+        },
+        AlterTableOperation::AlterColumn { column_name, op } => match op {
+            AlterColumnOperation::SetDataType { .. } => {
+                AlterOperation::AlterType(column_name.value.clone())
+            }
+            AlterColumnOperation::SetNotNull => {
+                AlterOperation::SetNotNull(column_name.value.clone())
+            }
+            _ => AlterOperation::Other,
+        },
+        AlterTableOperation::DropColumn { column_name, .. } => {
+            AlterOperation::DropColumn(column_name.value.clone())
+        }
+        _ => AlterOperation::Other,
+    }
+}
+
+/// Cursor over the tokens of a statement
+struct Cursor<'a> {
+    tokens: &'a [Token],
+    pos: usize,
+}
+
+impl<'a> Cursor<'a> {
+    fn new(tokens: &'a [Token]) -> Self {
+        Self { tokens, pos: 0 }
+    }
+    fn peek(&self, keyword: &str) -> bool {
+        self.tokens
+            .get(self.pos)
+            .is_some_and(|t| t.is_keyword(keyword))
+    }
+    /// Consume the keywords if all of them follow
+    fn eat(&mut self, keywords: &[&str]) -> bool {
+        let matches = keywords.len() <= self.tokens.len() - self.pos
+            && keywords
+                .iter()
+                .zip(&self.tokens[self.pos..])
+                .all(|(keyword, token)| token.is_keyword(keyword));
+        if matches {
+            self.pos += keywords.len();
+        }
+        matches
+    }
+    fn eat_punct(&mut self, c: char) -> bool {
+        let matches = self.tokens.get(self.pos) == Some(&Token::Punct(c));
+        if matches {
+            self.pos += 1;
+        }
+        matches
+    }
+    fn word(&mut self) -> Option<String> {
+        match self.tokens.get(self.pos) {
+            Some(Token::Word { value, .. }) => {
+                self.pos += 1;
+                Some(value.clone())
+            }
+            _ => None,
+        }
+    }
+    /// Last part of a possibly schema qualified name
+    fn name(&mut self) -> Option<String> {
+        let mut name = self.word()?;
+        while self.eat_punct('.') {
+            name = self.word()?;
+        }
+        Some(name)
+    }
+    fn rest(&self) -> &'a [Token] {
+        &self.tokens[self.pos..]
+    }
+}
+
+/// Tokens outside of parentheses
+fn top_level(tokens: &[Token]) -> Vec<&Token> {
+    let mut depth = 0usize;
+    let mut top_level = Vec::new();
+    for token in tokens {
+        match token {
+            Token::Punct('(') => depth += 1,
+            Token::Punct(')') => depth = depth.saturating_sub(1),
+            _ if depth == 0 => top_level.push(token),
+            _ => {}
+        }
+    }
+    top_level
+}
+
+/// Extract the parts checked by the rules based on the keywords of a
+/// statement the parser doesn't support
+fn keyword_statement(statement: &str) -> Option<CheckedStatement> {
+    let tokens = tokenize(statement);
+    let mut c = Cursor::new(&tokens);
+    if c.eat(&["set"]) {
+        let _ = c.eat(&["session"]) || c.eat(&["local"]);
+        if !c.eat(&["lock_timeout"]) || !(c.eat(&["to"]) || c.eat_punct('=')) {
+            return None;
+        }
+        let enabled = match c.rest().first()? {
+            Token::Word { value, .. } | Token::Literal(value) => !disables_timeout(value),
+            Token::Punct(_) => return None,
+        };
+        Some(CheckedStatement::SetLockTimeout(enabled))
+    } else if c.eat(&["create"]) {
+        let _ = c.eat(&["unique"]);
+        if c.eat(&["index"]) {
+            let concurrently = c.eat(&["concurrently"]);
+            while !c.eat(&["on"]) {
+                c.tokens.get(c.pos)?;
+                c.pos += 1;
+            }
+            let _ = c.eat(&["only"]);
+            Some(CheckedStatement::CreateIndex {
+                table: c.name()?,
+                concurrently,
+            })
+        } else {
+            for modifier in ["global", "local", "temp", "temporary", "unlogged"] {
+                let _ = c.eat(&[modifier]);
+            }
+            if !c.eat(&["table"]) {
+                return None;
+            }
+            let _ = c.eat(&["if", "not", "exists"]);
+            Some(CheckedStatement::CreateTable(c.name()?))
+        }
+    } else if c.eat(&["alter", "table"]) {
+        let _ = c.eat(&["if", "exists"]);
+        let _ = c.eat(&["only"]);
+        let table = c.name()?;
+        let _ = c.eat_punct('*');
+        let mut operations = Vec::new();
+        let mut depth = 0usize;
+        let mut start = c.pos;
+        for (i, token) in tokens.iter().enumerate().skip(c.pos) {
+            match token {
+                Token::Punct('(') => depth += 1,
+                Token::Punct(')') => depth = depth.saturating_sub(1),
+                Token::Punct(',' | ';') if depth == 0 => {
+                    operations.push(keyword_operation(&tokens[start..i]));
+                    start = i + 1;
+                }
+                _ => {}
+            }
+        }
+        if start < tokens.len() {
+            operations.push(keyword_operation(&tokens[start..]));
+        }
+        Some(CheckedStatement::AlterTable { table, operations })
+    } else {
+        None
+    }
+}
+
+fn keyword_operation(tokens: &[Token]) -> AlterOperation {
+    let mut c = Cursor::new(tokens);
+    let column = |c: &mut Cursor| {
+        let _ = c.eat(&["column"]);
+        c.word()
+    };
+    if c.eat(&["add"]) {
+        let constraint = [
+            "constraint",
+            "primary",
+            "unique",
+            "check",
+            "foreign",
+            "exclude",
+        ]
+        .iter()
+        .any(|keyword| c.peek(keyword));
+        if constraint {
+            return AlterOperation::Other;
+        }
+        let _ = c.eat(&["column"]);
+        let _ = c.eat(&["if", "not", "exists"]);
+        let Some(column) = c.word() else {
+            return AlterOperation::Other;
+        };
+        let definition = top_level(c.rest());
+        AlterOperation::AddColumn {
+            column,
+            not_null: definition
+                .windows(2)
+                .any(|w| w[0].is_keyword("not") && w[1].is_keyword("null")),
+            default: definition
+                .iter()
+                .any(|t| t.is_keyword("default") || t.is_keyword("generated")),
+        }
+    } else if c.eat(&["alter"]) {
+        let Some(column) = column(&mut c) else {
+            return AlterOperation::Other;
+        };
+        if c.eat(&["type"]) || c.eat(&["set", "data", "type"]) {
+            AlterOperation::AlterType(column)
+        } else if c.eat(&["set", "not", "null"]) {
+            AlterOperation::SetNotNull(column)
+        } else {
+            AlterOperation::Other
+        }
+    } else if c.eat(&["drop"]) && !c.peek("constraint") {
+        let _ = c.eat(&["column"]);
+        let _ = c.eat(&["if", "exists"]);
+        c.word()
+            .map_or(AlterOperation::Other, AlterOperation::DropColumn)
+    } else {
+        AlterOperation::Other
+    }
+}
+
+/// Check the statements of a migration for risky operations. Tables
+/// created by the migration itself are considered empty. Statements the
+/// parser doesn't support are checked based on their keywords.
+pub fn lint(sql: &str, options: &LintOptions) -> Vec<Finding> {
+    let mut findings = Vec::new();
+    let mut created_tables = HashSet::new();
+    let mut lock_timeout = options
+        .lock_timeout
+        .as_deref()
+        .is_some_and(|value| !disables_timeout(value));
+    for statement in split_statements(sql) {
+        let line = line_number(sql, statement);
+        let ignored = ignored_rules(statement);
+        let mut report = |rule: Rule, message: String| {
+            let level = options.level(rule);
+            let suppressed = ignored
+                .as_ref()
+                .is_some_and(|rules| rules.is_empty() || rules.contains(&rule));
+            if level != Level::Off && !suppressed {
+                findings.push(Finding {
+                    rule,
+                    level,
+                    line,
+                    message,
+                });
+            }
+        };
+        let checked = match Parser::parse_sql(&PostgreSqlDialect {}, statement) {
+            Ok(parsed) => parsed.iter().filter_map(parsed_statement).collect(),
+            Err(e) => {
+                let checked = keyword_statement(statement);
+                if checked.is_some() {
+                    report(
+                        Rule::ParseError,
+                        format!("Statement only checked based on its keywords: {}", e),
+                    );
+                }
+                checked.into_iter().collect::<Vec<_>>()
+            }
+        };
+        for checked in checked {
+            let locked_table = match checked {
+                CheckedStatement::CreateTable(table) => {
+                    created_tables.insert(table);
+                    None
+                }
+                CheckedStatement::SetLockTimeout(enabled) => {
+                    lock_timeout = enabled;
+                    None
+                }
+                CheckedStatement::CreateIndex {
+                    table,
+                    concurrently,
+                } => {
+                    if created_tables.contains(&table) || concurrently {
+                        None
+                    } else {
+                        report(
+                            Rule::NonConcurrentIndex,
+                            format!(
+                                "Creating an index on table {} blocks writes. Use CREATE INDEX CONCURRENTLY instead.",
+                                table
+                            ),
+                        );
+                        Some(table)
+                    }
+                }
+                CheckedStatement::AlterTable { table, operations } => {
+                    if created_tables.contains(&table) {
+                        None
+                    } else {
+                        for operation in &operations {
+                            lint_alter_table(&table, operation, &mut report);
+                        }
+                        Some(table)
+                    }
+                }
+            };
+            if let Some(table) = locked_table {
+                if !lock_timeout {
+                    report(
+                        Rule::MissingLockTimeout,
+                        format!(
+                            "Locking table {} without lock_timeout blocks all queries on it while waiting for the lock. Add SET lock_timeout first.",
+                            table
+                        ),
+                    );
+                    // Report the missing timeout only once per migration
+                    lock_timeout = true;
+                }
+            }
+        }
+    }
+    findings
+}
+
+fn lint_alter_table(
+    table: &str,
+    operation: &AlterOperation,
+    report: &mut impl FnMut(Rule, String),
+) {
+    match operation {
+        AlterOperation::AddColumn {
+            column,
+            not_null,
+            default,
+        } => {
+            if *not_null && !default {
+                report(
+                    Rule::NotNullWithoutDefault,
+                    format!(
+                        "Adding column {}.{} as NOT NULL without a default fails if the table contains rows.",
+                        table, column
+                    ),
+                );
+            }
+        }
+        AlterOperation::AlterType(column) => report(
+            Rule::AlterColumnType,
+            format!(
+                "Changing the type of column {}.{} may rewrite the table while holding an ACCESS EXCLUSIVE lock.",
+                table, column
+            ),
+        ),
+        AlterOperation::SetNotNull(column) => report(
+            Rule::SetNotNull,
+            format!(
+                "Setting column {}.{} NOT NULL scans the table while holding an ACCESS EXCLUSIVE lock. Add and validate a CHECK ({} IS NOT NULL) constraint first.",
+                table, column, column
+            ),
+        ),
+        AlterOperation::DropColumn(column) => report(
+            Rule::ConfirmDropColumn,
+            format!(
+                "Dropping column {}.{} breaks code still referencing it. Confirm the drop once the code not using it is deployed.",
+                table, column
+            ),
+        ),
+        AlterOperation::Other => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{lint, LintOptions, Rule};
+
+    fn rules(sql: &str) -> Vec<(Rule, usize)> {
+        lint(sql, &LintOptions::default())
+            .into_iter()
+            .map(|f| (f.rule, f.line))
+            .collect()
+    }
+
+    #[test]
+    fn flags_risky_statements() {
+        let sql = "CREATE TABLE b (id int);\n\
+            CREATE INDEX b_idx ON b (id);\n\
+            ALTER TABLE a ADD COLUMN c text NOT NULL;\n\
+            ALTER TABLE a ADD COLUMN d text NOT NULL DEFAULT '';\n\
+            CREATE INDEX a_idx ON a (c);\n\
+            ALTER TABLE a ALTER COLUMN c TYPE integer USING c::integer;\n\
+            ALTER TABLE \"a\" DROP COLUMN d;\n";
+        assert_eq!(
+            rules(sql),
+            vec![
+                (Rule::NotNullWithoutDefault, 3),
+                (Rule::MissingLockTimeout, 3),
+                (Rule::NonConcurrentIndex, 5),
+                (Rule::AlterColumnType, 6),
+                (Rule::ConfirmDropColumn, 7),
+            ]
+        );
+    }
+
+    #[test]
+    fn respects_lock_timeout_and_ignore_comments() {
+        let sql = "SET lock_timeout = '5s';\n\
+            -- tusker:lint-ignore confirm_drop_column\n\
+            ALTER TABLE a DROP COLUMN b;\n\
+            -- tusker:lint-ignore\n\
+            CREATE INDEX a_idx ON a (c);\n\
+            CREATE INDEX CONCURRENTLY a_idx2 ON a (d);\n";
+        assert_eq!(rules(sql), vec![]);
+    }
+
+    #[test]
+    fn configures_levels() {
+        let options = LintOptions {
+            levels: [(Rule::ConfirmDropColumn, super::Level::Off)].into(),
+            ..Default::default()
+        };
+        assert!(lint(
+            "SET lock_timeout = '1s'; ALTER TABLE a DROP COLUMN b;",
+            &options
+        )
+        .is_empty());
+    }
+
+    #[test]
+    fn uses_configured_lock_timeout() {
+        let options = LintOptions {
+            lock_timeout: Some("5s".into()),
+            ..Default::default()
+        };
+        assert!(lint("ALTER TABLE a ADD COLUMN b text;", &options).is_empty());
+        let options = LintOptions {
+            lock_timeout: Some("0".into()),
+            ..Default::default()
+        };
+        assert_eq!(
+            lint("ALTER TABLE a ADD COLUMN b text;", &options)[0].rule,
+            Rule::MissingLockTimeout
+        );
+    }
+
+    #[test]
+    fn reports_parse_errors_of_checked_statements_only() {
+        let options = LintOptions {
+            levels: [(Rule::ParseError, super::Level::Error)].into(),
+            ..Default::default()
+        };
+        let findings = lint(
+            "CREATE EVENT TRIGGER t ON ddl_command_end EXECUTE FUNCTION f();\n\
+            ALTER TABLE a SET (fillfactor = 70);\n",
+            &options,
+        );
+        assert_eq!(
+            findings
+                .iter()
+                .map(|f| (f.rule, f.level, f.line))
+                .collect::<Vec<_>>(),
+            vec![
+                (Rule::ParseError, super::Level::Error, 2),
+                (Rule::MissingLockTimeout, super::Level::Warning, 2),
+            ]
+        );
+    }
+
+    #[test]
+    fn checks_unsupported_statements_by_keywords() {
+        let sql = "SET lock_timeout TO '5s';\n\
+            ALTER TABLE ONLY \"public\".\"a\"\n    \
+                ADD COLUMN b text COLLATE \"C\" NOT NULL CHECK (b IS NOT NULL),\n    \
+                ALTER COLUMN c TYPE text COLLATE \"C\",\n    \
+                ALTER COLUMN d SET NOT NULL,\n    \
+                ALTER COLUMN e SET STORAGE EXTERNAL,\n    \
+                ADD CONSTRAINT a_f_fkey FOREIGN KEY (f) REFERENCES b (id) NOT VALID,\n    \
+                DROP COLUMN IF EXISTS g;\n\
+            CREATE INDEX a_h_idx ON ONLY a USING btree (h);\n";
+        assert_eq!(
+            rules(sql),
+            vec![
+                (Rule::ParseError, 2),
+                (Rule::NotNullWithoutDefault, 2),
+                (Rule::AlterColumnType, 2),
+                (Rule::SetNotNull, 2),
+                (Rule::ConfirmDropColumn, 2),
+                (Rule::ParseError, 9),
+                (Rule::NonConcurrentIndex, 9),
+            ]
+        );
+    }
+
+    #[test]
+    fn flags_set_not_null() {
+        assert_eq!(
+            rules("SET lock_timeout = '5s'; ALTER TABLE a ALTER COLUMN b SET NOT NULL;"),
+            vec![(Rule::SetNotNull, 1)]
+        );
+    }
+}
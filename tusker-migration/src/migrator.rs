@@ -0,0 +1,279 @@
+use std::path::Path;
+use std::time::Duration;
+
+use crate::db::{Database, LockRetry, LogInfo, TableUpgrade, Timeouts};
+use crate::error::Error;
+use crate::file::{
+    load_embedded_migrations, load_migration_files, EmbeddedMigration, MigrationFile,
+};
+use crate::models::{combine_migrations, Migration, MigrationStatus};
+use crate::table::{MigrationTable, ValidityConstraint};
+
+/// Applies migrations from a directory or compiled into the binary
+/// using `embed_migrations!`. Unlike the `cli` module nothing is printed
+/// and the results are returned instead.
+#[derive(Debug, Clone)]
+pub struct Migrator {
+    pub migrations: Vec<MigrationFile>,
+    pub timeouts: Timeouts,
+}
+
+#[derive(Debug, Clone)]
+pub struct AppliedMigration {
+    pub number: i32,
+    pub name: String,
+    pub duration: Duration,
+    /// Number of retries after failing to acquire a lock
+    pub lock_retries: u32,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct RunReport {
+    pub table_upgrades: Vec<TableUpgrade>,
+    pub applied: Vec<AppliedMigration>,
+}
+
+pub(crate) async fn migration_table_exists(db: &Database) -> Result<bool, Error> {
+    db.migration_table_exists()
+        .await
+        .map_err(|e| Error::Pg("Checking status table failed".into(), e))
+}
+
+async fn legacy_table_schema(db: &Database) -> Result<Option<String>, Error> {
+    if db.table.qualified_name() == MigrationTable::default().qualified_name()
+        || migration_table_exists(db).await?
+    {
+        return Ok(None);
+    }
+    db.legacy_table_schema()
+        .await
+        .map_err(|e| Error::Pg("Checking status table failed".into(), e))
+}
+
+/// Changes `upgrade_table` would make to the migration table. If the
+/// table still needs to be moved only that is returned as the table
+/// can't be checked in its old location.
+pub async fn pending_table_upgrades(db: &Database) -> Result<Vec<TableUpgrade>, Error> {
+    if let Some(schema) = legacy_table_schema(db).await? {
+        return Ok(vec![TableUpgrade::Moved(schema)]);
+    }
+    let mut upgrades = Vec::new();
+    if !migration_table_exists(db).await? {
+        return Ok(upgrades);
+    }
+    let outdated = db
+        .migration_table_outdated()
+        .await
+        .map_err(|e| Error::Pg("Checking status table failed".into(), e))?;
+    if outdated {
+        upgrades.push(TableUpgrade::AddedColumns);
+    }
+    if db.table.constraint != ValidityConstraint::Auto {
+        let current = db
+            .validity_constraint()
+            .await
+            .map_err(|e| Error::Pg("Checking status table failed".into(), e))?;
+        if current != db.table.constraint {
+            upgrades.push(TableUpgrade::ChangedConstraint(db.table.constraint));
+        }
+    }
+    Ok(upgrades)
+}
+
+/// Move the migration table to the configured location, add the columns
+/// missing in migration tables created by older versions and change its
+/// constraint if configured. Callers should hold the lock taken by
+/// `Database::lock`.
+pub async fn upgrade_table(db: &Database) -> Result<Vec<TableUpgrade>, Error> {
+    let mut upgrades = Vec::new();
+    if let Some(schema) = legacy_table_schema(db).await? {
+        db.relocate(&schema)
+            .await
+            .map_err(|e| Error::Pg("Unable to move migration table".into(), e))?;
+        upgrades.push(TableUpgrade::Moved(schema));
+    }
+    for upgrade in pending_table_upgrades(db).await? {
+        match upgrade {
+            TableUpgrade::AddedColumns => db
+                .upgrade()
+                .await
+                .map_err(|e| Error::Pg("Unable to upgrade migration table".into(), e))?,
+            TableUpgrade::ChangedConstraint(constraint) => db
+                .change_validity_constraint(constraint)
+                .await
+                .map_err(|e| Error::Pg("Unable to upgrade migration table".into(), e))?,
+            TableUpgrade::Created | TableUpgrade::Moved(_) => unreachable!(),
+        }
+        upgrades.push(upgrade);
+    }
+    Ok(upgrades)
+}
+
+/// Upgrade the migration table or create it if it doesn't exist, yet
+pub async fn prepare_table(db: &Database) -> Result<Vec<TableUpgrade>, Error> {
+    let mut upgrades = upgrade_table(db).await?;
+    if !migration_table_exists(db).await? {
+        db.init()
+            .await
+            .map_err(|e| Error::Pg("Unable to create migration table".into(), e))?;
+        upgrades.push(TableUpgrade::Created);
+    }
+    Ok(upgrades)
+}
+
+/// Migrations which still need to be applied. Fails if an applied
+/// migration was changed or removed.
+pub fn pending(migrations: &[Migration]) -> Result<Vec<&MigrationFile>, Error> {
+    let mut pending = Vec::new();
+    for migration in migrations {
+        match migration.get_status() {
+            MigrationStatus::Ok(_, _) => {}
+            MigrationStatus::Mismatch(_, _) => return Err(Error::Mismatch(migration.number)),
+            MigrationStatus::NotApplied(migration_file) => pending.push(migration_file),
+            MigrationStatus::FileMissing(_) => return Err(Error::FileMissing(migration.number)),
+        }
+    }
+    Ok(pending)
+}
+
+impl Migrator {
+    pub fn new(migrations: Vec<MigrationFile>) -> Self {
+        Self {
+            migrations,
+            timeouts: Timeouts::default(),
+        }
+    }
+    pub fn from_dir(dir: &Path) -> Result<Self, Error> {
+        Ok(Self::new(load_migration_files(dir)?))
+    }
+    pub fn from_embedded(embedded: &[EmbeddedMigration]) -> Result<Self, Error> {
+        Ok(Self::new(load_embedded_migrations(embedded)?))
+    }
+    /// Combine the migrations with the ones applied to the database. Use
+    /// `Migration::get_status` to check their status.
+    pub async fn status(&self, db: &Database) -> Result<Vec<Migration>, Error> {
+        if let Some(schema) = legacy_table_schema(db).await? {
+            return Err(Error::Misc(format!(
+                "The migration log is still stored in {}.migration. Run the migrations to move it to {}.",
+                schema,
+                db.table.qualified_name()
+            )));
+        }
+        let db_migrations = match migration_table_exists(db).await? {
+            true => db
+                .get_migrations()
+                .await
+                .map_err(|e| Error::Pg("Unable to load already applied migrations".into(), e))?,
+            false => Vec::new(),
+        };
+        Ok(combine_migrations(&self.migrations, &db_migrations))
+    }
+    pub async fn apply(
+        &self,
+        db: &Database,
+        migration_file: &MigrationFile,
+        info: &LogInfo,
+    ) -> Result<AppliedMigration, Error> {
+        self.apply_with(db, migration_file, info, &|_| {}).await
+    }
+    /// Apply a migration and call `on_retry` before it is retried after
+    /// failing to acquire a lock
+    pub async fn apply_with(
+        &self,
+        db: &Database,
+        migration_file: &MigrationFile,
+        info: &LogInfo,
+        on_retry: &(dyn Fn(&LockRetry) + Sync),
+    ) -> Result<AppliedMigration, Error> {
+        let sql = migration_file.read()?;
+        let (duration, lock_retries) = db
+            .apply_migration(migration_file, &sql, &self.timeouts, info, on_retry)
+            .await
+            .map_err(|e| {
+                Error::Pg(
+                    format!("Applying migration file {:?} failed", migration_file.path),
+                    e,
+                )
+            })?;
+        Ok(AppliedMigration {
+            number: migration_file.number,
+            name: migration_file.name.clone(),
+            duration,
+            lock_retries,
+        })
+    }
+    /// Apply all migrations which were not applied, yet. Concurrent runs,
+    /// e.g. of several instances of a service starting at once, are
+    /// serialized using an advisory lock.
+    pub async fn run(&self, db: &Database, info: &LogInfo) -> Result<RunReport, Error> {
+        db.lock()
+            .await
+            .map_err(|e| Error::Pg("Unable to lock migration table".into(), e))?;
+        let result = self.run_locked(db, info).await;
+        if result.is_err() {
+            // A failed migration may leave an aborted transaction block
+            // behind which would make unlocking fail, too.
+            let _ = db.client.simple_query("ROLLBACK").await;
+        }
+        let unlocked = db.unlock().await;
+        let report = result?;
+        unlocked.map_err(|e| Error::Pg("Unable to unlock migration table".into(), e))?;
+        Ok(report)
+    }
+    async fn run_locked(&self, db: &Database, info: &LogInfo) -> Result<RunReport, Error> {
+        let table_upgrades = prepare_table(db).await?;
+        let migrations = self.status(db).await?;
+        let mut applied = Vec::new();
+        for migration_file in pending(&migrations)? {
+            applied.push(self.apply(db, migration_file, info).await?);
+        }
+        Ok(RunReport {
+            table_upgrades,
+            applied,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::pending;
+    use crate::db::DbMigration;
+    use crate::error::Error;
+    use crate::file::{load_embedded_migrations, EmbeddedMigration};
+    use crate::models::combine_migrations;
+
+    const EMBEDDED: &[EmbeddedMigration] = &[
+        EmbeddedMigration {
+            filename: "0001_initial.sql",
+            sql: "CREATE TABLE fruit (id int);",
+        },
+        EmbeddedMigration {
+            filename: "0002_fruit_name.sql",
+            sql: "ALTER TABLE fruit ADD COLUMN name text;",
+        },
+    ];
+
+    #[test]
+    fn pending_embedded_migrations() {
+        let files = load_embedded_migrations(EMBEDDED).unwrap();
+        let applied = DbMigration {
+            number: 1,
+            name: "initial".into(),
+            hash: files[0].hash.clone(),
+        };
+        let migrations = combine_migrations(&files, std::slice::from_ref(&applied));
+        let numbers: Vec<i32> = pending(&migrations)
+            .unwrap()
+            .iter()
+            .map(|f| f.number)
+            .collect();
+        assert_eq!(numbers, vec![2]);
+
+        let changed = DbMigration {
+            hash: vec![0],
+            ..applied
+        };
+        let migrations = combine_migrations(&files, &[changed]);
+        assert!(matches!(pending(&migrations), Err(Error::Mismatch(1))));
+    }
+}
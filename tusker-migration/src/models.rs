@@ -3,6 +3,7 @@ use std::collections::BTreeMap;
 use crate::db::DbMigration;
 use crate::file::MigrationFile;
 
+#[derive(Debug)]
 pub enum MigrationStatus<'a> {
     Ok(&'a MigrationFile, &'a DbMigration),
     Mismatch(&'a MigrationFile, &'a DbMigration),
@@ -10,6 +11,7 @@ pub enum MigrationStatus<'a> {
     FileMissing(&'a DbMigration),
 }
 
+#[derive(Debug)]
 pub struct Migration {
     pub number: i32,
     pub file: Option<MigrationFile>,
@@ -25,6 +25,11 @@ pub struct MigrationLogRow {
     pub name: String,
     pub timestamp: OffsetDateTime,
     pub operation: String,
+    pub duration: Option<f64>,
+    pub username: Option<String>,
+    pub hostname: Option<String>,
+    pub version: Option<String>,
+    pub comment: String,
 }
 
 #[derive(Query)]
@@ -33,6 +38,11 @@ pub struct MigrationInsert<'a> {
     pub number: i32,
     pub name: &'a str,
     pub hash: &'a [u8],
+    /// Duration in seconds
+    pub duration: f64,
+    pub hostname: &'a str,
+    pub version: &'a str,
+    pub comment: &'a str,
 }
 
 #[derive(Query)]
@@ -41,6 +51,9 @@ pub struct MigrationFake<'a> {
     pub number: i32,
     pub name: &'a str,
     pub hash: &'a [u8],
+    pub hostname: &'a str,
+    pub version: &'a str,
+    pub comment: &'a str,
 }
 
 #[derive(Query)]
@@ -49,10 +62,16 @@ pub struct MigrationUpdate<'a> {
     pub number: i32,
     pub name: &'a str,
     pub hash: &'a [u8],
+    pub hostname: &'a str,
+    pub version: &'a str,
+    pub comment: &'a str,
 }
 
 #[derive(Query)]
 #[query(sql = "migration_delete", row = NoRow)]
-pub struct MigrationDelete {
+pub struct MigrationDelete<'a> {
     pub number: i32,
+    pub hostname: &'a str,
+    pub version: &'a str,
+    pub comment: &'a str,
 }
@@ -0,0 +1,366 @@
+/// Migrations starting with this line are not wrapped in a transaction.
+/// Their statements are executed one by one which is required for
+/// statements like `CREATE INDEX CONCURRENTLY`.
+pub const NO_TRANSACTION_MARKER: &str = "-- tusker:no-transaction";
+
+/// Check if the migration must be run inside a transaction. Only the
+/// leading comment lines are searched for the marker.
+pub fn is_transactional(sql: &str) -> bool {
+    !sql.lines()
+        .map(str::trim)
+        .take_while(|line| line.is_empty() || line.starts_with("--"))
+        .any(|line| line == NO_TRANSACTION_MARKER)
+}
+
+/// End of the quoted identifier, string literal, dollar quoted string or
+/// comment starting at byte `i`. `None` if there is none at `i`.
+fn skip_quoted(sql: &str, i: usize) -> Option<usize> {
+    let bytes = sql.as_bytes();
+    let is_ident = |b: u8| b.is_ascii_alphanumeric() || b == b'_' || b == b'$';
+    match bytes[i] {
+        b'\'' | b'"' => {
+            let quote = bytes[i];
+            // Backslashes only escape characters in `E'...'` strings
+            let escape = quote == b'\''
+                && i > 0
+                && bytes[i - 1].eq_ignore_ascii_case(&b'e')
+                && (i < 2 || !is_ident(bytes[i - 2]));
+            let mut j = i + 1;
+            while j < bytes.len() {
+                match bytes[j] {
+                    b'\\' if escape => j += 1,
+                    // Doubled quotes are part of the string
+                    b if b == quote && bytes.get(j + 1) == Some(&quote) => j += 1,
+                    b if b == quote => return Some(j + 1),
+                    _ => {}
+                }
+                j += 1;
+            }
+            Some(bytes.len())
+        }
+        b'-' if bytes.get(i + 1) == Some(&b'-') => {
+            Some(sql[i..].find('\n').map_or(bytes.len(), |end| i + end))
+        }
+        b'/' if bytes.get(i + 1) == Some(&b'*') => {
+            // Block comments may be nested
+            let mut depth = 0;
+            let mut j = i;
+            while j < bytes.len() {
+                if bytes[j] == b'/' && bytes.get(j + 1) == Some(&b'*') {
+                    depth += 1;
+                    j += 2;
+                } else if bytes[j] == b'*' && bytes.get(j + 1) == Some(&b'/') {
+                    depth -= 1;
+                    j += 2;
+                    if depth == 0 {
+                        return Some(j);
+                    }
+                } else {
+                    j += 1;
+                }
+            }
+            Some(bytes.len())
+        }
+        b'$' if i == 0 || !is_ident(bytes[i - 1]) => {
+            let n = sql[i + 1..]
+                .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+                .filter(|&n| sql[i + 1 + n..].starts_with('$'))?;
+            let tag = &sql[i..i + n + 2];
+            let body = i + tag.len();
+            Some(
+                sql[body..]
+                    .find(tag)
+                    .map_or(bytes.len(), |end| body + end + tag.len()),
+            )
+        }
+        _ => None,
+    }
+}
+
+/// Split SQL into single statements. Quoted identifiers, string
+/// literals, dollar quoted strings and comments are skipped when looking
+/// for the terminating semicolon.
+pub fn split_statements(sql: &str) -> Vec<&str> {
+    let bytes = sql.as_bytes();
+    let mut statements = Vec::new();
+    let mut start = 0;
+    let mut i = 0;
+    while i < bytes.len() {
+        if let Some(end) = skip_quoted(sql, i) {
+            i = end;
+            continue;
+        }
+        if bytes[i] == b';' {
+            statements.push(&sql[start..=i]);
+            start = i + 1;
+        }
+        i += 1;
+    }
+    statements.push(&sql[start.min(sql.len())..]);
+    statements
+        .into_iter()
+        .map(str::trim)
+        .filter(|s| !strip_comment_lines(s).is_empty())
+        .collect()
+}
+
+/// Strip leading comment lines so that trailing comments are not
+/// considered statements.
+fn strip_comment_lines(sql: &str) -> &str {
+    let mut rest = sql.trim_start();
+    while rest.starts_with("--") {
+        rest = rest.split_once('\n').map_or("", |(_, r)| r).trim_start();
+    }
+    rest
+}
+
+/// Check if the migration controls transactions itself using `BEGIN`,
+/// `COMMIT`, `ROLLBACK` and the like
+pub fn has_transaction_control(sql: &str) -> bool {
+    split_statements(sql).into_iter().any(|statement| {
+        leading_keywords(statement, 1)
+            .first()
+            .is_some_and(|&(start, end)| {
+                ["BEGIN", "START", "COMMIT", "END", "ROLLBACK", "ABORT"]
+                    .iter()
+                    .any(|keyword| statement[start..end].eq_ignore_ascii_case(keyword))
+            })
+    })
+}
+
+/// Keywords preceding `CONCURRENTLY` in the statements supporting it
+const CONCURRENTLY_PREFIXES: &[&[&str]] = &[
+    &["CREATE", "INDEX"],
+    &["CREATE", "UNIQUE", "INDEX"],
+    &["DROP", "INDEX"],
+    &["REINDEX", "INDEX"],
+    &["REINDEX", "TABLE"],
+    &["REINDEX", "SCHEMA"],
+    &["REINDEX", "DATABASE"],
+    &["REFRESH", "MATERIALIZED", "VIEW"],
+];
+
+/// Remove `CONCURRENTLY` from the statements supporting it so they can
+/// run inside a transaction. Occurrences anywhere else, e.g. in string
+/// literals or comments, are kept.
+pub fn without_concurrently(sql: &str) -> String {
+    split_statements(sql)
+        .into_iter()
+        .map(strip_concurrently)
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn strip_concurrently(statement: &str) -> String {
+    match concurrently_keyword(statement) {
+        Some((start, end)) => format!("{}{}", &statement[..start], statement[end..].trim_start()),
+        None => statement.to_owned(),
+    }
+}
+
+/// Check if the statement uses `CONCURRENTLY`
+pub fn is_concurrent(statement: &str) -> bool {
+    concurrently_keyword(statement).is_some()
+}
+
+/// Byte range of the `CONCURRENTLY` keyword of a statement
+fn concurrently_keyword(statement: &str) -> Option<(usize, usize)> {
+    let keywords = leading_keywords(statement, 4);
+    CONCURRENTLY_PREFIXES.iter().find_map(|prefix| {
+        let &(start, end) = keywords.get(prefix.len())?;
+        let matches = prefix
+            .iter()
+            .zip(&keywords)
+            .all(|(keyword, &(s, e))| statement[s..e].eq_ignore_ascii_case(keyword));
+        (matches && statement[start..end].eq_ignore_ascii_case("CONCURRENTLY"))
+            .then_some((start, end))
+    })
+}
+
+/// Byte ranges of the first `n` keywords of a statement. Whitespace and
+/// comments between them are skipped. Anything else ends the search.
+fn leading_keywords(statement: &str, n: usize) -> Vec<(usize, usize)> {
+    let mut keywords = Vec::new();
+    let mut i = 0;
+    while i < statement.len() && keywords.len() < n {
+        let rest = &statement[i..];
+        if rest.starts_with(|c: char| c.is_ascii_whitespace()) {
+            i += 1;
+        } else if rest.starts_with("--") || rest.starts_with("/*") {
+            i = skip_quoted(statement, i).unwrap_or(statement.len());
+        } else if rest.starts_with(|c: char| c.is_ascii_alphabetic()) {
+            let len = rest
+                .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                .unwrap_or(rest.len());
+            keywords.push((i, i + len));
+            i += len;
+        } else {
+            break;
+        }
+    }
+    keywords
+}
+
+/// Token of a statement as used by the keyword based lint rules
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub(crate) enum Token {
+    /// Keyword or identifier. Unquoted words are lowercased like
+    /// PostgreSQL does.
+    Word {
+        value: String,
+        quoted: bool,
+    },
+    /// String literal, number or other constant as written
+    Literal(String),
+    Punct(char),
+}
+
+impl Token {
+    /// Check if the token is the given unquoted lowercase keyword
+    pub(crate) fn is_keyword(&self, keyword: &str) -> bool {
+        matches!(self, Self::Word { value, quoted: false } if value == keyword)
+    }
+}
+
+/// Split a statement into tokens. Comments are skipped.
+pub(crate) fn tokenize(statement: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while let Some(c) = statement[i..].chars().next() {
+        let start = i;
+        if c.is_whitespace() {
+            i += c.len_utf8();
+        } else if c == '"' {
+            i = skip_quoted(statement, i).unwrap_or(statement.len());
+            let value = statement[start + 1..i]
+                .strip_suffix('"')
+                .unwrap_or(&statement[start + 1..i])
+                .replace("\"\"", "\"");
+            tokens.push(Token::Word {
+                value,
+                quoted: true,
+            });
+        } else if let Some(end) = skip_quoted(statement, i) {
+            i = end;
+            if c == '\'' || c == '$' {
+                tokens.push(Token::Literal(statement[start..i].into()));
+            }
+        } else if c.is_alphabetic() || c == '_' {
+            i += statement[i..]
+                .find(|c: char| !(c.is_alphanumeric() || c == '_' || c == '$'))
+                .unwrap_or(statement.len() - i);
+            // String constants with a prefix like `E'...'`
+            match statement[i..].starts_with('\'') {
+                true => {
+                    i = skip_quoted(statement, i).unwrap_or(statement.len());
+                    tokens.push(Token::Literal(statement[start..i].into()));
+                }
+                false => tokens.push(Token::Word {
+                    value: statement[start..i].to_lowercase(),
+                    quoted: false,
+                }),
+            }
+        } else if c.is_ascii_digit() {
+            i += statement[i..]
+                .find(|c: char| !(c.is_ascii_alphanumeric() || c == '.'))
+                .unwrap_or(statement.len() - i);
+            tokens.push(Token::Literal(statement[start..i].into()));
+        } else {
+            i += c.len_utf8();
+            tokens.push(Token::Punct(c));
+        }
+    }
+    tokens
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        has_transaction_control, is_concurrent, is_transactional, split_statements,
+        without_concurrently,
+    };
+
+    #[test]
+    fn detects_marker() {
+        assert!(is_transactional("CREATE TABLE a ();\n"));
+        assert!(!is_transactional(
+            "-- tusker:no-transaction\n\nCREATE INDEX CONCURRENTLY a_idx ON a (id);\n"
+        ));
+        assert!(is_transactional("SELECT 1;\n-- tusker:no-transaction\n"));
+    }
+
+    #[test]
+    fn splits_statements() {
+        let sql = "-- tusker:no-transaction\n\
+            CREATE INDEX CONCURRENTLY \"a;idx\" ON a (id);\n\n\
+            CREATE FUNCTION f() RETURNS text LANGUAGE sql AS $fn$ SELECT ';' $fn$;\n\
+            COMMENT /* a; */ ON TABLE a IS 'it''s; fine';\n\
+            -- trailing comment\n";
+        assert_eq!(
+            split_statements(sql),
+            vec![
+                "-- tusker:no-transaction\nCREATE INDEX CONCURRENTLY \"a;idx\" ON a (id);",
+                "CREATE FUNCTION f() RETURNS text LANGUAGE sql AS $fn$ SELECT ';' $fn$;",
+                "COMMENT /* a; */ ON TABLE a IS 'it''s; fine';",
+            ]
+        );
+    }
+
+    #[test]
+    fn splits_escape_strings_and_nested_comments() {
+        let sql = "COMMENT ON TABLE a IS E'it\\'s; fine';\n\
+            /* outer /* inner; */ still a comment; */ SELECT 1;\n\
+            SELECT 'C:\\'; SELECT 2;\n";
+        assert_eq!(
+            split_statements(sql),
+            vec![
+                "COMMENT ON TABLE a IS E'it\\'s; fine';",
+                "/* outer /* inner; */ still a comment; */ SELECT 1;",
+                "SELECT 'C:\\';",
+                "SELECT 2;",
+            ]
+        );
+    }
+
+    #[test]
+    fn removes_concurrently() {
+        let sql = "-- tusker:no-transaction\n\
+            create unique index concurrently a_idx ON a (id);\n\
+            DROP INDEX /* old */ CONCURRENTLY b_idx;\n\
+            REFRESH MATERIALIZED VIEW CONCURRENTLY c;\n\
+            COMMENT ON INDEX a_idx IS 'built CONCURRENTLY';\n\
+            CREATE INDEX concurrently_idx ON a (id);\n";
+        assert_eq!(
+            without_concurrently(sql),
+            "-- tusker:no-transaction\n\
+            create unique index a_idx ON a (id);\n\
+            DROP INDEX /* old */ b_idx;\n\
+            REFRESH MATERIALIZED VIEW c;\n\
+            COMMENT ON INDEX a_idx IS 'built CONCURRENTLY';\n\
+            CREATE INDEX concurrently_idx ON a (id);"
+        );
+    }
+
+    #[test]
+    fn detects_concurrent_statements() {
+        assert!(is_concurrent("CREATE INDEX CONCURRENTLY a_idx ON a (id);"));
+        assert!(is_concurrent(
+            "-- comment\nreindex index concurrently a_idx;"
+        ));
+        assert!(!is_concurrent("CREATE INDEX a_idx ON a (id);"));
+        assert!(!is_concurrent("SELECT 'CREATE INDEX CONCURRENTLY';"));
+    }
+
+    #[test]
+    fn detects_transaction_control() {
+        assert!(has_transaction_control(
+            "BEGIN;\nALTER TABLE a ADD COLUMN b text;\nCOMMIT;\n"
+        ));
+        assert!(has_transaction_control(
+            "-- comment\nstart transaction; select 1; end;"
+        ));
+        assert!(!has_transaction_control(
+            "CREATE FUNCTION f() RETURNS void LANGUAGE plpgsql AS $$ BEGIN END $$;"
+        ));
+    }
+}
@@ -0,0 +1,136 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+/// Names of the objects storing the migration log. The SQL of the
+/// migration table uses the default names in braces (e.g. `{migration}`)
+/// as placeholders which are replaced by the configured names.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct MigrationTable {
+    /// Schema containing the table and type. The objects are looked up
+    /// using the `search_path` if no schema is set.
+    #[serde(default)]
+    pub schema: Option<String>,
+    #[serde(default = "default_name")]
+    pub name: String,
+    /// Enum type of the `operation` column
+    #[serde(default = "default_operation_type")]
+    pub operation_type: String,
+    /// Index on the `number` and `validity` columns
+    #[serde(default = "default_index")]
+    pub index: String,
+    /// How the table makes sure that there is only one valid log entry
+    /// per migration
+    #[serde(default)]
+    pub constraint: ValidityConstraint,
+}
+
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ValidityConstraint {
+    /// Use an exclusion constraint if the `btree_gist` extension is
+    /// installed. Otherwise use a unique index. Existing tables are not
+    /// changed.
+    #[default]
+    Auto,
+    /// Exclusion constraint on the `number` and `validity` columns. This
+    /// requires the `btree_gist` extension.
+    Exclusion,
+    /// Partial unique index on the `number` of entries with an open-ended
+    /// validity
+    UniqueIndex,
+}
+
+impl fmt::Display for ValidityConstraint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Auto => "auto",
+            Self::Exclusion => "exclusion",
+            Self::UniqueIndex => "unique_index",
+        })
+    }
+}
+
+fn default_name() -> String {
+    "migration".into()
+}
+
+fn default_operation_type() -> String {
+    "migration_operation".into()
+}
+
+fn default_index() -> String {
+    "validity_idx".into()
+}
+
+impl Default for MigrationTable {
+    fn default() -> Self {
+        Self {
+            schema: None,
+            name: default_name(),
+            operation_type: default_operation_type(),
+            index: default_index(),
+            constraint: ValidityConstraint::default(),
+        }
+    }
+}
+
+pub fn quote_ident(ident: &str) -> String {
+    format!("\"{}\"", ident.replace('"', "\"\""))
+}
+
+impl MigrationTable {
+    fn qualify(&self, name: &str) -> String {
+        match &self.schema {
+            Some(schema) => format!("{}.{}", quote_ident(schema), quote_ident(name)),
+            None => quote_ident(name),
+        }
+    }
+    /// Quoted and schema qualified name of the table
+    pub fn qualified_name(&self) -> String {
+        self.qualify(&self.name)
+    }
+    /// Quoted and schema qualified name of the operation type
+    pub fn qualified_operation_type(&self) -> String {
+        self.qualify(&self.operation_type)
+    }
+    /// Quoted and schema qualified name of the index
+    pub fn qualified_index(&self) -> String {
+        self.qualify(&self.index)
+    }
+    /// Replace the placeholders in the SQL by the quoted names
+    pub fn format(&self, sql: &str) -> String {
+        sql.replace("{migration}", &self.qualified_name())
+            .replace("{migration_operation}", &self.qualified_operation_type())
+            .replace("{validity_idx}", &quote_ident(&self.index))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{MigrationTable, ValidityConstraint};
+
+    #[test]
+    fn formats_placeholders() {
+        let sql = "CREATE INDEX {validity_idx} ON {migration} USING GIST (number);\n\
+            SELECT 'apply'::{migration_operation};";
+        assert_eq!(
+            MigrationTable::default().format(sql),
+            "CREATE INDEX \"validity_idx\" ON \"migration\" USING GIST (number);\n\
+            SELECT 'apply'::\"migration_operation\";"
+        );
+        let table = MigrationTable {
+            schema: Some("tusker".into()),
+            name: "log".into(),
+            operation_type: "log_operation".into(),
+            index: "log_validity_idx".into(),
+            constraint: ValidityConstraint::Auto,
+        };
+        assert_eq!(
+            table.format(sql),
+            "CREATE INDEX \"log_validity_idx\" ON \"tusker\".\"log\" USING GIST (number);\n\
+            SELECT 'apply'::\"tusker\".\"log_operation\";"
+        );
+    }
+}
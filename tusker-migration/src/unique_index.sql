@@ -0,0 +1,4 @@
+-- Unlike an exclusion constraint this only makes sure that there is one
+-- open-ended log entry per migration but does not require btree_gist.
+CREATE UNIQUE INDEX {validity_idx} ON {migration} ("number")
+    WHERE upper_inf("validity");
@@ -0,0 +1,9 @@
+-- Columns added after the first release. They are added without
+-- defaults first so existing log entries don't get made up values.
+ALTER TABLE {migration}
+    ADD COLUMN IF NOT EXISTS "duration" interval,
+    ADD COLUMN IF NOT EXISTS "username" text,
+    ADD COLUMN IF NOT EXISTS "hostname" text,
+    ADD COLUMN IF NOT EXISTS "version" text;
+
+ALTER TABLE {migration} ALTER COLUMN "username" SET DEFAULT current_user;
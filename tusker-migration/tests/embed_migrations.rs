@@ -0,0 +1,26 @@
+use tusker_migration::embed_migrations;
+use tusker_migration::file::EmbeddedMigration;
+use tusker_migration::migrator::Migrator;
+
+const MIGRATIONS: &[EmbeddedMigration] = embed_migrations!("tests/migrations");
+
+#[test]
+fn embeds_migrations_in_order() {
+    let filenames: Vec<&str> = MIGRATIONS.iter().map(|m| m.filename).collect();
+    assert_eq!(filenames, vec!["0001_initial.sql", "0002_fruit_name.sql"]);
+    assert_eq!(
+        MIGRATIONS[1].sql,
+        "ALTER TABLE fruit ADD COLUMN name text NOT NULL;\n"
+    );
+}
+
+#[test]
+fn loads_embedded_migrations() {
+    let migrator = Migrator::from_embedded(MIGRATIONS).unwrap();
+    let migrations: Vec<(i32, &str)> = migrator
+        .migrations
+        .iter()
+        .map(|m| (m.number, m.name.as_str()))
+        .collect();
+    assert_eq!(migrations, vec![(1, "initial"), (2, "fruit_name")]);
+}
@@ -0,0 +1,3 @@
+CREATE TABLE fruit (
+    id bigint GENERATED BY DEFAULT AS IDENTITY
+);
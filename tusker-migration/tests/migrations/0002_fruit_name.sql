@@ -0,0 +1 @@
+ALTER TABLE fruit ADD COLUMN name text NOT NULL;
@@ -0,0 +1,24 @@
+SELECT
+    format_type(c.castsource, NULL) AS source,
+    format_type(c.casttarget, NULL) AS target,
+    fns.nspname AS function_schema,
+    fn.proname AS function_name,
+    pg_get_function_identity_arguments(fn.oid) AS function_arguments,
+    c.castmethod::text AS method,
+    c.castcontext::text AS context,
+    obj_description(c.oid, 'pg_cast') AS comment
+FROM pg_catalog.pg_cast AS c
+LEFT JOIN pg_catalog.pg_proc AS fn ON fn.oid = c.castfunc
+LEFT JOIN pg_catalog.pg_namespace AS fns ON fns.oid = fn.pronamespace
+-- Skip built-in casts
+WHERE c.oid >= 16384
+  -- Skip casts owned by extensions and casts created implicitly
+  -- together with range types.
+  AND NOT EXISTS (
+      SELECT 1
+      FROM pg_catalog.pg_depend AS dep
+      WHERE dep.classid = 'pg_cast'::regclass
+        AND dep.objid = c.oid
+        AND dep.deptype IN ('e', 'i')
+  )
+ORDER BY source, target;
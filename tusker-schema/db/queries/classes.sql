@@ -2,23 +2,120 @@ SELECT
     ns.nspname AS schema,
     cls.relname AS name,
     cls.relkind AS kind,
-    json_agg(
-        json_build_object(
-            'name', a.attname,
-            'type', format_type(a.atttypid, a.atttypmod),
-            'notnull', a.attnotnull,
-            'identity', a.attidentity,
-            'generated', a.attgenerated,
-            'default', pg_get_expr(a_def.adbin, a_def.adrelid)
+    COALESCE(
+        (
+            SELECT json_agg(
+                json_build_object(
+                    'name', a.attname,
+                    'type', format_type(a.atttypid, a.atttypmod),
+                    'base_type', format_type(a.atttypid, NULL),
+                    'typmod', a.atttypmod,
+                    'notnull', a.attnotnull,
+                    'identity', a.attidentity,
+                    'generated', a.attgenerated,
+                    'default', pg_get_expr(a_def.adbin, a_def.adrelid),
+                    'comment', col_description(cls.oid, a.attnum),
+                    'storage', a.attstorage,
+                    'default_storage', a_t.typstorage,
+                    'compression', NULLIF(a.attcompression, ''),
+                    'statistics', NULLIF(a.attstattarget, -1)
+                )
+                ORDER BY a.attnum
+            )
+            FROM pg_catalog.pg_attribute a
+                JOIN pg_catalog.pg_type a_t ON a_t.oid = a.atttypid
+                LEFT JOIN pg_catalog.pg_attrdef AS a_def
+                    ON a_def.adrelid = cls.oid AND a_def.adnum = a.attnum
+            WHERE a.attrelid = cls.oid
+              AND a.attnum > 0
+              -- Columns only inherited from a parent table are part of the
+              -- parent and must not be added to the child. Columns of
+              -- partitions are never local but partitions are not modeled
+              -- as inheritance children.
+              AND (a.attislocal OR cls.relispartition)
+        ),
+        '[]'::json
+    ) AS columns,
+    pg_get_viewdef(cls.oid) as viewdef,
+    obj_description(cls.oid, 'pg_class') AS comment,
+    pg_get_userbyid(cls.relowner) AS owner,
+    cls.relpersistence = 'u' AS unlogged,
+    -- Options of the TOAST table are set using the `toast.` prefix
+    ARRAY(
+        SELECT opt
+        FROM unnest(cls.reloptions) AS opt
+        UNION ALL
+        SELECT 'toast.' || opt
+        FROM pg_catalog.pg_class AS toast,
+            unnest(toast.reloptions) AS opt
+        WHERE toast.oid = cls.reltoastrelid
+    ) AS options,
+    ts.spcname AS tablespace,
+    am.amname AS access_method,
+    COALESCE(
+        (
+            SELECT json_agg(
+                json_build_object(
+                    'schema', parent_ns.nspname,
+                    'name', parent.relname
+                )
+                ORDER BY inh.inhseqno
+            )
+            FROM pg_catalog.pg_inherits AS inh
+                JOIN pg_catalog.pg_class AS parent ON parent.oid = inh.inhparent
+                JOIN pg_catalog.pg_namespace AS parent_ns
+                    ON parent_ns.oid = parent.relnamespace
+            WHERE inh.inhrelid = cls.oid
+              -- Partitions are attached using `PARTITION OF`, not `INHERITS`
+              AND NOT cls.relispartition
+        ),
+        '[]'::json
+    ) AS inherits,
+    (
+        SELECT json_build_object(
+            'parent', json_build_object(
+                'schema', parent_ns.nspname,
+                'name', parent.relname
+            ),
+            'bound', pg_get_expr(cls.relpartbound, cls.oid)
         )
+        FROM pg_catalog.pg_inherits AS inh
+            JOIN pg_catalog.pg_class AS parent ON parent.oid = inh.inhparent
+            JOIN pg_catalog.pg_namespace AS parent_ns
+                ON parent_ns.oid = parent.relnamespace
+        WHERE inh.inhrelid = cls.oid
+          AND cls.relispartition
+    ) AS partition_of,
+    ARRAY(
+        SELECT a.attname::text
+        FROM pg_catalog.pg_attribute AS a
+        WHERE a.attrelid = cls.oid
+          AND a.attnum > 0
+          AND NOT a.attisdropped
+          AND a.attinhcount > 0
+          AND NOT cls.relispartition
         ORDER BY a.attnum
-    ) AS columns,
-    pg_get_viewdef(cls.oid) as viewdef
+    ) AS inherited_columns,
+    ARRAY(
+        SELECT con.conname::text
+        FROM pg_catalog.pg_constraint AS con
+        WHERE con.conrelid = cls.oid
+          AND con.contype <> 'n'
+          AND con.coninhcount > 0
+          AND NOT cls.relispartition
+        ORDER BY con.conname
+    ) AS inherited_constraints
 FROM pg_catalog.pg_class AS cls
     JOIN pg_catalog.pg_namespace AS ns ON ns.oid = cls.relnamespace
-    JOIN pg_catalog.pg_attribute a ON a.attrelid = cls.oid AND a.attnum > 0
-    JOIN pg_catalog.pg_type a_t ON a_t.oid = a.atttypid
-    LEFT JOIN pg_catalog.pg_attrdef AS a_def
-        ON a_def.adrelid = cls.oid AND a_def.adnum = a.attnum
+    LEFT JOIN pg_catalog.pg_tablespace AS ts ON ts.oid = cls.reltablespace
+    LEFT JOIN pg_catalog.pg_am AS am ON am.oid = cls.relam
 WHERE ns.nspname = $1
-GROUP BY ns.nspname, cls.relname, cls.relkind, cls.oid;
+  -- Skip tables, views and composite types that belong to an installed
+  -- extension. Those are managed via CREATE EXTENSION / ALTER EXTENSION.
+  AND NOT EXISTS (
+      SELECT 1
+      FROM pg_catalog.pg_depend AS dep
+      WHERE dep.classid = 'pg_class'::regclass
+        AND dep.objid = cls.oid
+        AND dep.deptype = 'e'
+  );
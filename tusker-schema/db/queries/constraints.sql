@@ -2,8 +2,32 @@ SELECT
     cls.relname,
     con.conname,
     con.contype,
-    pg_get_constraintdef(con.oid)
+    pg_get_constraintdef(con.oid),
+    con.convalidated,
+    con.condeferrable,
+    con.condeferred,
+    -- `conenforced` was added in PostgreSQL 18
+    COALESCE((to_jsonb(con) ->> 'conenforced')::boolean, true),
+    NULLIF(con.confupdtype, ' '),
+    NULLIF(con.confdeltype, ' '),
+    -- Index backing primary keys and unique constraints
+    CASE
+        WHEN con.contype IN ('p', 'u') THEN pg_get_indexdef(con.conindid)
+    END,
+    obj_description(con.oid, 'pg_constraint')
 FROM pg_catalog.pg_constraint AS con
 JOIN pg_catalog.pg_namespace AS ns ON ns.oid = con.connamespace
 JOIN pg_catalog.pg_class AS cls ON cls.oid = con.conrelid
-WHERE ns.nspname = $1;
+WHERE ns.nspname = $1
+  -- Constraints inherited from a parent table are created together with
+  -- the inheritance relationship. Partitions are not modeled as
+  -- inheritance children.
+  AND (con.coninhcount = 0 OR cls.relispartition)
+  -- Constraints of tables which belong to an installed extension.
+  AND NOT EXISTS (
+      SELECT 1
+      FROM pg_catalog.pg_depend AS dep
+      WHERE dep.classid = 'pg_class'::regclass
+        AND dep.objid = cls.oid
+        AND dep.deptype = 'e'
+  );
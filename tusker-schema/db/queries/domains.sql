@@ -13,7 +13,9 @@ SELECT
         array_agg(pg_catalog.pg_get_constraintdef(c.oid) ORDER BY c.conname)
             FILTER (WHERE c.oid IS NOT NULL AND c.contype = 'c'),
         ARRAY[]::text[]
-    ) AS constraint_definitions
+    ) AS constraint_definitions,
+    pg_catalog.obj_description(t.oid, 'pg_type') AS comment,
+    pg_catalog.pg_get_userbyid(t.typowner) AS owner
 FROM pg_catalog.pg_type AS t
 JOIN pg_catalog.pg_namespace AS n ON n.oid = t.typnamespace
 LEFT JOIN pg_catalog.pg_constraint AS c ON c.contypid = t.oid
@@ -26,9 +28,10 @@ WHERE n.nspname = $1
       FROM pg_catalog.pg_depend AS dep
       WHERE dep.classid = 'pg_type'::regclass
         AND dep.objid = t.oid
-        AND dep.refclassid = 'pg_extension'::regclass
+        AND dep.deptype = 'e'
   )
 GROUP BY
+    t.oid,
     n.nspname,
     t.typname,
     t.typbasetype,
@@ -1,7 +1,9 @@
 SELECT
     ns.nspname AS schema,
     t.typname AS name,
-    array_agg(e.enumlabel ORDER BY e.enumsortorder) AS labels
+    array_agg(e.enumlabel ORDER BY e.enumsortorder) AS labels,
+    obj_description(t.oid, 'pg_type') AS comment,
+    pg_get_userbyid(t.typowner) AS owner
 FROM pg_catalog.pg_type AS t
 JOIN pg_catalog.pg_namespace AS ns ON ns.oid = t.typnamespace
 JOIN pg_catalog.pg_enum AS e ON e.enumtypid = t.oid
@@ -14,6 +16,6 @@ WHERE ns.nspname = $1
       FROM pg_catalog.pg_depend AS dep
       WHERE dep.classid = 'pg_type'::regclass
         AND dep.objid = t.oid
-        AND dep.refclassid = 'pg_extension'::regclass
+        AND dep.deptype = 'e'
   )
-GROUP BY ns.nspname, t.typname;
+GROUP BY ns.nspname, t.typname, t.oid;
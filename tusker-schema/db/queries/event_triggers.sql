@@ -0,0 +1,20 @@
+SELECT
+    evt.evtname AS name,
+    evt.evtevent AS event,
+    COALESCE(evt.evttags, '{}') AS tags,
+    nsp.nspname AS function_schema,
+    p.proname AS function_name,
+    evt.evtenabled AS enabled,
+    pg_get_userbyid(evt.evtowner) AS owner,
+    obj_description(evt.oid, 'pg_event_trigger') AS comment
+FROM pg_catalog.pg_event_trigger AS evt
+JOIN pg_catalog.pg_proc AS p ON p.oid = evt.evtfoid
+JOIN pg_catalog.pg_namespace AS nsp ON nsp.oid = p.pronamespace
+WHERE NOT EXISTS (
+    SELECT 1
+    FROM pg_catalog.pg_depend AS dep
+    WHERE dep.classid = 'pg_event_trigger'::regclass
+      AND dep.objid = evt.oid
+      AND dep.deptype = 'e'
+)
+ORDER BY evt.evtname;
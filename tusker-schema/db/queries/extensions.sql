@@ -1,7 +1,17 @@
 SELECT
     nspname AS schema,
     extname AS name,
-    extversion AS version
+    extversion AS version,
+    -- Objects which are created by the extension script. Those are
+    -- excluded from all other queries.
+    ARRAY(
+        SELECT pg_describe_object(dep.classid, dep.objid, dep.objsubid)
+        FROM pg_catalog.pg_depend AS dep
+        WHERE dep.refclassid = 'pg_extension'::regclass
+          AND dep.refobjid = e.oid
+          AND dep.deptype = 'e'
+        ORDER BY 1
+    ) AS objects
 FROM pg_extension AS e
 INNER JOIN pg_namespace AS ns ON ns.oid = e.extnamespace
 WHERE nspname = $1
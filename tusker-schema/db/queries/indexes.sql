@@ -2,7 +2,8 @@ SELECT
     ns.nspname AS schema,
     tbl.relname AS table_name,
     idx.relname AS name,
-    pg_get_indexdef(idx.oid) AS definition
+    pg_get_indexdef(idx.oid) AS definition,
+    obj_description(idx.oid, 'pg_class') AS comment
 FROM pg_catalog.pg_index AS indexrel
 JOIN pg_catalog.pg_class AS tbl ON tbl.oid = indexrel.indrelid
 JOIN pg_catalog.pg_class AS idx ON idx.oid = indexrel.indexrelid
@@ -20,13 +21,13 @@ WHERE ns.nspname = $1
       FROM pg_catalog.pg_depend AS dep
       WHERE dep.classid = 'pg_class'::regclass
         AND dep.objid = idx.oid
-        AND dep.refclassid = 'pg_extension'::regclass
+        AND dep.deptype = 'e'
   )
   AND NOT EXISTS (
       SELECT 1
       FROM pg_catalog.pg_depend AS dep
       WHERE dep.classid = 'pg_class'::regclass
         AND dep.objid = tbl.oid
-        AND dep.refclassid = 'pg_extension'::regclass
+        AND dep.deptype = 'e'
   )
 ORDER BY schema, table_name, name;
@@ -0,0 +1,84 @@
+SELECT
+    ns.nspname AS schema,
+    opc.opcname AS name,
+    am.amname AS method,
+    fns.nspname AS family_schema,
+    opf.opfname AS family_name,
+    format_type(opc.opcintype, NULL) AS input_type,
+    opc.opcdefault AS "default",
+    CASE WHEN opc.opckeytype <> 0 THEN format_type(opc.opckeytype, NULL) END AS storage,
+    ARRAY(
+        SELECT m.definition
+        FROM (
+            SELECT
+                'OPERATOR' AS kind,
+                amop.amopstrategy AS number,
+                amop.amoplefttype AS left_type,
+                amop.amoprighttype AS right_type,
+                format(
+                    'OPERATOR %s %I.%s(%s, %s)%s',
+                    amop.amopstrategy,
+                    ons.nspname,
+                    op.oprname,
+                    format_type(amop.amoplefttype, NULL),
+                    format_type(amop.amoprighttype, NULL),
+                    CASE
+                        WHEN amop.amoppurpose = 'o' THEN (
+                            SELECT format(' FOR ORDER BY %I.%I', sns.nspname, sopf.opfname)
+                            FROM pg_catalog.pg_opfamily AS sopf
+                            JOIN pg_catalog.pg_namespace AS sns ON sns.oid = sopf.opfnamespace
+                            WHERE sopf.oid = amop.amopsortfamily
+                        )
+                        ELSE ''
+                    END
+                ) AS definition
+            FROM pg_catalog.pg_amop AS amop
+            JOIN pg_catalog.pg_depend AS dep
+                ON dep.classid = 'pg_amop'::regclass
+                AND dep.objid = amop.oid
+                AND dep.refclassid = 'pg_opclass'::regclass
+                AND dep.refobjid = opc.oid
+            JOIN pg_catalog.pg_operator AS op ON op.oid = amop.amopopr
+            JOIN pg_catalog.pg_namespace AS ons ON ons.oid = op.oprnamespace
+            UNION ALL
+            SELECT
+                'FUNCTION' AS kind,
+                amproc.amprocnum AS number,
+                amproc.amproclefttype AS left_type,
+                amproc.amprocrighttype AS right_type,
+                format(
+                    'FUNCTION %s (%s, %s) %I.%I(%s)',
+                    amproc.amprocnum,
+                    format_type(amproc.amproclefttype, NULL),
+                    format_type(amproc.amprocrighttype, NULL),
+                    pns.nspname,
+                    p.proname,
+                    pg_get_function_identity_arguments(p.oid)
+                ) AS definition
+            FROM pg_catalog.pg_amproc AS amproc
+            JOIN pg_catalog.pg_depend AS dep
+                ON dep.classid = 'pg_amproc'::regclass
+                AND dep.objid = amproc.oid
+                AND dep.refclassid = 'pg_opclass'::regclass
+                AND dep.refobjid = opc.oid
+            JOIN pg_catalog.pg_proc AS p ON p.oid = amproc.amproc
+            JOIN pg_catalog.pg_namespace AS pns ON pns.oid = p.pronamespace
+        ) AS m
+        ORDER BY m.kind DESC, m.number, m.left_type, m.right_type
+    ) AS members,
+    obj_description(opc.oid, 'pg_opclass') AS comment,
+    pg_get_userbyid(opc.opcowner) AS owner
+FROM pg_catalog.pg_opclass AS opc
+JOIN pg_catalog.pg_namespace AS ns ON ns.oid = opc.opcnamespace
+JOIN pg_catalog.pg_am AS am ON am.oid = opc.opcmethod
+JOIN pg_catalog.pg_opfamily AS opf ON opf.oid = opc.opcfamily
+JOIN pg_catalog.pg_namespace AS fns ON fns.oid = opf.opfnamespace
+WHERE ns.nspname = $1
+  AND NOT EXISTS (
+      SELECT 1
+      FROM pg_catalog.pg_depend AS dep
+      WHERE dep.classid = 'pg_opclass'::regclass
+        AND dep.objid = opc.oid
+        AND dep.deptype = 'e'
+  )
+ORDER BY name, method;
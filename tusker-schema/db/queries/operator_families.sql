@@ -0,0 +1,82 @@
+-- Only members which were added to the family via `ALTER OPERATOR
+-- FAMILY` are part of the family. Members of operator classes depend
+-- on the operator class instead.
+SELECT
+    ns.nspname AS schema,
+    opf.opfname AS name,
+    am.amname AS method,
+    COALESCE(
+        (
+            SELECT json_agg(m ORDER BY m.kind DESC, m.number, m.left_type, m.right_type)
+            FROM (
+                SELECT
+                    'OPERATOR' AS kind,
+                    amop.amopstrategy AS number,
+                    format_type(amop.amoplefttype, NULL) AS left_type,
+                    format_type(amop.amoprighttype, NULL) AS right_type,
+                    format(
+                        'OPERATOR %s %I.%s(%s, %s)%s',
+                        amop.amopstrategy,
+                        ons.nspname,
+                        op.oprname,
+                        format_type(amop.amoplefttype, NULL),
+                        format_type(amop.amoprighttype, NULL),
+                        CASE
+                            WHEN amop.amoppurpose = 'o' THEN (
+                                SELECT format(' FOR ORDER BY %I.%I', sns.nspname, sopf.opfname)
+                                FROM pg_catalog.pg_opfamily AS sopf
+                                JOIN pg_catalog.pg_namespace AS sns ON sns.oid = sopf.opfnamespace
+                                WHERE sopf.oid = amop.amopsortfamily
+                            )
+                            ELSE ''
+                        END
+                    ) AS definition
+                FROM pg_catalog.pg_amop AS amop
+                JOIN pg_catalog.pg_depend AS dep
+                    ON dep.classid = 'pg_amop'::regclass
+                    AND dep.objid = amop.oid
+                    AND dep.refclassid = 'pg_opfamily'::regclass
+                JOIN pg_catalog.pg_operator AS op ON op.oid = amop.amopopr
+                JOIN pg_catalog.pg_namespace AS ons ON ons.oid = op.oprnamespace
+                WHERE amop.amopfamily = opf.oid
+                UNION ALL
+                SELECT
+                    'FUNCTION' AS kind,
+                    amproc.amprocnum AS number,
+                    format_type(amproc.amproclefttype, NULL) AS left_type,
+                    format_type(amproc.amprocrighttype, NULL) AS right_type,
+                    format(
+                        'FUNCTION %s (%s, %s) %I.%I(%s)',
+                        amproc.amprocnum,
+                        format_type(amproc.amproclefttype, NULL),
+                        format_type(amproc.amprocrighttype, NULL),
+                        pns.nspname,
+                        p.proname,
+                        pg_get_function_identity_arguments(p.oid)
+                    ) AS definition
+                FROM pg_catalog.pg_amproc AS amproc
+                JOIN pg_catalog.pg_depend AS dep
+                    ON dep.classid = 'pg_amproc'::regclass
+                    AND dep.objid = amproc.oid
+                    AND dep.refclassid = 'pg_opfamily'::regclass
+                JOIN pg_catalog.pg_proc AS p ON p.oid = amproc.amproc
+                JOIN pg_catalog.pg_namespace AS pns ON pns.oid = p.pronamespace
+                WHERE amproc.amprocfamily = opf.oid
+            ) AS m
+        ),
+        '[]'::json
+    ) AS members,
+    obj_description(opf.oid, 'pg_opfamily') AS comment,
+    pg_get_userbyid(opf.opfowner) AS owner
+FROM pg_catalog.pg_opfamily AS opf
+JOIN pg_catalog.pg_namespace AS ns ON ns.oid = opf.opfnamespace
+JOIN pg_catalog.pg_am AS am ON am.oid = opf.opfmethod
+WHERE ns.nspname = $1
+  AND NOT EXISTS (
+      SELECT 1
+      FROM pg_catalog.pg_depend AS dep
+      WHERE dep.classid = 'pg_opfamily'::regclass
+        AND dep.objid = opf.oid
+        AND dep.deptype = 'e'
+  )
+ORDER BY name, method;
@@ -0,0 +1,38 @@
+SELECT
+    ns.nspname AS schema,
+    op.oprname AS name,
+    CASE WHEN op.oprleft <> 0 THEN format_type(op.oprleft, NULL) END AS left_type,
+    CASE WHEN op.oprright <> 0 THEN format_type(op.oprright, NULL) END AS right_type,
+    fns.nspname AS function_schema,
+    fn.proname AS function_name,
+    (
+        SELECT format('OPERATOR(%I.%s)', cns.nspname, com.oprname)
+        FROM pg_catalog.pg_operator AS com
+        JOIN pg_catalog.pg_namespace AS cns ON cns.oid = com.oprnamespace
+        WHERE com.oid = op.oprcom
+    ) AS commutator,
+    (
+        SELECT format('OPERATOR(%I.%s)', nns.nspname, neg.oprname)
+        FROM pg_catalog.pg_operator AS neg
+        JOIN pg_catalog.pg_namespace AS nns ON nns.oid = neg.oprnamespace
+        WHERE neg.oid = op.oprnegate
+    ) AS negator,
+    CASE WHEN op.oprrest::oid <> 0 THEN op.oprrest::text END AS restrict,
+    CASE WHEN op.oprjoin::oid <> 0 THEN op.oprjoin::text END AS "join",
+    op.oprcanhash AS hashes,
+    op.oprcanmerge AS merges,
+    obj_description(op.oid, 'pg_operator') AS comment,
+    pg_get_userbyid(op.oprowner) AS owner
+FROM pg_catalog.pg_operator AS op
+JOIN pg_catalog.pg_namespace AS ns ON ns.oid = op.oprnamespace
+JOIN pg_catalog.pg_proc AS fn ON fn.oid = op.oprcode
+JOIN pg_catalog.pg_namespace AS fns ON fns.oid = fn.pronamespace
+WHERE ns.nspname = $1
+  AND NOT EXISTS (
+      SELECT 1
+      FROM pg_catalog.pg_depend AS dep
+      WHERE dep.classid = 'pg_operator'::regclass
+        AND dep.objid = op.oid
+        AND dep.deptype = 'e'
+  )
+ORDER BY name, left_type, right_type;
@@ -0,0 +1,47 @@
+SELECT
+    pub.pubname AS name,
+    pg_get_userbyid(pub.pubowner) AS owner,
+    pub.puballtables AS all_tables,
+    ARRAY_REMOVE(
+        ARRAY[
+            CASE WHEN pub.pubinsert THEN 'insert' END,
+            CASE WHEN pub.pubupdate THEN 'update' END,
+            CASE WHEN pub.pubdelete THEN 'delete' END,
+            CASE WHEN pub.pubtruncate THEN 'truncate' END
+        ],
+        NULL
+    ) AS publish,
+    pub.pubviaroot AS publish_via_partition_root,
+    COALESCE(
+        (
+            SELECT json_agg(
+                json_build_object(
+                    'schema', nsp.nspname,
+                    'name', cls.relname,
+                    'columns', (
+                        SELECT json_agg(a.attname ORDER BY a.attnum)
+                        FROM pg_catalog.pg_attribute AS a
+                        WHERE a.attrelid = pr.prrelid
+                          AND a.attnum = ANY(pr.prattrs::int2[])
+                    ),
+                    'row_filter', pg_get_expr(pr.prqual, pr.prrelid)
+                )
+                ORDER BY nsp.nspname, cls.relname
+            )
+            FROM pg_catalog.pg_publication_rel AS pr
+            JOIN pg_catalog.pg_class AS cls ON cls.oid = pr.prrelid
+            JOIN pg_catalog.pg_namespace AS nsp ON nsp.oid = cls.relnamespace
+            WHERE pr.prpubid = pub.oid
+        ),
+        '[]'::json
+    ) AS tables,
+    ARRAY(
+        SELECT nsp.nspname::text
+        FROM pg_catalog.pg_publication_namespace AS pn
+        JOIN pg_catalog.pg_namespace AS nsp ON nsp.oid = pn.pnnspid
+        WHERE pn.pnpubid = pub.oid
+        ORDER BY nsp.nspname
+    ) AS schemas,
+    obj_description(pub.oid, 'pg_publication') AS comment
+FROM pg_catalog.pg_publication AS pub
+ORDER BY pub.pubname;
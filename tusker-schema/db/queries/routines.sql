@@ -4,7 +4,9 @@ SELECT
     p.prokind AS kind,
     pg_get_function_identity_arguments(p.oid) AS identity_arguments,
     pg_get_functiondef(p.oid) AS definition,
-    deps.dependencies AS dependencies
+    deps.dependencies AS dependencies,
+    obj_description(p.oid, 'pg_proc') AS comment,
+    pg_get_userbyid(p.proowner) AS owner
 FROM pg_catalog.pg_proc AS p
 JOIN pg_catalog.pg_namespace AS ns ON ns.oid = p.pronamespace
 LEFT JOIN LATERAL (
@@ -37,5 +39,5 @@ WHERE ns.nspname = $1
       FROM pg_catalog.pg_depend AS dep
       WHERE dep.classid = 'pg_proc'::regclass
         AND dep.objid = p.oid
-        AND dep.refclassid = 'pg_extension'::regclass
+        AND dep.deptype = 'e'
   );
@@ -1,5 +1,13 @@
-SELECT nspname
+SELECT nspname, obj_description(oid, 'pg_namespace'), pg_get_userbyid(nspowner)
 FROM pg_catalog.pg_namespace
 WHERE nspname NOT IN ('pg_internal', 'pg_catalog', 'information_schema', 'pg_toast')
 AND nspname NOT LIKE 'pg_temp_%'
-AND nspname NOT LIKE 'pg_toast_temp_%';
\ No newline at end of file
+AND nspname NOT LIKE 'pg_toast_temp_%'
+-- Schemas created by an extension script belong to the extension.
+AND NOT EXISTS (
+    SELECT 1
+    FROM pg_catalog.pg_depend AS dep
+    WHERE dep.classid = 'pg_namespace'::regclass
+      AND dep.objid = pg_namespace.oid
+      AND dep.deptype = 'e'
+);
\ No newline at end of file
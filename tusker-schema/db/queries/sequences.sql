@@ -7,7 +7,8 @@ SELECT
     seqs.max_value,
     seqs.increment_by,
     seqs.cycle,
-    seqs.cache_size
+    seqs.cache_size,
+    pg_get_userbyid(cls.relowner) AS owner
 FROM pg_catalog.pg_class AS cls
 JOIN pg_catalog.pg_namespace AS ns ON ns.oid = cls.relnamespace
 JOIN pg_catalog.pg_sequences AS seqs
@@ -22,7 +23,7 @@ WHERE ns.nspname = $1
       FROM pg_catalog.pg_depend AS dep
       WHERE dep.classid = 'pg_class'::regclass
         AND dep.objid = cls.oid
-        AND dep.refclassid = 'pg_extension'::regclass
+        AND dep.deptype = 'e'
   )
   AND NOT EXISTS (
       SELECT 1
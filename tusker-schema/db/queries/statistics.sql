@@ -0,0 +1,21 @@
+SELECT
+    ns.nspname AS schema,
+    tbl.relname AS table_name,
+    stx.stxname AS name,
+    pg_get_statisticsobjdef(stx.oid) AS definition,
+    -- PostgreSQL 17 uses NULL instead of -1 for the default target
+    NULLIF(stx.stxstattarget, -1)::int2 AS target,
+    obj_description(stx.oid, 'pg_statistic_ext') AS comment,
+    pg_get_userbyid(stx.stxowner) AS owner
+FROM pg_catalog.pg_statistic_ext AS stx
+JOIN pg_catalog.pg_class AS tbl ON tbl.oid = stx.stxrelid
+JOIN pg_catalog.pg_namespace AS ns ON ns.oid = stx.stxnamespace
+WHERE ns.nspname = $1
+  AND NOT EXISTS (
+      SELECT 1
+      FROM pg_catalog.pg_depend AS dep
+      WHERE dep.classid = 'pg_statistic_ext'::regclass
+        AND dep.objid = stx.oid
+        AND dep.deptype = 'e'
+  )
+ORDER BY schema, name;
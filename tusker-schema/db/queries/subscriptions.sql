@@ -0,0 +1,15 @@
+-- `subconninfo` is deliberately not selected. It usually contains
+-- credentials and is only readable by superusers.
+SELECT
+    sub.subname AS name,
+    pg_get_userbyid(sub.subowner) AS owner,
+    sub.subenabled AS enabled,
+    ARRAY(
+        SELECT p FROM unnest(sub.subpublications) AS p ORDER BY p
+    ) AS publications,
+    obj_description(sub.oid, 'pg_subscription') AS comment
+FROM pg_catalog.pg_subscription AS sub
+WHERE sub.subdbid = (
+    SELECT oid FROM pg_catalog.pg_database WHERE datname = current_database()
+)
+ORDER BY sub.subname;
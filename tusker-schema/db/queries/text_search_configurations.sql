@@ -0,0 +1,40 @@
+SELECT
+    ns.nspname AS schema,
+    cfg.cfgname AS name,
+    format('%I.%I', pns.nspname, prs.prsname) AS parser,
+    COALESCE(
+        (
+            SELECT json_object_agg(m.alias, m.dictionaries ORDER BY m.alias)
+            FROM (
+                SELECT
+                    tt.alias,
+                    json_agg(
+                        format('%I.%I', dns.nspname, dict.dictname)
+                        ORDER BY map.mapseqno
+                    ) AS dictionaries
+                FROM pg_catalog.pg_ts_config_map AS map
+                JOIN pg_catalog.ts_token_type(cfg.cfgparser) AS tt
+                    ON tt.tokid = map.maptokentype
+                JOIN pg_catalog.pg_ts_dict AS dict ON dict.oid = map.mapdict
+                JOIN pg_catalog.pg_namespace AS dns ON dns.oid = dict.dictnamespace
+                WHERE map.mapcfg = cfg.oid
+                GROUP BY tt.alias
+            ) AS m
+        ),
+        '{}'::json
+    ) AS mappings,
+    obj_description(cfg.oid, 'pg_ts_config') AS comment,
+    pg_get_userbyid(cfg.cfgowner) AS owner
+FROM pg_catalog.pg_ts_config AS cfg
+JOIN pg_catalog.pg_namespace AS ns ON ns.oid = cfg.cfgnamespace
+JOIN pg_catalog.pg_ts_parser AS prs ON prs.oid = cfg.cfgparser
+JOIN pg_catalog.pg_namespace AS pns ON pns.oid = prs.prsnamespace
+WHERE ns.nspname = $1
+  AND NOT EXISTS (
+      SELECT 1
+      FROM pg_catalog.pg_depend AS dep
+      WHERE dep.classid = 'pg_ts_config'::regclass
+        AND dep.objid = cfg.oid
+        AND dep.deptype = 'e'
+  )
+ORDER BY name;
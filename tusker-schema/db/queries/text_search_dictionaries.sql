@@ -0,0 +1,20 @@
+SELECT
+    ns.nspname AS schema,
+    dict.dictname AS name,
+    format('%I.%I', tns.nspname, tmpl.tmplname) AS template,
+    dict.dictinitoption AS options,
+    obj_description(dict.oid, 'pg_ts_dict') AS comment,
+    pg_get_userbyid(dict.dictowner) AS owner
+FROM pg_catalog.pg_ts_dict AS dict
+JOIN pg_catalog.pg_namespace AS ns ON ns.oid = dict.dictnamespace
+JOIN pg_catalog.pg_ts_template AS tmpl ON tmpl.oid = dict.dicttemplate
+JOIN pg_catalog.pg_namespace AS tns ON tns.oid = tmpl.tmplnamespace
+WHERE ns.nspname = $1
+  AND NOT EXISTS (
+      SELECT 1
+      FROM pg_catalog.pg_depend AS dep
+      WHERE dep.classid = 'pg_ts_dict'::regclass
+        AND dep.objid = dict.oid
+        AND dep.deptype = 'e'
+  )
+ORDER BY name;
@@ -0,0 +1,45 @@
+SELECT
+    ns.nspname AS schema,
+    prs.prsname AS name,
+    (
+        SELECT format('%I.%I', pns.nspname, p.proname)
+        FROM pg_catalog.pg_proc AS p
+        JOIN pg_catalog.pg_namespace AS pns ON pns.oid = p.pronamespace
+        WHERE p.oid = prs.prsstart
+    ) AS start,
+    (
+        SELECT format('%I.%I', pns.nspname, p.proname)
+        FROM pg_catalog.pg_proc AS p
+        JOIN pg_catalog.pg_namespace AS pns ON pns.oid = p.pronamespace
+        WHERE p.oid = prs.prstoken
+    ) AS gettoken,
+    (
+        SELECT format('%I.%I', pns.nspname, p.proname)
+        FROM pg_catalog.pg_proc AS p
+        JOIN pg_catalog.pg_namespace AS pns ON pns.oid = p.pronamespace
+        WHERE p.oid = prs.prsend
+    ) AS "end",
+    (
+        SELECT format('%I.%I', pns.nspname, p.proname)
+        FROM pg_catalog.pg_proc AS p
+        JOIN pg_catalog.pg_namespace AS pns ON pns.oid = p.pronamespace
+        WHERE p.oid = prs.prslextype
+    ) AS lextypes,
+    (
+        SELECT format('%I.%I', pns.nspname, p.proname)
+        FROM pg_catalog.pg_proc AS p
+        JOIN pg_catalog.pg_namespace AS pns ON pns.oid = p.pronamespace
+        WHERE p.oid = prs.prsheadline
+    ) AS headline,
+    obj_description(prs.oid, 'pg_ts_parser') AS comment
+FROM pg_catalog.pg_ts_parser AS prs
+JOIN pg_catalog.pg_namespace AS ns ON ns.oid = prs.prsnamespace
+WHERE ns.nspname = $1
+  AND NOT EXISTS (
+      SELECT 1
+      FROM pg_catalog.pg_depend AS dep
+      WHERE dep.classid = 'pg_ts_parser'::regclass
+        AND dep.objid = prs.oid
+        AND dep.deptype = 'e'
+  )
+ORDER BY name;
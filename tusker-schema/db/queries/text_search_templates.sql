@@ -0,0 +1,27 @@
+SELECT
+    ns.nspname AS schema,
+    tmpl.tmplname AS name,
+    (
+        SELECT format('%I.%I', pns.nspname, p.proname)
+        FROM pg_catalog.pg_proc AS p
+        JOIN pg_catalog.pg_namespace AS pns ON pns.oid = p.pronamespace
+        WHERE p.oid = tmpl.tmplinit
+    ) AS init,
+    (
+        SELECT format('%I.%I', pns.nspname, p.proname)
+        FROM pg_catalog.pg_proc AS p
+        JOIN pg_catalog.pg_namespace AS pns ON pns.oid = p.pronamespace
+        WHERE p.oid = tmpl.tmpllexize
+    ) AS lexize,
+    obj_description(tmpl.oid, 'pg_ts_template') AS comment
+FROM pg_catalog.pg_ts_template AS tmpl
+JOIN pg_catalog.pg_namespace AS ns ON ns.oid = tmpl.tmplnamespace
+WHERE ns.nspname = $1
+  AND NOT EXISTS (
+      SELECT 1
+      FROM pg_catalog.pg_depend AS dep
+      WHERE dep.classid = 'pg_ts_template'::regclass
+        AND dep.objid = tmpl.oid
+        AND dep.deptype = 'e'
+  )
+ORDER BY name;
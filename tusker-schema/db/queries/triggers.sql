@@ -14,5 +14,5 @@ WHERE nsp.nspname = $1
       FROM pg_catalog.pg_depend AS dep
       WHERE dep.classid = 'pg_trigger'::regclass
         AND dep.objid = tg.oid
-        AND dep.refclassid = 'pg_extension'::regclass
+        AND dep.deptype = 'e'
   );
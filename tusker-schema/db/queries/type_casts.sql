@@ -0,0 +1,9 @@
+-- All casts including the built-in ones. Used to classify column type
+-- changes.
+SELECT
+    format_type(c.castsource, NULL) AS source,
+    format_type(c.casttarget, NULL) AS target,
+    c.castcontext::text AS context,
+    c.castmethod::text AS method
+FROM pg_catalog.pg_cast AS c
+ORDER BY source, target;
@@ -1,6 +1,11 @@
 use std::{cmp::Reverse, collections::HashMap, fmt::Debug, hash::Hash};
 
-use crate::models::constraint::ConstraintType;
+use serde::Serialize;
+
+use crate::{
+    filter::ObjectKind,
+    models::{constraint::ConstraintType, type_change::TypeCasts},
+};
 
 #[derive(Debug, Eq, PartialEq)]
 pub struct Diff<'a, T: Eq> {
@@ -9,6 +14,33 @@ pub struct Diff<'a, T: Eq> {
     pub b_only: Vec<&'a T>,
 }
 
+impl<'a, T: Eq> Diff<'a, T> {
+    /// Split the diff into one diff per object. The object of `a_and_b`
+    /// pairs is the one of `b`.
+    pub fn split(&self) -> Vec<(&'a T, Self)> {
+        let only = |a_only, b_only| Diff {
+            a_only,
+            a_and_b: vec![],
+            b_only,
+        };
+        self.a_only
+            .iter()
+            .map(|&a| (a, only(vec![a], vec![])))
+            .chain(self.a_and_b.iter().map(|&(a, b)| {
+                (
+                    b,
+                    Diff {
+                        a_only: vec![],
+                        a_and_b: vec![(a, b)],
+                        b_only: vec![],
+                    },
+                )
+            }))
+            .chain(self.b_only.iter().map(|&b| (b, only(vec![], vec![b]))))
+            .collect()
+    }
+}
+
 pub fn diff<'a, T: Eq, K>(
     a: impl Iterator<Item = &'a T>,
     b: impl Iterator<Item = &'a T>,
@@ -39,16 +71,40 @@ where
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
 pub enum ChangeType {
+    // Event triggers are dropped first so they don't fire for any of
+    // the following statements.
+    DropEventTrigger,
+    // Tables are removed from publications before they or any of the
+    // published columns are dropped.
+    DropPublication,
     // DROP CONSTRAINT statements must be generated in reverse
     // order.
     DropConstraint(Reverse<ConstraintType>),
+    // Inheritance must be removed before the parent table or any of
+    // its columns are dropped.
+    DropInherit,
+    DropStatistics,
     DropColumn,
     DropTrigger,
+    // Indexes may use operator classes and routines.
+    DropIndex,
+    // Concurrent index changes can't run inside a transaction.
+    DropIndexConcurrently,
+    // Mappings are removed before the dictionaries they use and
+    // added again once the new dictionaries exist.
+    DropTextSearchMapping,
+    DropTextSearchConfiguration,
+    DropTextSearchDictionary,
+    DropTextSearchTemplate,
+    DropTextSearchParser,
+    DropOperatorClass,
+    DropOperatorFamily,
+    DropOperator,
+    DropCast,
     DropRoutine,
     DropSequence,
-    DropIndex,
     DropTable,
     DropType,
     DropExtension,
@@ -56,6 +112,7 @@ pub enum ChangeType {
     AlterExtension,
     AlterSequence,
     AlterType,
+    AlterTable,
     AlterColumn,
     Unsupported,
     CreateSchema,
@@ -63,13 +120,173 @@ pub enum ChangeType {
     CreateSequence,
     CreateType,
     CreateRoutine,
+    // Operators and casts are implemented by routines and used by
+    // operator classes, tables and indexes.
+    CreateOperator,
+    AlterOperator,
+    CreateCast,
+    CreateOperatorFamily,
+    AlterOperatorFamily,
+    CreateOperatorClass,
+    // Text search objects are used by tables and indexes.
+    CreateTextSearchParser,
+    CreateTextSearchTemplate,
+    CreateTextSearchDictionary,
+    AlterTextSearchDictionary,
+    CreateTextSearchConfiguration,
+    AlterTextSearchConfiguration,
     CreateTable,
     CreateIndex,
+    CreateIndexConcurrently,
     CreateColumn,
+    // Statistics refer to columns and may contain expressions using
+    // routines.
+    CreateStatistics,
+    AlterStatistics,
+    // Indexes of primary keys and unique constraints built concurrently
+    // before the constraint is added using them.
+    CreateConstraintIndexConcurrently,
     CreateConstraint(ConstraintType),
+    AlterConstraint,
+    // Children must have all columns and constraints of the parent
+    // before they can inherit from it.
+    Inherit,
     CreateTrigger,
+    // Constraints added as NOT VALID are validated once the migration
+    // is otherwise done.
+    ValidateConstraint,
+    // Constraints added online are validated in a transaction of their
+    // own so `ADD CONSTRAINT` doesn't hold its lock during the scan.
+    ValidateConstraintOnline,
+    // Publications refer to tables, columns and schemas.
+    CreatePublication,
+    AlterPublication,
+    AlterSubscription,
+    // Event triggers execute routines and fire for DDL commands. They
+    // are created once the routines exist and most of the migration
+    // is done.
+    CreateEventTrigger,
+    AlterEventTrigger,
+    // Owners are changed once all objects exist.
+    AlterOwner,
+    // Comments are set once all objects they refer to exist.
+    Comment,
+}
+
+impl ChangeType {
+    /// Whether the change drops an object
+    pub fn is_destructive(&self) -> bool {
+        matches!(
+            self,
+            Self::DropEventTrigger
+                | Self::DropPublication
+                | Self::DropConstraint(_)
+                | Self::DropInherit
+                | Self::DropStatistics
+                | Self::DropColumn
+                | Self::DropTrigger
+                | Self::DropIndex
+                | Self::DropIndexConcurrently
+                | Self::DropTextSearchMapping
+                | Self::DropTextSearchConfiguration
+                | Self::DropTextSearchDictionary
+                | Self::DropTextSearchTemplate
+                | Self::DropTextSearchParser
+                | Self::DropOperatorClass
+                | Self::DropOperatorFamily
+                | Self::DropOperator
+                | Self::DropCast
+                | Self::DropRoutine
+                | Self::DropSequence
+                | Self::DropTable
+                | Self::DropType
+                | Self::DropExtension
+                | Self::DropSchema
+        )
+    }
+    /// Whether the change can't be run inside the transaction of the
+    /// migration. It either uses `CONCURRENTLY` or has to commit on its
+    /// own.
+    pub fn is_non_transactional(&self) -> bool {
+        matches!(
+            self,
+            Self::DropIndexConcurrently
+                | Self::CreateIndexConcurrently
+                | Self::CreateConstraintIndexConcurrently
+                | Self::ValidateConstraintOnline
+        )
+    }
+}
+
+/// Single statement of a diff and the object it belongs to
+#[derive(Debug, Clone, Eq, PartialEq, Serialize)]
+pub struct Change {
+    pub kind: ObjectKind,
+    /// `None` for objects which don't belong to a schema
+    pub schema: Option<String>,
+    /// Name of the object. Objects belonging to a table are prefixed
+    /// with the table name and routines are followed by their arguments.
+    pub name: String,
+    pub change_type: ChangeType,
+    /// The statement drops an object or a column
+    pub destructive: bool,
+    pub sql: String,
+}
+
+/// Attribute the statements of a diff to the objects they belong to
+pub fn object_changes<'a, T: Eq>(
+    diff: &Diff<'a, T>,
+    options: &DiffOptions,
+    kind: ObjectKind,
+    schema: Option<&str>,
+    name: impl Fn(&T) -> String,
+) -> Vec<Change>
+where
+    Diff<'a, T>: DiffSql,
+{
+    diff.split()
+        .into_iter()
+        .flat_map(|(object, diff)| {
+            let name = name(object);
+            diff.sql_with(options)
+                .into_iter()
+                .map(move |(change_type, sql)| Change {
+                    kind,
+                    schema: schema.map(Into::into),
+                    name: name.clone(),
+                    change_type,
+                    destructive: change_type.is_destructive(),
+                    sql,
+                })
+        })
+        .collect()
 }
 
 pub trait DiffSql {
     fn sql(&self) -> Vec<(ChangeType, String)>;
+    /// Generate the SQL using non-default options. Only diffs which
+    /// are affected by any of the options need to implement this.
+    fn sql_with(&self, options: &DiffOptions) -> Vec<(ChangeType, String)> {
+        let _ = options;
+        self.sql()
+    }
+}
+
+/// Options controlling how changes are turned into SQL
+#[derive(Debug, Clone, Default)]
+pub struct DiffOptions {
+    /// Add foreign keys and check constraints as `NOT VALID` and
+    /// validate them using a separate `VALIDATE CONSTRAINT` statement.
+    /// This avoids scanning the table while holding the `ACCESS
+    /// EXCLUSIVE` lock taken by `ADD CONSTRAINT`. Such migrations can't
+    /// run inside a transaction.
+    pub online_constraints: bool,
+    /// Create and drop indexes using `CONCURRENTLY` and back new primary
+    /// keys and unique constraints by a concurrently built index. Such
+    /// migrations can't run inside a transaction.
+    pub concurrent_indexes: bool,
+    /// Casts of the server the migration is generated for. Used to
+    /// decide whether column type changes rewrite the table or need a
+    /// `USING` expression. If empty, type changes are not classified.
+    pub type_casts: TypeCasts,
 }
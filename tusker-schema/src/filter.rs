@@ -0,0 +1,368 @@
+use std::{fmt, str::FromStr};
+
+use glob::Pattern;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::models::schema::Schema;
+
+/// Kind of a schema object as used by the `kind:` prefix of an
+/// [`ObjectPattern`] and the JSON output of the diff.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Ord, PartialOrd, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ObjectKind {
+    Schema,
+    Extension,
+    /// Enums and domains
+    Type,
+    Sequence,
+    Table,
+    View,
+    // Indexes and statistics can't be filtered directly but follow their
+    // table like columns, constraints and triggers.
+    Index,
+    Statistics,
+    /// Functions and procedures
+    Routine,
+    Operator,
+    OperatorFamily,
+    OperatorClass,
+    /// Text search parsers, templates, dictionaries and configurations
+    TextSearch,
+    Column,
+    Constraint,
+    Trigger,
+    // Database level objects aren't part of a schema. Their patterns
+    // consist of the kind and the name only.
+    Publication,
+    Subscription,
+    EventTrigger,
+    /// Casts are named `source AS target`
+    Cast,
+}
+
+impl ObjectKind {
+    /// Kinds which can be used in patterns
+    const FILTERABLE: [Self; 15] = [
+        Self::Schema,
+        Self::Extension,
+        Self::Type,
+        Self::Sequence,
+        Self::Table,
+        Self::View,
+        Self::Routine,
+        Self::Operator,
+        Self::OperatorFamily,
+        Self::OperatorClass,
+        Self::TextSearch,
+        Self::Publication,
+        Self::Subscription,
+        Self::EventTrigger,
+        Self::Cast,
+    ];
+
+    /// Kinds of objects which don't belong to a schema
+    fn is_database_level(&self) -> bool {
+        matches!(
+            self,
+            Self::Publication | Self::Subscription | Self::EventTrigger | Self::Cast
+        )
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Schema => "schema",
+            Self::Extension => "extension",
+            Self::Type => "type",
+            Self::Sequence => "sequence",
+            Self::Table => "table",
+            Self::Column => "column",
+            Self::View => "view",
+            Self::Index => "index",
+            Self::Statistics => "statistics",
+            Self::Routine => "routine",
+            Self::Operator => "operator",
+            Self::OperatorFamily => "operator_family",
+            Self::OperatorClass => "operator_class",
+            Self::TextSearch => "text_search",
+            Self::Constraint => "constraint",
+            Self::Trigger => "trigger",
+            Self::Publication => "publication",
+            Self::Subscription => "subscription",
+            Self::EventTrigger => "event_trigger",
+            Self::Cast => "cast",
+        }
+    }
+}
+
+impl fmt::Display for ObjectKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum InvalidPattern {
+    #[error("Unknown object kind `{0}` in pattern `{1}`")]
+    Kind(String, String),
+    #[error("Invalid glob in pattern `{0}`: {1}")]
+    Glob(String, glob::PatternError),
+}
+
+/// Glob pattern matching schema objects. The syntax is
+/// `[kind:]schema[.name]`, e.g. `cron`, `public.tmp_*` or
+/// `table:public.audit_*`. A pattern without a name matches every object
+/// of the schema and one without a kind matches objects of all kinds.
+/// Database level objects are matched by `kind:name`, e.g.
+/// `publication:debezium_*` or `cast:citext AS *`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct ObjectPattern {
+    source: String,
+    kind: Option<ObjectKind>,
+    schema: Pattern,
+    name: Option<Pattern>,
+}
+
+impl ObjectPattern {
+    pub fn matches(&self, kind: ObjectKind, schema: &str, name: &str) -> bool {
+        self.kind.is_none_or(|k| k == kind)
+            && self.schema.matches(schema)
+            && self.name.as_ref().is_none_or(|p| p.matches(name))
+    }
+
+    /// Whether the pattern matches a database level object
+    fn matches_database_level(&self, kind: ObjectKind, name: &str) -> bool {
+        self.kind == Some(kind) && self.schema.matches(name)
+    }
+
+    /// Whether the pattern matches the schema and all of its objects
+    fn matches_whole_schema(&self, schema: &str) -> bool {
+        self.kind.is_none_or(|k| k == ObjectKind::Schema)
+            && self.schema.matches(schema)
+            && self.name.as_ref().is_none_or(|p| p.as_str() == "*")
+    }
+}
+
+impl FromStr for ObjectPattern {
+    type Err = InvalidPattern;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (kind, rest) = match s.split_once(':') {
+            Some((kind, rest)) => {
+                let kind = ObjectKind::FILTERABLE
+                    .into_iter()
+                    .find(|k| k.as_str() == kind)
+                    .ok_or_else(|| InvalidPattern::Kind(kind.into(), s.into()))?;
+                (Some(kind), rest)
+            }
+            None => (None, s),
+        };
+        let glob = |p: &str| Pattern::new(p).map_err(|e| InvalidPattern::Glob(s.into(), e));
+        // Names of database level objects are stored in place of the
+        // schema as they may contain dots, e.g. `cast:public.a AS text`.
+        let (schema, name) = match rest.split_once('.') {
+            Some(_) if kind.is_some_and(|k| k.is_database_level()) => (glob(rest)?, None),
+            Some((schema, name)) => (glob(schema)?, Some(glob(name)?)),
+            None => (glob(rest)?, None),
+        };
+        Ok(Self {
+            source: s.into(),
+            kind,
+            schema,
+            name,
+        })
+    }
+}
+
+impl TryFrom<String> for ObjectPattern {
+    type Error = InvalidPattern;
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl From<ObjectPattern> for String {
+    fn from(pattern: ObjectPattern) -> Self {
+        pattern.source
+    }
+}
+
+impl fmt::Display for ObjectPattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.source)
+    }
+}
+
+/// Objects to inspect. If `include` is empty all objects are included.
+/// Objects matching any of the `exclude` patterns are never inspected.
+/// Indexes, statistics, triggers and constraints follow the table they
+/// belong to.
+///
+/// Database level objects (publications, subscriptions, event triggers
+/// and casts) are only filtered by patterns of their kind. They are
+/// included unless there are include patterns of the same kind.
+#[derive(Debug, Clone, Default)]
+pub struct ObjectFilter {
+    pub include: Vec<ObjectPattern>,
+    pub exclude: Vec<ObjectPattern>,
+}
+
+impl ObjectFilter {
+    pub fn includes(&self, kind: ObjectKind, schema: &str, name: &str) -> bool {
+        (self.include.is_empty() || self.include.iter().any(|p| p.matches(kind, schema, name)))
+            && !self.exclude.iter().any(|p| p.matches(kind, schema, name))
+    }
+
+    /// Whether a publication, subscription, event trigger or cast is
+    /// included
+    pub fn includes_database_level(&self, kind: ObjectKind, name: &str) -> bool {
+        let mut include = self
+            .include
+            .iter()
+            .filter(|p| p.kind == Some(kind))
+            .peekable();
+        (include.peek().is_none() || include.any(|p| p.matches_database_level(kind, name)))
+            && !self
+                .exclude
+                .iter()
+                .any(|p| p.matches_database_level(kind, name))
+    }
+
+    /// Whether the schema needs to be inspected at all. A schema is
+    /// inspected if any include pattern refers to it and it is not
+    /// excluded as a whole.
+    pub fn includes_schema(&self, schema: &str) -> bool {
+        let refers_to = |p: &ObjectPattern| {
+            !p.kind.is_some_and(|k| k.is_database_level()) && p.schema.matches(schema)
+        };
+        (self.include.is_empty() || self.include.iter().any(refers_to))
+            && !self.exclude.iter().any(|p| p.matches_whole_schema(schema))
+    }
+
+    /// Remove all objects from the schema which are not included
+    pub fn apply(&self, schema: &mut Schema) {
+        if self.include.is_empty() && self.exclude.is_empty() {
+            return;
+        }
+        let s = schema.name.clone();
+        let includes = |kind, name: &str| self.includes(kind, &s, name);
+        schema
+            .extensions
+            .retain(|name, _| includes(ObjectKind::Extension, name));
+        schema
+            .enums
+            .retain(|name, _| includes(ObjectKind::Type, name));
+        schema
+            .domains
+            .retain(|name, _| includes(ObjectKind::Type, name));
+        schema
+            .sequences
+            .retain(|name, _| includes(ObjectKind::Sequence, name));
+        schema
+            .tables
+            .retain(|name, _| includes(ObjectKind::Table, name));
+        schema
+            .views
+            .retain(|name, _| includes(ObjectKind::View, name));
+        let tables = schema.tables.keys().cloned().collect::<Vec<_>>();
+        // Indexes, statistics and triggers may also belong to
+        // materialized views.
+        let relations = tables
+            .iter()
+            .chain(schema.views.keys())
+            .cloned()
+            .collect::<Vec<_>>();
+        schema
+            .indexes
+            .retain(|_, index| relations.contains(&index.table_name));
+        schema
+            .statistics
+            .retain(|_, statistics| relations.contains(&statistics.table_name));
+        schema
+            .triggers
+            .retain(|(table, _), _| relations.contains(table));
+        schema
+            .constraints
+            .retain(|(table, _), _| tables.contains(table));
+        schema
+            .routines
+            .retain(|(name, _), _| includes(ObjectKind::Routine, name));
+        schema
+            .operators
+            .retain(|(name, _, _), _| includes(ObjectKind::Operator, name));
+        schema
+            .operator_families
+            .retain(|(name, _), _| includes(ObjectKind::OperatorFamily, name));
+        schema
+            .operator_classes
+            .retain(|(name, _), _| includes(ObjectKind::OperatorClass, name));
+        schema
+            .text_search_parsers
+            .retain(|name, _| includes(ObjectKind::TextSearch, name));
+        schema
+            .text_search_templates
+            .retain(|name, _| includes(ObjectKind::TextSearch, name));
+        schema
+            .text_search_dictionaries
+            .retain(|name, _| includes(ObjectKind::TextSearch, name));
+        schema
+            .text_search_configurations
+            .retain(|name, _| includes(ObjectKind::TextSearch, name));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ObjectFilter, ObjectKind, ObjectPattern};
+
+    fn filter(include: &[&str], exclude: &[&str]) -> ObjectFilter {
+        ObjectFilter {
+            include: include.iter().map(|p| p.parse().unwrap()).collect(),
+            exclude: exclude.iter().map(|p| p.parse().unwrap()).collect(),
+        }
+    }
+
+    #[test]
+    fn excludes_schemas() {
+        let filter = filter(&[], &["cron", "pgboss.*", "public.tmp_*"]);
+        assert!(!filter.includes_schema("cron"));
+        assert!(!filter.includes_schema("pgboss"));
+        assert!(filter.includes_schema("public"));
+        assert!(!filter.includes(ObjectKind::Table, "public", "tmp_import"));
+        assert!(filter.includes(ObjectKind::Table, "public", "users"));
+    }
+
+    #[test]
+    fn includes_objects_by_kind() {
+        let filter = filter(&["app", "table:public.audit_*"], &["routine:app.debug_*"]);
+        assert!(filter.includes_schema("app"));
+        assert!(filter.includes_schema("public"));
+        assert!(!filter.includes_schema("cron"));
+        assert!(filter.includes(ObjectKind::Table, "public", "audit_log"));
+        assert!(!filter.includes(ObjectKind::View, "public", "audit_view"));
+        assert!(filter.includes(ObjectKind::Routine, "app", "login"));
+        assert!(!filter.includes(ObjectKind::Routine, "app", "debug_dump"));
+    }
+
+    #[test]
+    fn rejects_unknown_kinds() {
+        assert!("tbl:public.users".parse::<ObjectPattern>().is_err());
+        assert!("index:public.users_pkey".parse::<ObjectPattern>().is_err());
+    }
+
+    #[test]
+    fn filters_database_level_objects_by_kind() {
+        let filter = filter(
+            &["public", "subscription:app_*"],
+            &["publication:debezium_*", "cast:public.citext AS *"],
+        );
+        assert!(!filter.includes_database_level(ObjectKind::Publication, "debezium_pub"));
+        assert!(filter.includes_database_level(ObjectKind::Publication, "app_pub"));
+        assert!(filter.includes_database_level(ObjectKind::Subscription, "app_sub"));
+        assert!(!filter.includes_database_level(ObjectKind::Subscription, "other_sub"));
+        assert!(!filter.includes_schema("app_sub"));
+        assert!(filter.includes_database_level(ObjectKind::EventTrigger, "public"));
+        assert!(!filter.includes_database_level(ObjectKind::Cast, "public.citext AS text"));
+        assert!(filter.includes_database_level(ObjectKind::Cast, "text AS public.citext"));
+    }
+}
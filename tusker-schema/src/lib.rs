@@ -1,11 +1,17 @@
 use std::collections::HashMap;
 
 use anyhow::Result;
-use diff::{diff, Diff};
+use diff::{diff, object_changes, Change, ChangeType, Diff, DiffOptions, DiffSql};
+use filter::{ObjectFilter, ObjectKind};
 use itertools::Itertools;
 use models::{
-    constraint::Constraint, domain::Domain, extension::Extension, r#enum::Enum, routine::Routine,
-    schema::Schema, sequence::Sequence, table::Table, trigger::Trigger, view::View,
+    cast::Cast, constraint::Constraint, domain::Domain, event_trigger::EventTrigger,
+    extension::Extension, operator::Operator, operator_class::OperatorClass,
+    operator_family::OperatorFamily, publication::Publication, r#enum::Enum, routine::Routine,
+    schema::Schema, sequence::Sequence, statistics::Statistics, subscription::Subscription,
+    table::Table, text_search_configuration::TextSearchConfiguration,
+    text_search_dictionary::TextSearchDictionary, text_search_parser::TextSearchParser,
+    text_search_template::TextSearchTemplate, trigger::Trigger, type_change::TypeCasts, view::View,
 };
 use queries::Relkind;
 use tokio_postgres::Client;
@@ -13,35 +19,301 @@ use tokio_postgres::Client;
 use crate::models::constraint::ConstraintType;
 
 pub mod diff;
+pub mod filter;
 pub mod models;
 pub mod queries;
 pub(crate) mod sql;
+pub mod summary;
 
 #[derive(Debug, Eq, PartialEq)]
 pub struct Inspection {
     pub schemas: HashMap<String, Schema>,
+    pub publications: HashMap<String, Publication>,
+    pub subscriptions: HashMap<String, Subscription>,
+    pub event_triggers: HashMap<String, EventTrigger>,
+    pub casts: HashMap<(String, String), Cast>,
 }
 
 impl Inspection {
     pub fn empty() -> Self {
         Self {
             schemas: Default::default(),
+            publications: Default::default(),
+            subscriptions: Default::default(),
+            event_triggers: Default::default(),
+            casts: Default::default(),
         }
     }
-    pub fn diff<'a>(&'a self, other: &'a Self) -> Diff<'a, Schema> {
+    pub fn diff<'a>(&'a self, other: &'a Self) -> InspectionDiff<'a> {
+        InspectionDiff {
+            schemas: self.diff_schemas(other),
+            publications: self.diff_publications(other),
+            subscriptions: self.diff_subscriptions(other),
+            event_triggers: self.diff_event_triggers(other),
+            casts: self.diff_casts(other),
+        }
+    }
+    /// Tables whose columns end up in a different order than in `other`
+    /// when migrating this inspection to `other`. Pairs are returned as
+    /// `(self, other)`.
+    pub fn column_order_drift<'a>(&'a self, other: &'a Self) -> Vec<(&'a Table, &'a Table)> {
+        self.diff_schemas(other)
+            .a_and_b
+            .into_iter()
+            .flat_map(|(a, b)| a.diff_tables(b).a_and_b)
+            .filter(|(a, b)| Table::column_order_differs(a, b))
+            .collect()
+    }
+    pub fn diff_schemas<'a>(&'a self, other: &'a Self) -> Diff<'a, Schema> {
         diff(
             self.schemas.values().sorted_by(|a, b| a.name.cmp(&b.name)),
             other.schemas.values().sorted_by(|a, b| a.name.cmp(&b.name)),
             |schema| &schema.name,
         )
     }
+    pub fn diff_publications<'a>(&'a self, other: &'a Self) -> Diff<'a, Publication> {
+        diff(
+            self.publications
+                .values()
+                .sorted_by(|a, b| a.name.cmp(&b.name)),
+            other
+                .publications
+                .values()
+                .sorted_by(|a, b| a.name.cmp(&b.name)),
+            |publication| &publication.name,
+        )
+    }
+    pub fn diff_subscriptions<'a>(&'a self, other: &'a Self) -> Diff<'a, Subscription> {
+        diff(
+            self.subscriptions
+                .values()
+                .sorted_by(|a, b| a.name.cmp(&b.name)),
+            other
+                .subscriptions
+                .values()
+                .sorted_by(|a, b| a.name.cmp(&b.name)),
+            |subscription| &subscription.name,
+        )
+    }
+    /// Event triggers executing a routine which is recreated are
+    /// recreated, too.
+    pub fn diff_event_triggers<'a>(&'a self, other: &'a Self) -> Diff<'a, EventTrigger> {
+        let mut event_triggers = diff(
+            self.event_triggers
+                .values()
+                .sorted_by(|a, b| a.name.cmp(&b.name)),
+            other
+                .event_triggers
+                .values()
+                .sorted_by(|a, b| a.name.cmp(&b.name)),
+            |event_trigger| &event_trigger.name,
+        );
+        let (recreated, unchanged) = event_triggers
+            .a_and_b
+            .into_iter()
+            .partition(|(a, b)| self.routine_recreated(other, a, b));
+        event_triggers.a_and_b = unchanged;
+        for (a, b) in recreated {
+            event_triggers.a_only.push(a);
+            event_triggers.b_only.push(b);
+        }
+        event_triggers
+    }
+    fn routine_recreated(&self, other: &Self, a: &EventTrigger, b: &EventTrigger) -> bool {
+        match (
+            self.event_trigger_routine(a),
+            other.event_trigger_routine(b),
+        ) {
+            (Some(a), Some(b)) => !a.definition_eq(b),
+            _ => false,
+        }
+    }
+    /// Casts using a routine which is recreated are recreated, too.
+    pub fn diff_casts<'a>(&'a self, other: &'a Self) -> Diff<'a, Cast> {
+        let mut casts = diff(
+            self.casts
+                .values()
+                .sorted_by(|a, b| (&a.source, &a.target).cmp(&(&b.source, &b.target))),
+            other
+                .casts
+                .values()
+                .sorted_by(|a, b| (&a.source, &a.target).cmp(&(&b.source, &b.target))),
+            |cast| (&cast.source, &cast.target),
+        );
+        let (recreated, unchanged) = casts.a_and_b.into_iter().partition(|(a, b)| {
+            match (self.cast_routine(a), other.cast_routine(b)) {
+                (Some(a), Some(b)) => !a.definition_eq(b),
+                _ => false,
+            }
+        });
+        casts.a_and_b = unchanged;
+        for (a, b) in recreated {
+            casts.a_only.push(a);
+            casts.b_only.push(b);
+        }
+        casts
+    }
+    fn cast_routine(&self, cast: &Cast) -> Option<&Routine> {
+        let function = cast.function.as_ref()?;
+        self.schemas
+            .get(&function.schema)?
+            .routines
+            .get(&(function.name.clone(), function.arguments.clone()))
+    }
+    fn event_trigger_routine(&self, event_trigger: &EventTrigger) -> Option<&Routine> {
+        // Event trigger functions never take any arguments
+        self.schemas
+            .get(&event_trigger.function_schema)?
+            .routines
+            .get(&(event_trigger.function_name.clone(), String::new()))
+    }
+}
+
+/// Difference between two inspections. Publications, subscriptions,
+/// event triggers and casts are not part of a schema and therefore
+/// diffed separately.
+#[derive(Debug, Eq, PartialEq)]
+pub struct InspectionDiff<'a> {
+    pub schemas: Diff<'a, Schema>,
+    pub publications: Diff<'a, Publication>,
+    pub subscriptions: Diff<'a, Subscription>,
+    pub event_triggers: Diff<'a, EventTrigger>,
+    pub casts: Diff<'a, Cast>,
+}
+
+impl DiffSql for InspectionDiff<'_> {
+    fn sql(&self) -> Vec<(ChangeType, String)> {
+        self.sql_with(&DiffOptions::default())
+    }
+    fn sql_with(&self, options: &DiffOptions) -> Vec<(ChangeType, String)> {
+        self.changes(options)
+            .into_iter()
+            .map(|change| (change.change_type, change.sql))
+            .collect()
+    }
+}
+
+impl InspectionDiff<'_> {
+    /// Statements of the diff attributed to the objects they belong to.
+    /// The changes are in the same order as the statements returned by
+    /// `join_sql`.
+    pub fn changes(&self, options: &DiffOptions) -> Vec<Change> {
+        let mut v = self.schemas.changes(options);
+        v.extend(self.global_changes(options));
+        // The sort is stable and keeps the dependency order of the
+        // objects within each change type.
+        v.sort_by_key(|change| change.change_type);
+        v
+    }
+    /// Changes of the objects which are not part of a schema
+    fn global_changes(&self, options: &DiffOptions) -> Vec<Change> {
+        let mut v = object_changes(
+            &self.publications,
+            options,
+            ObjectKind::Publication,
+            None,
+            |p| p.name.clone(),
+        );
+        v.extend(object_changes(
+            &self.subscriptions,
+            options,
+            ObjectKind::Subscription,
+            None,
+            |s| s.name.clone(),
+        ));
+        v.extend(object_changes(
+            &self.event_triggers,
+            options,
+            ObjectKind::EventTrigger,
+            None,
+            |e| e.name.clone(),
+        ));
+        v.extend(object_changes(
+            &self.casts,
+            options,
+            ObjectKind::Cast,
+            None,
+            |c| format!("{} AS {}", c.source, c.target),
+        ));
+        v
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct InspectOptions {
+    /// Inspect object owners. This is disabled by default as the
+    /// temporary database used for diffing is usually owned by a
+    /// different role than the deployed database.
+    pub owners: bool,
+    /// Translate owner roles before comparing them. Roles not contained
+    /// in this map are used as is.
+    pub roles: HashMap<String, String>,
+    /// Only inspect objects matching this filter
+    pub filter: ObjectFilter,
+}
+
+impl InspectOptions {
+    fn owner(&self, owner: Option<String>) -> Option<String> {
+        if !self.owners {
+            return None;
+        }
+        owner.map(|owner| self.roles.get(&owner).cloned().unwrap_or(owner))
+    }
+    fn apply_owners(&self, schema: &mut Schema) {
+        schema.owner = self.owner(schema.owner.take());
+        for e in schema.enums.values_mut() {
+            e.owner = self.owner(e.owner.take());
+        }
+        for domain in schema.domains.values_mut() {
+            domain.owner = self.owner(domain.owner.take());
+        }
+        for sequence in schema.sequences.values_mut() {
+            sequence.owner = self.owner(sequence.owner.take());
+        }
+        for table in schema.tables.values_mut() {
+            table.owner = self.owner(table.owner.take());
+        }
+        for statistics in schema.statistics.values_mut() {
+            statistics.owner = self.owner(statistics.owner.take());
+        }
+        for operator in schema.operators.values_mut() {
+            operator.owner = self.owner(operator.owner.take());
+        }
+        for family in schema.operator_families.values_mut() {
+            family.owner = self.owner(family.owner.take());
+        }
+        for class in schema.operator_classes.values_mut() {
+            class.owner = self.owner(class.owner.take());
+        }
+        for dictionary in schema.text_search_dictionaries.values_mut() {
+            dictionary.owner = self.owner(dictionary.owner.take());
+        }
+        for configuration in schema.text_search_configurations.values_mut() {
+            configuration.owner = self.owner(configuration.owner.take());
+        }
+        for view in schema.views.values_mut() {
+            view.owner = self.owner(view.owner.take());
+        }
+        for routine in schema.routines.values_mut() {
+            routine.owner = self.owner(routine.owner.take());
+        }
+    }
 }
 
 pub async fn inspect(client: &Client) -> Result<Inspection> {
+    inspect_with(client, &InspectOptions::default()).await
+}
+
+pub async fn inspect_with(client: &Client, options: &InspectOptions) -> Result<Inspection> {
     let mut schemas: HashMap<String, Schema> = HashMap::new();
     let rows = tusker_query::query(client, queries::Schemas {}).await?;
-    for schema in rows {
-        let mut schema = Schema::new(&schema.name);
+    for row in rows {
+        if !options.filter.includes_schema(&row.name) {
+            continue;
+        }
+        let mut schema = Schema::new(&row.name);
+        schema.comment = row.comment;
+        schema.owner = Some(row.owner);
         // Enums
         let rows = tusker_query::query(
             client,
@@ -102,6 +374,20 @@ pub async fn inspect(client: &Client) -> Result<Inspection> {
             let index = models::index::Index::from(row);
             schema.indexes.insert(index.name.clone(), index);
         }
+        // Statistics
+        let rows = tusker_query::query(
+            client,
+            queries::Statistics {
+                schema: schema.name.clone(),
+            },
+        )
+        .await?;
+        for row in rows {
+            let statistics = Statistics::from(row);
+            schema
+                .statistics
+                .insert(statistics.name.clone(), statistics);
+        }
         // Tables
         let rows = tusker_query::query(
             client,
@@ -141,13 +427,7 @@ pub async fn inspect(client: &Client) -> Result<Inspection> {
         )
         .await?;
         for row in rows {
-            let constraint = Constraint {
-                schema: schema.name.clone(),
-                table: row.table,
-                name: row.name,
-                r#type: row.r#type,
-                definition: row.def,
-            };
+            let constraint = Constraint::from_row(&schema.name, row);
             if constraint.r#type == ConstraintType::NotNull {
                 // Skip NOT NULL constraints introduced in PostgreSQL 18
                 // It might be useful to support named not null constraints
@@ -175,6 +455,95 @@ pub async fn inspect(client: &Client) -> Result<Inspection> {
                 routine,
             );
         }
+        // Operators
+        let rows = tusker_query::query(
+            client,
+            queries::Operators {
+                schema: schema.name.clone(),
+            },
+        )
+        .await?;
+        for row in rows {
+            let operator = Operator::from(row);
+            schema.operators.insert(operator.key(), operator);
+        }
+        // Operator families
+        let rows = tusker_query::query(
+            client,
+            queries::OperatorFamilies {
+                schema: schema.name.clone(),
+            },
+        )
+        .await?;
+        for row in rows {
+            let family = OperatorFamily::from(row);
+            schema.operator_families.insert(family.key(), family);
+        }
+        // Operator classes
+        let rows = tusker_query::query(
+            client,
+            queries::OperatorClasses {
+                schema: schema.name.clone(),
+            },
+        )
+        .await?;
+        for row in rows {
+            let class = OperatorClass::from(row);
+            schema.operator_classes.insert(class.key(), class);
+        }
+        // Text search
+        let rows = tusker_query::query(
+            client,
+            queries::TextSearchParsers {
+                schema: schema.name.clone(),
+            },
+        )
+        .await?;
+        for row in rows {
+            let parser = TextSearchParser::from(row);
+            schema
+                .text_search_parsers
+                .insert(parser.name.clone(), parser);
+        }
+        let rows = tusker_query::query(
+            client,
+            queries::TextSearchTemplates {
+                schema: schema.name.clone(),
+            },
+        )
+        .await?;
+        for row in rows {
+            let template = TextSearchTemplate::from(row);
+            schema
+                .text_search_templates
+                .insert(template.name.clone(), template);
+        }
+        let rows = tusker_query::query(
+            client,
+            queries::TextSearchDictionaries {
+                schema: schema.name.clone(),
+            },
+        )
+        .await?;
+        for row in rows {
+            let dictionary = TextSearchDictionary::from(row);
+            schema
+                .text_search_dictionaries
+                .insert(dictionary.name.clone(), dictionary);
+        }
+        let rows = tusker_query::query(
+            client,
+            queries::TextSearchConfigurations {
+                schema: schema.name.clone(),
+            },
+        )
+        .await?;
+        for row in rows {
+            let configuration = TextSearchConfiguration::from(row);
+            schema
+                .text_search_configurations
+                .insert(configuration.name.clone(), configuration);
+        }
         // Triggers
         let rows = tusker_query::query(
             client,
@@ -189,8 +558,100 @@ pub async fn inspect(client: &Client) -> Result<Inspection> {
                 .triggers
                 .insert((trigger.table_name.clone(), trigger.name.clone()), trigger);
         }
+        options.filter.apply(&mut schema);
+        options.apply_owners(&mut schema);
         schemas.insert(schema.name.clone(), schema);
     }
 
-    Ok(Inspection { schemas })
+    let mut publications = HashMap::new();
+    for row in tusker_query::query(client, queries::Publications {}).await? {
+        let mut publication = Publication::from(row);
+        publication.owner = options.owner(publication.owner.take());
+        if options
+            .filter
+            .includes_database_level(ObjectKind::Publication, &publication.name)
+        {
+            publications.insert(publication.name.clone(), publication);
+        }
+    }
+
+    let mut subscriptions = HashMap::new();
+    for row in tusker_query::query(client, queries::Subscriptions {}).await? {
+        let mut subscription = Subscription::from(row);
+        subscription.owner = options.owner(subscription.owner.take());
+        if options
+            .filter
+            .includes_database_level(ObjectKind::Subscription, &subscription.name)
+        {
+            subscriptions.insert(subscription.name.clone(), subscription);
+        }
+    }
+
+    let mut event_triggers = HashMap::new();
+    for row in tusker_query::query(client, queries::EventTriggers {}).await? {
+        let mut event_trigger = EventTrigger::from(row);
+        event_trigger.owner = options.owner(event_trigger.owner.take());
+        if options
+            .filter
+            .includes_database_level(ObjectKind::EventTrigger, &event_trigger.name)
+        {
+            event_triggers.insert(event_trigger.name.clone(), event_trigger);
+        }
+    }
+
+    let mut casts = HashMap::new();
+    for row in tusker_query::query(client, queries::Casts {}).await? {
+        let cast = Cast::from(row);
+        let name = format!("{} AS {}", cast.source, cast.target);
+        if options
+            .filter
+            .includes_database_level(ObjectKind::Cast, &name)
+        {
+            casts.insert((cast.source.clone(), cast.target.clone()), cast);
+        }
+    }
+
+    Ok(Inspection {
+        schemas,
+        publications,
+        subscriptions,
+        event_triggers,
+        casts,
+    })
+}
+
+/// Load all casts known to the server including the built-in ones.
+/// They are passed to the diff via [`DiffOptions::type_casts`].
+pub async fn inspect_type_casts(client: &Client) -> Result<TypeCasts> {
+    Ok(tusker_query::query(client, queries::TypeCasts {})
+        .await?
+        .into_iter()
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::InspectOptions;
+
+    #[test]
+    fn owners_are_ignored_unless_enabled() {
+        let options = InspectOptions::default();
+        assert_eq!(options.owner(Some("postgres".into())), None);
+    }
+
+    #[test]
+    fn owners_are_translated_using_roles() {
+        let options = InspectOptions {
+            owners: true,
+            roles: HashMap::from([("postgres".into(), "migrator".into())]),
+            ..Default::default()
+        };
+        assert_eq!(
+            options.owner(Some("postgres".into())),
+            Some("migrator".into())
+        );
+        assert_eq!(options.owner(Some("app".into())), Some("app".into()));
+    }
 }
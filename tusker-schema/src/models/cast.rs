@@ -0,0 +1,114 @@
+use crate::{
+    diff::{ChangeType, Diff, DiffSql},
+    queries::CastRow,
+    sql::{comment_sql, quote_ident},
+};
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Cast {
+    pub source: String,
+    pub target: String,
+    pub function: Option<CastFunction>,
+    /// `f` = function, `i` = inout, `b` = binary coercible
+    pub method: String,
+    /// `e` = explicit, `a` = assignment, `i` = implicit
+    pub context: String,
+    pub comment: Option<String>,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct CastFunction {
+    pub schema: String,
+    pub name: String,
+    pub arguments: String,
+}
+
+impl Cast {
+    fn name(&self) -> String {
+        format!("({} AS {})", self.source, self.target)
+    }
+
+    /// Compare everything but the comment.
+    fn definition_eq(&self, other: &Self) -> bool {
+        self.function == other.function
+            && self.method == other.method
+            && self.context == other.context
+    }
+
+    fn create_sql(&self) -> String {
+        let method = match (&self.function, self.method.as_str()) {
+            (Some(f), _) => format!(
+                "WITH FUNCTION {}.{}({})",
+                quote_ident(&f.schema),
+                quote_ident(&f.name),
+                f.arguments
+            ),
+            (None, "i") => "WITH INOUT".into(),
+            (None, _) => "WITHOUT FUNCTION".into(),
+        };
+        let context = match self.context.as_str() {
+            "a" => " AS ASSIGNMENT",
+            "i" => " AS IMPLICIT",
+            _ => "",
+        };
+        format!("CREATE CAST {} {}{};\n", self.name(), method, context)
+    }
+
+    fn drop_sql(&self) -> String {
+        format!("DROP CAST {};\n", self.name())
+    }
+
+    fn comment_sql(&self) -> String {
+        comment_sql("CAST", &self.name(), self.comment.as_deref())
+    }
+}
+
+impl From<CastRow> for Cast {
+    fn from(row: CastRow) -> Self {
+        let function = match (
+            row.function_schema,
+            row.function_name,
+            row.function_arguments,
+        ) {
+            (Some(schema), Some(name), Some(arguments)) => Some(CastFunction {
+                schema,
+                name,
+                arguments,
+            }),
+            _ => None,
+        };
+        Self {
+            source: row.source,
+            target: row.target,
+            function,
+            method: row.method,
+            context: row.context,
+            comment: row.comment,
+        }
+    }
+}
+
+impl DiffSql for Diff<'_, Cast> {
+    fn sql(&self) -> Vec<(ChangeType, String)> {
+        let mut v = Vec::new();
+        let mut creates = self.b_only.clone();
+        for a in &self.a_only {
+            v.push((ChangeType::DropCast, a.drop_sql()));
+        }
+        for (a, b) in &self.a_and_b {
+            if !a.definition_eq(b) {
+                v.push((ChangeType::DropCast, a.drop_sql()));
+                creates.push(b);
+            } else if a.comment != b.comment {
+                v.push((ChangeType::Comment, b.comment_sql()));
+            }
+        }
+        for b in creates {
+            v.push((ChangeType::CreateCast, b.create_sql()));
+            if b.comment.is_some() {
+                v.push((ChangeType::Comment, b.comment_sql()));
+            }
+        }
+        v
+    }
+}
@@ -1,18 +1,34 @@
 use serde::Deserialize;
 
 use crate::{
-    diff::{ChangeType, Diff, DiffSql},
+    diff::{ChangeType, Diff, DiffOptions, DiffSql},
     sql::{quote_ident, StatementBuilder},
 };
 
+use super::type_change::TypeChange;
+
 #[derive(Debug, Eq, PartialEq, Deserialize)]
 pub struct Column {
     pub name: String,
     pub r#type: String,
+    /// Type without the type modifier, e.g. `character varying`
+    pub base_type: String,
+    /// Type modifier, `-1` if the type has none
+    pub typmod: i32,
     pub notnull: bool,
     pub identity: Identity,
     pub generated: Generated,
     pub default: Option<String>,
+    #[serde(default)]
+    pub comment: Option<String>,
+    pub storage: Storage,
+    /// Storage mode of the column type. Used to decide whether the
+    /// storage of a new column needs to be set explicitly.
+    pub default_storage: Storage,
+    /// `None` if `default_toast_compression` is used
+    pub compression: Option<Compression>,
+    /// `None` if `default_statistics_target` is used
+    pub statistics: Option<i16>,
 }
 
 impl Column {
@@ -62,8 +78,45 @@ impl Column {
         format!("DROP COLUMN {}", quote_ident(&self.name))
     }
 
-    pub fn alter_changes(old: &Self, new: &Self) -> Vec<(ChangeType, String)> {
-        if old != new && (old.generated != Generated::No || new.generated != Generated::No) {
+    /// Compare everything but the comment and the attribute options
+    /// (storage, compression and statistics target). Comments are not
+    /// part of the `ALTER TABLE` statement and handled by
+    /// `Table::comment_sql`.
+    fn definition_eq(&self, other: &Self) -> bool {
+        self.name == other.name
+            && self.r#type == other.r#type
+            && self.notnull == other.notnull
+            && self.identity == other.identity
+            && self.generated == other.generated
+            && self.default == other.default
+    }
+
+    /// Names of the attributes which differ from `other`
+    pub fn changed_attributes(&self, other: &Self) -> Vec<&'static str> {
+        [
+            ("type", self.r#type != other.r#type),
+            ("not null", self.notnull != other.notnull),
+            ("default", self.default != other.default),
+            ("identity", self.identity != other.identity),
+            ("generated", self.generated != other.generated),
+            ("storage", self.storage != other.storage),
+            ("compression", self.compression != other.compression),
+            ("statistics", self.statistics != other.statistics),
+            ("comment", self.comment != other.comment),
+        ]
+        .into_iter()
+        .filter_map(|(name, changed)| changed.then_some(name))
+        .collect()
+    }
+
+    pub fn alter_changes(
+        old: &Self,
+        new: &Self,
+        options: &DiffOptions,
+    ) -> Vec<(ChangeType, String)> {
+        if !old.definition_eq(new)
+            && (old.generated != Generated::No || new.generated != Generated::No)
+        {
             return vec![(
                 ChangeType::Unsupported,
                 new.unsupported_alter_warning_sql(old),
@@ -72,14 +125,7 @@ impl Column {
 
         let mut sql = Vec::new();
         if old.r#type != new.r#type {
-            sql.push((
-                ChangeType::AlterColumn,
-                format!(
-                    "ALTER COLUMN {} TYPE {}",
-                    quote_ident(&new.name),
-                    new.r#type
-                ),
-            ));
+            sql.push((ChangeType::AlterColumn, new.alter_type_sql(old, options)));
         }
 
         match (&old.identity, &new.identity) {
@@ -167,6 +213,72 @@ impl Column {
             ));
         }
 
+        sql.extend(new.attribute_changes(Some(old)));
+
+        sql
+    }
+
+    /// `ALTER COLUMN ... TYPE` clause. Type changes which rewrite the
+    /// table are preceded by a warning and a `USING` expression is added
+    /// if there is no implicit or assignment cast.
+    fn alter_type_sql(&self, previous: &Self, options: &DiffOptions) -> String {
+        let name = quote_ident(&self.name);
+        let mut sql = String::new();
+        let change = TypeChange::classify(previous, self, &options.type_casts);
+        if matches!(change, TypeChange::Rewrite | TypeChange::ExplicitCast) {
+            sql.push_str(&format!(
+                "-- WARNING: changing the type of column {} from {} to {} rewrites the table \
+and its indexes while holding an ACCESS EXCLUSIVE lock\n    ",
+                name, previous.r#type, self.r#type
+            ));
+        }
+        sql.push_str(&format!("ALTER COLUMN {} TYPE {}", name, self.r#type));
+        if change == TypeChange::ExplicitCast {
+            sql.push_str(&format!(" USING {}::{}", name, self.r#type));
+        }
+        sql
+    }
+
+    /// `ALTER COLUMN` clauses for storage, compression and statistics
+    /// target. `previous` is `None` for new columns in which case only
+    /// non-default values are set. Changing the type resets the storage
+    /// to the default of the new type, too.
+    pub fn attribute_changes(&self, previous: Option<&Self>) -> Vec<(ChangeType, String)> {
+        let mut sql = Vec::new();
+        let previous_storage = match previous {
+            Some(p) if p.r#type == self.r#type => p.storage,
+            _ => self.default_storage,
+        };
+        if self.storage != previous_storage {
+            sql.push((
+                ChangeType::AlterColumn,
+                format!(
+                    "ALTER COLUMN {} SET STORAGE {}",
+                    quote_ident(&self.name),
+                    self.storage.sql()
+                ),
+            ));
+        }
+        if self.compression != previous.and_then(|p| p.compression) {
+            sql.push((
+                ChangeType::AlterColumn,
+                format!(
+                    "ALTER COLUMN {} SET COMPRESSION {}",
+                    quote_ident(&self.name),
+                    self.compression.map_or("DEFAULT", |c| c.sql())
+                ),
+            ));
+        }
+        if self.statistics != previous.and_then(|p| p.statistics) {
+            sql.push((
+                ChangeType::AlterColumn,
+                format!(
+                    "ALTER COLUMN {} SET STATISTICS {}",
+                    quote_ident(&self.name),
+                    self.statistics.unwrap_or(-1)
+                ),
+            ));
+        }
         sql
     }
 
@@ -199,6 +311,46 @@ pub enum Generated {
     Stored,
 }
 
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Deserialize)]
+pub enum Storage {
+    #[serde(rename = "p")]
+    Plain,
+    #[serde(rename = "e")]
+    External,
+    #[serde(rename = "m")]
+    Main,
+    #[serde(rename = "x")]
+    Extended,
+}
+
+impl Storage {
+    fn sql(&self) -> &'static str {
+        match self {
+            Self::Plain => "PLAIN",
+            Self::External => "EXTERNAL",
+            Self::Main => "MAIN",
+            Self::Extended => "EXTENDED",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Deserialize)]
+pub enum Compression {
+    #[serde(rename = "p")]
+    Pglz,
+    #[serde(rename = "l")]
+    Lz4,
+}
+
+impl Compression {
+    fn sql(&self) -> &'static str {
+        match self {
+            Self::Pglz => "pglz",
+            Self::Lz4 => "lz4",
+        }
+    }
+}
+
 #[derive(Debug, Eq, PartialEq, Deserialize)]
 pub enum Identity {
     #[serde(rename = "")]
@@ -211,17 +363,21 @@ pub enum Identity {
 
 impl DiffSql for Diff<'_, Column> {
     fn sql(&self) -> Vec<(ChangeType, String)> {
+        self.sql_with(&DiffOptions::default())
+    }
+    fn sql_with(&self, options: &DiffOptions) -> Vec<(ChangeType, String)> {
         let mut v = Vec::new();
         for a in &self.a_only {
             v.push((ChangeType::DropColumn, a.drop_sql()));
         }
         for (a, b) in &self.a_and_b {
             if a != b {
-                v.extend(Column::alter_changes(a, b));
+                v.extend(Column::alter_changes(a, b, options));
             }
         }
         for b in &self.b_only {
             v.push((ChangeType::CreateColumn, b.create_sql()));
+            v.extend(b.attribute_changes(None));
         }
         v
     }
@@ -229,17 +385,24 @@ impl DiffSql for Diff<'_, Column> {
 
 #[cfg(test)]
 mod tests {
-    use super::{Column, Generated, Identity};
+    use super::{Column, Generated, Identity, Storage};
 
     #[test]
     fn generated_columns_use_parenthesized_expression_without_default_clause() {
         let column = Column {
             name: "vacation_during".into(),
             r#type: "daterange".into(),
+            base_type: "daterange".into(),
+            typmod: -1,
             notnull: false,
             identity: Identity::No,
             generated: Generated::Stored,
             default: Some("daterange(start_date, end_date, '[]'::text)".into()),
+            comment: None,
+            storage: Storage::Plain,
+            default_storage: Storage::Plain,
+            compression: None,
+            statistics: None,
         };
 
         assert_eq!(
@@ -253,6 +416,8 @@ mod tests {
         let column = Column {
             name: "gross_minutes".into(),
             r#type: "integer".into(),
+            base_type: "integer".into(),
+            typmod: -1,
             notnull: false,
             identity: Identity::No,
             generated: Generated::Stored,
@@ -260,6 +425,11 @@ mod tests {
                 "\nCASE\n    WHEN ((start_time IS NULL) OR (end_time IS NULL)) THEN 0\n    ELSE (floor((EXTRACT(epoch FROM (end_time - start_time)) / (60)::numeric)))::integer\nEND"
                     .into(),
             ),
+            comment: None,
+            storage: Storage::Plain,
+            default_storage: Storage::Plain,
+            compression: None,
+            statistics: None,
         };
 
         assert_eq!(
@@ -2,29 +2,232 @@ use std::cmp::Reverse;
 
 use anyhow::anyhow;
 use postgres_types::FromSql;
+use serde::Serialize;
 
 use crate::{
-    diff::{ChangeType, Diff, DiffSql},
-    sql::quote_ident,
+    diff::{ChangeType, Diff, DiffOptions, DiffSql},
+    queries,
+    sql::{comment_sql, quote_ident},
 };
 
-#[derive(Debug, Eq, PartialEq)]
+#[derive(Debug, Eq)]
 pub struct Constraint {
     pub schema: String,
     pub table: String,
     pub name: String,
     pub r#type: ConstraintType,
+    /// Definition without the `DEFERRABLE`, `INITIALLY DEFERRED`, `NOT
+    /// ENFORCED` and `NOT VALID` suffixes
     pub definition: String,
+    /// `false` for constraints added as `NOT VALID` which have not been
+    /// validated, yet.
+    pub validated: bool,
+    pub deferrable: bool,
+    pub initially_deferred: bool,
+    /// Always `true` before PostgreSQL 18
+    pub enforced: bool,
+    /// Foreign key actions. These are part of the definition, too.
+    pub on_update: Option<ForeignKeyAction>,
+    pub on_delete: Option<ForeignKeyAction>,
+    /// `CREATE INDEX` statement of the index backing a primary key or
+    /// unique constraint
+    pub index_definition: Option<String>,
+    pub comment: Option<String>,
+}
+
+/// Constraints are compared without their validation. Whether one needs
+/// to be validated depends on the direction: the diff only validates
+/// constraints which are validated in the target. `tusker check` reports
+/// them using the diffs of both directions.
+impl PartialEq for Constraint {
+    fn eq(&self, other: &Self) -> bool {
+        self.schema == other.schema
+            && self.table == other.table
+            && self.name == other.name
+            && self.r#type == other.r#type
+            && self.definition == other.definition
+            && self.attributes_eq(other)
+            && self.on_update == other.on_update
+            && self.on_delete == other.on_delete
+            && self.comment == other.comment
+    }
 }
 
 impl Constraint {
-    fn create_sql(&self) -> String {
+    pub fn from_row(schema: &str, row: queries::Constraint) -> Self {
+        // `pg_get_constraintdef` appends the attributes in this order.
+        // Constraints which are not enforced are never validated.
+        let mut definition = row.def.as_str();
+        for (suffix, present) in [
+            (" NOT VALID", !row.validated),
+            (" NOT ENFORCED", !row.enforced),
+            (" INITIALLY DEFERRED", row.initially_deferred),
+            (" DEFERRABLE", row.deferrable),
+        ] {
+            if present {
+                definition = definition.strip_suffix(suffix).unwrap_or(definition);
+            }
+        }
+        Self {
+            schema: schema.to_owned(),
+            table: row.table,
+            name: row.name,
+            r#type: row.r#type,
+            definition: definition.to_owned(),
+            validated: row.validated || !row.enforced,
+            deferrable: row.deferrable,
+            initially_deferred: row.initially_deferred,
+            enforced: row.enforced,
+            on_update: row.on_update,
+            on_delete: row.on_delete,
+            index_definition: row.index_definition,
+            comment: row.comment,
+        }
+    }
+    fn qualified_table(&self) -> String {
+        format!("{}.{}", quote_ident(&self.schema), quote_ident(&self.table))
+    }
+    fn deferrable_sql(&self) -> &'static str {
+        match (self.deferrable, self.initially_deferred) {
+            (false, _) => "",
+            (true, false) => " DEFERRABLE",
+            (true, true) => " DEFERRABLE INITIALLY DEFERRED",
+        }
+    }
+    fn attributes_sql(&self) -> String {
         format!(
-            "ALTER TABLE {}.{} ADD CONSTRAINT {} {};\n",
+            "{}{}",
+            self.deferrable_sql(),
+            if self.enforced { "" } else { " NOT ENFORCED" }
+        )
+    }
+    fn attributes_eq(&self, other: &Self) -> bool {
+        self.deferrable == other.deferrable
+            && self.initially_deferred == other.initially_deferred
+            && self.enforced == other.enforced
+    }
+    /// Only the attributes of foreign keys can be changed using `ALTER
+    /// CONSTRAINT`. Other constraints need to be recreated.
+    fn alter_sql(&self, previous: &Self) -> Option<Vec<(ChangeType, String)>> {
+        if self.r#type != ConstraintType::ForeignKey {
+            return None;
+        }
+        let mut v = Vec::new();
+        if self.deferrable != previous.deferrable
+            || self.initially_deferred != previous.initially_deferred
+        {
+            v.push((
+                ChangeType::AlterConstraint,
+                format!(
+                    "ALTER TABLE {} ALTER CONSTRAINT {} {};\n",
+                    self.qualified_table(),
+                    quote_ident(&self.name),
+                    match (self.deferrable, self.initially_deferred) {
+                        (false, _) => "NOT DEFERRABLE",
+                        (true, false) => "DEFERRABLE INITIALLY IMMEDIATE",
+                        (true, true) => "DEFERRABLE INITIALLY DEFERRED",
+                    }
+                ),
+            ));
+        }
+        if self.enforced != previous.enforced {
+            v.push((
+                ChangeType::AlterConstraint,
+                format!(
+                    "ALTER TABLE {} ALTER CONSTRAINT {} {};\n",
+                    self.qualified_table(),
+                    quote_ident(&self.name),
+                    if self.enforced {
+                        "ENFORCED"
+                    } else {
+                        "NOT ENFORCED"
+                    }
+                ),
+            ));
+        }
+        Some(v)
+    }
+    /// Only foreign keys and check constraints can be added as `NOT
+    /// VALID`.
+    fn supports_not_valid(&self) -> bool {
+        matches!(
+            self.r#type,
+            ConstraintType::ForeignKey | ConstraintType::Check
+        )
+    }
+    /// Build the index of a primary key or unique constraint
+    /// concurrently and attach it using `ADD CONSTRAINT ... USING INDEX`.
+    fn create_using_index_sql(&self) -> Option<Vec<(ChangeType, String)>> {
+        let keyword = match self.r#type {
+            ConstraintType::PrimaryKey => "PRIMARY KEY",
+            ConstraintType::Unique => "UNIQUE",
+            _ => return None,
+        };
+        let index_definition = self
+            .index_definition
+            .as_ref()
+            .filter(|def| !def.contains(" ON ONLY "))?;
+        // The index is renamed to the constraint name by PostgreSQL, so
+        // the definition already uses the right name.
+        Some(vec![
+            (
+                ChangeType::CreateConstraintIndexConcurrently,
+                format!(
+                    "{};\n",
+                    index_definition.replacen(" INDEX ", " INDEX CONCURRENTLY ", 1)
+                ),
+            ),
+            (
+                ChangeType::CreateConstraint(self.r#type),
+                format!(
+                    "ALTER TABLE {}.{} ADD CONSTRAINT {} {} USING INDEX {}{};\n",
+                    quote_ident(&self.schema),
+                    quote_ident(&self.table),
+                    quote_ident(&self.name),
+                    keyword,
+                    quote_ident(&self.name),
+                    self.deferrable_sql(),
+                ),
+            ),
+        ])
+    }
+    fn create_sql(&self, options: &DiffOptions) -> Vec<(ChangeType, String)> {
+        if options.concurrent_indexes {
+            if let Some(v) = self.create_using_index_sql() {
+                return v;
+            }
+        }
+        let online = options.online_constraints
+            && self.validated
+            && self.enforced
+            && self.supports_not_valid();
+        let mut v = vec![(
+            ChangeType::CreateConstraint(self.r#type),
+            format!(
+                "ALTER TABLE {}.{} ADD CONSTRAINT {} {}{}{};\n",
+                quote_ident(&self.schema),
+                quote_ident(&self.table),
+                quote_ident(&self.name),
+                self.definition,
+                self.attributes_sql(),
+                if online || !self.validated {
+                    " NOT VALID"
+                } else {
+                    ""
+                },
+            ),
+        )];
+        if online {
+            v.push((ChangeType::ValidateConstraintOnline, self.validate_sql()));
+        }
+        v
+    }
+    fn validate_sql(&self) -> String {
+        format!(
+            "ALTER TABLE {}.{} VALIDATE CONSTRAINT {};\n",
             quote_ident(&self.schema),
             quote_ident(&self.table),
             quote_ident(&self.name),
-            self.definition,
         )
     }
     fn drop_sql(&self) -> String {
@@ -35,9 +238,52 @@ impl Constraint {
             quote_ident(&self.name),
         )
     }
+    fn comment_sql(&self) -> String {
+        comment_sql(
+            "CONSTRAINT",
+            &format!(
+                "{} ON {}.{}",
+                quote_ident(&self.name),
+                quote_ident(&self.schema),
+                quote_ident(&self.table),
+            ),
+            self.comment.as_deref(),
+        )
+    }
+}
+
+/// `ON UPDATE` and `ON DELETE` actions of foreign keys
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum ForeignKeyAction {
+    NoAction,
+    Restrict,
+    Cascade,
+    SetNull,
+    SetDefault,
+}
+
+impl<'a> FromSql<'a> for ForeignKeyAction {
+    fn from_sql(
+        ty: &postgres_types::Type,
+        raw: &'a [u8],
+    ) -> Result<Self, Box<dyn std::error::Error + Sync + Send>> {
+        let s = String::from_sql(ty, raw)?;
+        match s.as_str() {
+            "a" => Ok(Self::NoAction),
+            "r" => Ok(Self::Restrict),
+            "c" => Ok(Self::Cascade),
+            "n" => Ok(Self::SetNull),
+            "d" => Ok(Self::SetDefault),
+            _ => Err(anyhow!("Unsupported foreign key action: {s}"))?,
+        }
+    }
+
+    fn accepts(ty: &postgres_types::Type) -> bool {
+        *ty == postgres_types::Type::CHAR
+    }
 }
 
-#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd)]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Serialize)]
 pub enum ConstraintType {
     Check,
     NotNull,
@@ -75,19 +321,185 @@ impl<'a> FromSql<'a> for ConstraintType {
 
 impl DiffSql for Diff<'_, Constraint> {
     fn sql(&self) -> Vec<(ChangeType, String)> {
+        self.sql_with(&DiffOptions::default())
+    }
+    fn sql_with(&self, options: &DiffOptions) -> Vec<(ChangeType, String)> {
         let mut v = Vec::new();
         for a in &self.a_only {
             v.push((ChangeType::DropConstraint(Reverse(a.r#type)), a.drop_sql()));
         }
         for (a, b) in &self.a_and_b {
-            if a != b {
+            let alter = if a.r#type != b.r#type || a.definition != b.definition {
+                None
+            } else if a.attributes_eq(b) {
+                Some(Vec::new())
+            } else {
+                b.alter_sql(a)
+            };
+            let Some(alter) = alter else {
                 v.push((ChangeType::DropConstraint(Reverse(a.r#type)), a.drop_sql()));
-                v.push((ChangeType::CreateConstraint(b.r#type), b.create_sql()));
+                v.extend(b.create_sql(options));
+                if b.comment.is_some() {
+                    v.push((ChangeType::Comment, b.comment_sql()));
+                }
+                continue;
+            };
+            v.extend(alter);
+            // A constraint which is not validated in the target schema
+            // is left alone if it was validated already.
+            if !a.validated && b.validated {
+                v.push((ChangeType::ValidateConstraint, b.validate_sql()));
+            }
+            if a.comment != b.comment {
+                v.push((ChangeType::Comment, b.comment_sql()));
             }
         }
         for b in &self.b_only {
-            v.push((ChangeType::CreateConstraint(b.r#type), b.create_sql()));
+            v.extend(b.create_sql(options));
+            if b.comment.is_some() {
+                v.push((ChangeType::Comment, b.comment_sql()));
+            }
         }
         v
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::diff::{diff, ChangeType, DiffOptions, DiffSql};
+
+    use super::{Constraint, ConstraintType};
+
+    fn constraint(name: &str, r#type: ConstraintType, definition: &str) -> Constraint {
+        Constraint {
+            schema: "public".into(),
+            table: "order".into(),
+            name: name.into(),
+            r#type,
+            definition: definition.into(),
+            validated: true,
+            deferrable: false,
+            initially_deferred: false,
+            enforced: true,
+            on_update: None,
+            on_delete: None,
+            index_definition: None,
+            comment: None,
+        }
+    }
+
+    #[test]
+    fn validates_constraints_of_the_target_only() {
+        let validated = [constraint(
+            "order_total_check",
+            ConstraintType::Check,
+            "CHECK ((total > 0))",
+        )];
+        let unvalidated = [Constraint {
+            validated: false,
+            ..constraint(
+                "order_total_check",
+                ConstraintType::Check,
+                "CHECK ((total > 0))",
+            )
+        }];
+        assert_eq!(validated, unvalidated);
+        assert_eq!(
+            diff(unvalidated.iter(), validated.iter(), |c| &c.name).sql(),
+            vec![(
+                ChangeType::ValidateConstraint,
+                "ALTER TABLE \"public\".\"order\" VALIDATE CONSTRAINT \"order_total_check\";\n"
+                    .into()
+            )]
+        );
+        assert_eq!(
+            diff(validated.iter(), unvalidated.iter(), |c| &c.name).sql(),
+            vec![]
+        );
+    }
+
+    #[test]
+    fn online_constraints_are_validated_separately() {
+        let b = [
+            constraint("order_pkey", ConstraintType::PrimaryKey, "PRIMARY KEY (id)"),
+            constraint(
+                "order_total_check",
+                ConstraintType::Check,
+                "CHECK ((total > 0))",
+            ),
+        ];
+        let options = DiffOptions {
+            online_constraints: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            diff([].iter(), b.iter(), |c| &c.name).sql_with(&options),
+            vec![
+                (
+                    ChangeType::CreateConstraint(ConstraintType::PrimaryKey),
+                    "ALTER TABLE \"public\".\"order\" ADD CONSTRAINT \"order_pkey\" PRIMARY KEY (id);\n".into()
+                ),
+                (
+                    ChangeType::CreateConstraint(ConstraintType::Check),
+                    "ALTER TABLE \"public\".\"order\" ADD CONSTRAINT \"order_total_check\" CHECK ((total > 0)) NOT VALID;\n".into()
+                ),
+                (
+                    ChangeType::ValidateConstraintOnline,
+                    "ALTER TABLE \"public\".\"order\" VALIDATE CONSTRAINT \"order_total_check\";\n".into()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn concurrent_indexes_back_unique_constraints() {
+        let mut unique = constraint(
+            "order_number_key",
+            ConstraintType::Unique,
+            "UNIQUE (number)",
+        );
+        unique.index_definition = Some(
+            "CREATE UNIQUE INDEX order_number_key ON public.\"order\" USING btree (number)".into(),
+        );
+        let b = [unique];
+        let options = DiffOptions {
+            concurrent_indexes: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            diff([].iter(), b.iter(), |c| &c.name).sql_with(&options),
+            vec![
+                (
+                    ChangeType::CreateConstraintIndexConcurrently,
+                    "CREATE UNIQUE INDEX CONCURRENTLY order_number_key ON public.\"order\" USING btree (number);\n".into()
+                ),
+                (
+                    ChangeType::CreateConstraint(ConstraintType::Unique),
+                    "ALTER TABLE \"public\".\"order\" ADD CONSTRAINT \"order_number_key\" UNIQUE USING INDEX \"order_number_key\";\n".into()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn alters_foreign_key_enforcement() {
+        let a = [constraint(
+            "order_customer_id_fkey",
+            ConstraintType::ForeignKey,
+            "FOREIGN KEY (customer_id) REFERENCES customer(id)",
+        )];
+        let mut b = [constraint(
+            "order_customer_id_fkey",
+            ConstraintType::ForeignKey,
+            "FOREIGN KEY (customer_id) REFERENCES customer(id)",
+        )];
+        b[0].enforced = false;
+        assert_eq!(
+            diff(a.iter(), b.iter(), |c| &c.name).sql(),
+            vec![(
+                ChangeType::AlterConstraint,
+                "ALTER TABLE \"public\".\"order\" ALTER CONSTRAINT \"order_customer_id_fkey\" NOT ENFORCED;\n".into()
+            )]
+        );
+    }
+}
@@ -1,7 +1,7 @@
 use crate::{
     diff::{diff, ChangeType, Diff, DiffSql},
     queries::DomainRow,
-    sql::quote_ident,
+    sql::{comment_sql, owner_sql, quote_ident},
 };
 
 #[derive(Debug, Eq, PartialEq)]
@@ -12,6 +12,8 @@ pub struct Domain {
     pub default: Option<String>,
     pub not_null: bool,
     pub constraints: Vec<DomainConstraint>,
+    pub comment: Option<String>,
+    pub owner: Option<String>,
 }
 
 #[derive(Debug, Eq, PartialEq)]
@@ -51,6 +53,16 @@ impl Domain {
         format!("DROP DOMAIN {};\n", self.qualified_name())
     }
 
+    fn comment_sql(&self) -> String {
+        comment_sql("DOMAIN", &self.qualified_name(), self.comment.as_deref())
+    }
+
+    fn owner_sql(&self) -> Option<String> {
+        self.owner
+            .as_ref()
+            .map(|owner| owner_sql("DOMAIN", &self.qualified_name(), owner))
+    }
+
     fn alter_sql(&self, previous: &Self) -> Vec<(ChangeType, String)> {
         if self.base_type != previous.base_type {
             return vec![(
@@ -174,6 +186,8 @@ impl From<DomainRow> for Domain {
                 .zip(row.constraint_definitions)
                 .map(|(name, definition)| DomainConstraint { name, definition })
                 .collect(),
+            comment: row.comment,
+            owner: Some(row.owner),
         }
     }
 }
@@ -188,9 +202,19 @@ impl DiffSql for Diff<'_, Domain> {
             if a != b {
                 v.extend(b.alter_sql(a));
             }
+            if a.comment != b.comment {
+                v.push((ChangeType::Comment, b.comment_sql()));
+            }
+            if a.owner != b.owner {
+                v.extend(b.owner_sql().map(|sql| (ChangeType::AlterOwner, sql)));
+            }
         }
         for b in &self.b_only {
             v.push((ChangeType::CreateType, b.create_sql()));
+            v.extend(b.owner_sql().map(|sql| (ChangeType::AlterOwner, sql)));
+            if b.comment.is_some() {
+                v.push((ChangeType::Comment, b.comment_sql()));
+            }
         }
         v
     }
@@ -3,7 +3,7 @@ use itertools::Itertools;
 use crate::{
     diff::{ChangeType, Diff, DiffSql},
     queries::EnumRow,
-    sql::quote_ident,
+    sql::{comment_sql, owner_sql, quote_ident, quote_literal},
 };
 
 #[derive(Debug, Eq, PartialEq)]
@@ -11,6 +11,8 @@ pub struct Enum {
     pub schema: String,
     pub name: String,
     pub labels: Vec<String>,
+    pub comment: Option<String>,
+    pub owner: Option<String>,
 }
 
 impl Enum {
@@ -34,6 +36,20 @@ impl Enum {
         )
     }
 
+    fn qualified_name(&self) -> String {
+        format!("{}.{}", quote_ident(&self.schema), quote_ident(&self.name))
+    }
+
+    fn comment_sql(&self) -> String {
+        comment_sql("TYPE", &self.qualified_name(), self.comment.as_deref())
+    }
+
+    fn owner_sql(&self) -> Option<String> {
+        self.owner
+            .as_ref()
+            .map(|owner| owner_sql("TYPE", &self.qualified_name(), owner))
+    }
+
     fn alter_sql(&self, previous: &Self) -> Vec<(ChangeType, String)> {
         if can_safely_add_values(previous, self) {
             self.add_value_sql(previous)
@@ -121,6 +137,8 @@ impl From<EnumRow> for Enum {
             schema: row.schema,
             name: row.name,
             labels: row.labels,
+            comment: row.comment,
+            owner: Some(row.owner),
         }
     }
 }
@@ -132,21 +150,27 @@ impl DiffSql for Diff<'_, Enum> {
             v.push((ChangeType::DropType, a.drop_sql()));
         }
         for (a, b) in &self.a_and_b {
-            if a != b {
+            if a.labels != b.labels {
                 v.extend(b.alter_sql(a));
             }
+            if a.comment != b.comment {
+                v.push((ChangeType::Comment, b.comment_sql()));
+            }
+            if a.owner != b.owner {
+                v.extend(b.owner_sql().map(|sql| (ChangeType::AlterOwner, sql)));
+            }
         }
         for b in &self.b_only {
             v.push((ChangeType::CreateType, b.create_sql()));
+            v.extend(b.owner_sql().map(|sql| (ChangeType::AlterOwner, sql)));
+            if b.comment.is_some() {
+                v.push((ChangeType::Comment, b.comment_sql()));
+            }
         }
         v
     }
 }
 
-fn quote_literal(value: &str) -> String {
-    format!("'{}'", value.replace('\'', "''"))
-}
-
 fn join_labels(labels: &[String]) -> String {
     labels.iter().map(|label| quote_literal(label)).join(", ")
 }
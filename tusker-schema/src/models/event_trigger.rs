@@ -0,0 +1,217 @@
+use anyhow::anyhow;
+use itertools::Itertools;
+use postgres_types::FromSql;
+
+use crate::{
+    diff::{ChangeType, Diff, DiffSql},
+    queries::EventTriggerRow,
+    sql::{comment_sql, owner_sql, quote_ident, quote_literal},
+};
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct EventTrigger {
+    pub name: String,
+    pub event: String,
+    /// Command tags the trigger is limited to. Empty if it fires for
+    /// all commands.
+    pub tags: Vec<String>,
+    pub function_schema: String,
+    pub function_name: String,
+    pub enabled: EventTriggerEnabled,
+    pub owner: Option<String>,
+    pub comment: Option<String>,
+}
+
+/// Sessions the event trigger fires in depending on their
+/// `session_replication_role`
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum EventTriggerEnabled {
+    Origin,
+    Disabled,
+    Replica,
+    Always,
+}
+
+impl<'a> FromSql<'a> for EventTriggerEnabled {
+    fn from_sql(
+        ty: &postgres_types::Type,
+        raw: &'a [u8],
+    ) -> Result<Self, Box<dyn std::error::Error + Sync + Send>> {
+        let s = String::from_sql(ty, raw)?;
+        match s.as_str() {
+            "O" => Ok(Self::Origin),
+            "D" => Ok(Self::Disabled),
+            "R" => Ok(Self::Replica),
+            "A" => Ok(Self::Always),
+            _ => Err(anyhow!("Unsupported event trigger state: {s}"))?,
+        }
+    }
+
+    fn accepts(ty: &postgres_types::Type) -> bool {
+        *ty == postgres_types::Type::CHAR
+    }
+}
+
+impl EventTrigger {
+    /// Compare everything but the enabled state, owner and comment.
+    fn definition_eq(&self, other: &Self) -> bool {
+        self.event == other.event
+            && self.tags == other.tags
+            && self.function_schema == other.function_schema
+            && self.function_name == other.function_name
+    }
+
+    fn create_sql(&self) -> String {
+        let mut sql = format!(
+            "CREATE EVENT TRIGGER {} ON {}",
+            quote_ident(&self.name),
+            quote_ident(&self.event),
+        );
+        if !self.tags.is_empty() {
+            sql.push_str(&format!(
+                "\n    WHEN TAG IN ({})",
+                self.tags.iter().map(|tag| quote_literal(tag)).join(", ")
+            ));
+        }
+        sql.push_str(&format!(
+            "\n    EXECUTE FUNCTION {}.{}();\n",
+            quote_ident(&self.function_schema),
+            quote_ident(&self.function_name),
+        ));
+        if self.enabled != EventTriggerEnabled::Origin {
+            sql.push_str(&self.enabled_sql());
+        }
+        sql
+    }
+
+    fn drop_sql(&self) -> String {
+        format!("DROP EVENT TRIGGER {};\n", quote_ident(&self.name))
+    }
+
+    fn enabled_sql(&self) -> String {
+        let action = match self.enabled {
+            EventTriggerEnabled::Origin => "ENABLE",
+            EventTriggerEnabled::Disabled => "DISABLE",
+            EventTriggerEnabled::Replica => "ENABLE REPLICA",
+            EventTriggerEnabled::Always => "ENABLE ALWAYS",
+        };
+        format!(
+            "ALTER EVENT TRIGGER {} {};\n",
+            quote_ident(&self.name),
+            action
+        )
+    }
+
+    fn owner_sql(&self) -> Option<String> {
+        self.owner
+            .as_ref()
+            .map(|owner| owner_sql("EVENT TRIGGER", &quote_ident(&self.name), owner))
+    }
+
+    fn comment_sql(&self) -> String {
+        comment_sql(
+            "EVENT TRIGGER",
+            &quote_ident(&self.name),
+            self.comment.as_deref(),
+        )
+    }
+
+    fn create_changes(&self) -> Vec<(ChangeType, String)> {
+        let mut v = vec![(ChangeType::CreateEventTrigger, self.create_sql())];
+        v.extend(self.owner_sql().map(|sql| (ChangeType::AlterOwner, sql)));
+        if self.comment.is_some() {
+            v.push((ChangeType::Comment, self.comment_sql()));
+        }
+        v
+    }
+}
+
+impl From<EventTriggerRow> for EventTrigger {
+    fn from(row: EventTriggerRow) -> Self {
+        Self {
+            name: row.name,
+            event: row.event,
+            tags: row.tags,
+            function_schema: row.function_schema,
+            function_name: row.function_name,
+            enabled: row.enabled,
+            owner: Some(row.owner),
+            comment: row.comment,
+        }
+    }
+}
+
+impl DiffSql for Diff<'_, EventTrigger> {
+    fn sql(&self) -> Vec<(ChangeType, String)> {
+        let mut v = Vec::new();
+        for a in &self.a_only {
+            v.push((ChangeType::DropEventTrigger, a.drop_sql()));
+        }
+        for (a, b) in &self.a_and_b {
+            if !a.definition_eq(b) {
+                v.push((ChangeType::DropEventTrigger, a.drop_sql()));
+                v.extend(b.create_changes());
+                continue;
+            }
+            if a.enabled != b.enabled {
+                v.push((ChangeType::AlterEventTrigger, b.enabled_sql()));
+            }
+            if a.owner != b.owner {
+                v.extend(b.owner_sql().map(|sql| (ChangeType::AlterOwner, sql)));
+            }
+            if a.comment != b.comment {
+                v.push((ChangeType::Comment, b.comment_sql()));
+            }
+        }
+        for b in &self.b_only {
+            v.extend(b.create_changes());
+        }
+        v
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{EventTrigger, EventTriggerEnabled};
+
+    fn event_trigger(enabled: EventTriggerEnabled, tags: &[&str]) -> EventTrigger {
+        EventTrigger {
+            name: "audit_ddl".into(),
+            event: "ddl_command_end".into(),
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+            function_schema: "public".into(),
+            function_name: "audit_ddl".into(),
+            enabled,
+            owner: None,
+            comment: None,
+        }
+    }
+
+    #[test]
+    fn creates_event_triggers() {
+        assert_eq!(
+            event_trigger(EventTriggerEnabled::Origin, &[]).create_sql(),
+            "CREATE EVENT TRIGGER \"audit_ddl\" ON \"ddl_command_end\"\n    EXECUTE FUNCTION \"public\".\"audit_ddl\"();\n"
+        );
+        assert_eq!(
+            event_trigger(EventTriggerEnabled::Disabled, &["CREATE TABLE", "DROP TABLE"])
+                .create_sql(),
+            "CREATE EVENT TRIGGER \"audit_ddl\" ON \"ddl_command_end\"\n    WHEN TAG IN ('CREATE TABLE', 'DROP TABLE')\n    EXECUTE FUNCTION \"public\".\"audit_ddl\"();\nALTER EVENT TRIGGER \"audit_ddl\" DISABLE;\n"
+        );
+    }
+
+    #[test]
+    fn alters_enabled_state() {
+        for (enabled, action) in [
+            (EventTriggerEnabled::Origin, "ENABLE"),
+            (EventTriggerEnabled::Disabled, "DISABLE"),
+            (EventTriggerEnabled::Replica, "ENABLE REPLICA"),
+            (EventTriggerEnabled::Always, "ENABLE ALWAYS"),
+        ] {
+            assert_eq!(
+                event_trigger(enabled, &[]).enabled_sql(),
+                format!("ALTER EVENT TRIGGER \"audit_ddl\" {action};\n")
+            );
+        }
+    }
+}
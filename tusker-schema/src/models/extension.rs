@@ -9,6 +9,10 @@ pub struct Extension {
     pub schema: String,
     pub name: String,
     pub version: String,
+    /// Descriptions of the objects which belong to the extension. They
+    /// are created and dropped together with the extension and are not
+    /// part of the schema itself.
+    pub objects: Vec<String>,
 }
 
 impl Extension {
@@ -60,6 +64,7 @@ impl From<ExtensionRow> for Extension {
             schema: row.schema,
             name: row.name,
             version: row.version,
+            objects: row.objects,
         }
     }
 }
@@ -71,7 +76,7 @@ impl DiffSql for Diff<'_, Extension> {
             v.push((ChangeType::DropExtension, a.drop_sql()));
         }
         for (a, b) in &self.a_and_b {
-            if a != b {
+            if a.schema != b.schema || a.version != b.version {
                 v.extend(b.alter_sql(a));
             }
         }
@@ -103,6 +108,7 @@ mod tests {
                 schema: "public".into(),
                 name: "hstore".into(),
                 version: "1.8".into(),
+                objects: Vec::new(),
             },
         );
 
@@ -125,6 +131,7 @@ mod tests {
                 schema: "public".into(),
                 name: "postgis".into(),
                 version: "3.4.0".into(),
+                objects: Vec::new(),
             },
         );
         let mut to = Schema::new("extensions");
@@ -134,6 +141,7 @@ mod tests {
                 schema: "extensions".into(),
                 name: "postgis".into(),
                 version: "3.5.0".into(),
+                objects: Vec::new(),
             },
         );
 
@@ -161,6 +169,7 @@ mod tests {
                 schema: "public".into(),
                 name: "hstore".into(),
                 version: "1.8".into(),
+                objects: Vec::new(),
             },
         );
         let to = Schema::new("public");
@@ -1,7 +1,7 @@
 use crate::{
-    diff::{ChangeType, Diff, DiffSql},
+    diff::{ChangeType, Diff, DiffOptions, DiffSql},
     queries::IndexRow,
-    sql::quote_ident,
+    sql::{comment_sql, quote_ident},
 };
 
 #[derive(Debug, Clone, Eq, PartialEq)]
@@ -10,21 +10,46 @@ pub struct Index {
     pub table_name: String,
     pub name: String,
     pub definition: String,
+    pub comment: Option<String>,
 }
 
 impl Index {
-    fn create_sql(&self) -> String {
-        format!(
-            "{};\n",
-            self.definition.trim_end_matches('\n').trim_end_matches(';')
-        )
+    /// Indexes on partitioned tables (`ON ONLY`) can't be created or
+    /// dropped concurrently.
+    fn concurrently(&self, options: &DiffOptions) -> bool {
+        options.concurrent_indexes && !self.definition.contains(" ON ONLY ")
+    }
+
+    fn create_sql(&self, options: &DiffOptions) -> (ChangeType, String) {
+        let definition = self.definition.trim_end_matches('\n').trim_end_matches(';');
+        match self.concurrently(options) {
+            true => (
+                ChangeType::CreateIndexConcurrently,
+                format!(
+                    "{};\n",
+                    definition.replacen(" INDEX ", " INDEX CONCURRENTLY ", 1)
+                ),
+            ),
+            false => (ChangeType::CreateIndex, format!("{};\n", definition)),
+        }
     }
 
-    fn drop_sql(&self) -> String {
-        format!(
-            "DROP INDEX {}.{};\n",
-            quote_ident(&self.schema),
-            quote_ident(&self.name),
+    fn drop_sql(&self, options: &DiffOptions) -> (ChangeType, String) {
+        let name = format!("{}.{}", quote_ident(&self.schema), quote_ident(&self.name));
+        match self.concurrently(options) {
+            true => (
+                ChangeType::DropIndexConcurrently,
+                format!("DROP INDEX CONCURRENTLY {};\n", name),
+            ),
+            false => (ChangeType::DropIndex, format!("DROP INDEX {};\n", name)),
+        }
+    }
+
+    fn comment_sql(&self) -> String {
+        comment_sql(
+            "INDEX",
+            &format!("{}.{}", quote_ident(&self.schema), quote_ident(&self.name)),
+            self.comment.as_deref(),
         )
     }
 }
@@ -36,24 +61,36 @@ impl From<IndexRow> for Index {
             table_name: row.table_name,
             name: row.name,
             definition: row.definition,
+            comment: row.comment,
         }
     }
 }
 
 impl DiffSql for Diff<'_, Index> {
     fn sql(&self) -> Vec<(ChangeType, String)> {
+        self.sql_with(&DiffOptions::default())
+    }
+    fn sql_with(&self, options: &DiffOptions) -> Vec<(ChangeType, String)> {
         let mut v = Vec::new();
         for a in &self.a_only {
-            v.push((ChangeType::DropIndex, a.drop_sql()));
+            v.push(a.drop_sql(options));
         }
         for (a, b) in &self.a_and_b {
-            if a != b {
-                v.push((ChangeType::DropIndex, a.drop_sql()));
-                v.push((ChangeType::CreateIndex, b.create_sql()));
+            if a.definition != b.definition {
+                v.push(a.drop_sql(options));
+                v.push(b.create_sql(options));
+                if b.comment.is_some() {
+                    v.push((ChangeType::Comment, b.comment_sql()));
+                }
+            } else if a.comment != b.comment {
+                v.push((ChangeType::Comment, b.comment_sql()));
             }
         }
         for b in &self.b_only {
-            v.push((ChangeType::CreateIndex, b.create_sql()));
+            v.push(b.create_sql(options));
+            if b.comment.is_some() {
+                v.push((ChangeType::Comment, b.comment_sql()));
+            }
         }
         v
     }
@@ -61,7 +98,7 @@ impl DiffSql for Diff<'_, Index> {
 
 #[cfg(test)]
 mod tests {
-    use crate::diff::{Diff, DiffSql};
+    use crate::diff::{Diff, DiffOptions, DiffSql};
 
     use super::Index;
 
@@ -71,6 +108,7 @@ mod tests {
             table_name: "employees".into(),
             name: name.into(),
             definition: definition.into(),
+            comment: None,
         }
     }
 
@@ -127,4 +165,40 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    fn recreates_indexes_concurrently() {
+        let old = index(
+            "employees_name_idx",
+            "CREATE INDEX employees_name_idx ON public.employees USING btree (name)",
+        );
+        let new = index(
+            "employees_name_idx",
+            "CREATE INDEX employees_name_idx ON public.employees USING btree (lower(name))",
+        );
+        let diff = Diff {
+            a_only: vec![],
+            a_and_b: vec![(&old, &new)],
+            b_only: vec![],
+        };
+        let options = DiffOptions {
+            concurrent_indexes: true,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            diff.sql_with(&options),
+            vec![
+                (
+                    crate::diff::ChangeType::DropIndexConcurrently,
+                    "DROP INDEX CONCURRENTLY \"public\".\"employees_name_idx\";\n".into(),
+                ),
+                (
+                    crate::diff::ChangeType::CreateIndexConcurrently,
+                    "CREATE INDEX CONCURRENTLY employees_name_idx ON public.employees USING btree (lower(name));\n"
+                        .into(),
+                ),
+            ]
+        );
+    }
 }
@@ -1,12 +1,25 @@
+pub mod cast;
 pub mod column;
 pub mod constraint;
 pub mod domain;
 pub mod r#enum;
+pub mod event_trigger;
 pub mod extension;
 pub mod index;
+pub mod operator;
+pub mod operator_class;
+pub mod operator_family;
+pub mod publication;
 pub mod routine;
 pub mod schema;
 pub mod sequence;
+pub mod statistics;
+pub mod subscription;
 pub mod table;
+pub mod text_search_configuration;
+pub mod text_search_dictionary;
+pub mod text_search_parser;
+pub mod text_search_template;
 pub mod trigger;
+pub mod type_change;
 pub mod view;
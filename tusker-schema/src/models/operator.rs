@@ -0,0 +1,175 @@
+use crate::{
+    diff::{ChangeType, Diff, DiffSql},
+    queries::OperatorRow,
+    sql::{comment_sql, owner_sql, quote_ident},
+};
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Operator {
+    pub schema: String,
+    pub name: String,
+    /// Left argument type. `None` for prefix operators.
+    pub left_type: Option<String>,
+    pub right_type: Option<String>,
+    pub function_schema: String,
+    pub function_name: String,
+    /// Already qualified `OPERATOR(schema.name)` reference
+    pub commutator: Option<String>,
+    /// Already qualified `OPERATOR(schema.name)` reference
+    pub negator: Option<String>,
+    pub restrict: Option<String>,
+    pub join: Option<String>,
+    pub hashes: bool,
+    pub merges: bool,
+    pub comment: Option<String>,
+    pub owner: Option<String>,
+}
+
+impl Operator {
+    pub fn key(&self) -> (String, Option<String>, Option<String>) {
+        (
+            self.name.clone(),
+            self.left_type.clone(),
+            self.right_type.clone(),
+        )
+    }
+
+    /// The operator including its argument types as used by `DROP`,
+    /// `ALTER` and `COMMENT` statements.
+    fn signature(&self) -> String {
+        format!(
+            "{}.{} ({}, {})",
+            quote_ident(&self.schema),
+            self.name,
+            self.left_type.as_deref().unwrap_or("NONE"),
+            self.right_type.as_deref().unwrap_or("NONE"),
+        )
+    }
+
+    /// Compare everything but the restriction and join estimators,
+    /// owner and comment. Those can be changed via `ALTER OPERATOR`.
+    fn definition_eq(&self, other: &Self) -> bool {
+        self.function_schema == other.function_schema
+            && self.function_name == other.function_name
+            && self.commutator == other.commutator
+            && self.negator == other.negator
+            && self.hashes == other.hashes
+            && self.merges == other.merges
+    }
+
+    fn create_sql(&self) -> String {
+        let mut options = vec![format!(
+            "FUNCTION = {}.{}",
+            quote_ident(&self.function_schema),
+            quote_ident(&self.function_name)
+        )];
+        if let Some(left_type) = &self.left_type {
+            options.push(format!("LEFTARG = {}", left_type));
+        }
+        if let Some(right_type) = &self.right_type {
+            options.push(format!("RIGHTARG = {}", right_type));
+        }
+        if let Some(commutator) = &self.commutator {
+            options.push(format!("COMMUTATOR = {}", commutator));
+        }
+        if let Some(negator) = &self.negator {
+            options.push(format!("NEGATOR = {}", negator));
+        }
+        if let Some(restrict) = &self.restrict {
+            options.push(format!("RESTRICT = {}", restrict));
+        }
+        if let Some(join) = &self.join {
+            options.push(format!("JOIN = {}", join));
+        }
+        if self.hashes {
+            options.push("HASHES".into());
+        }
+        if self.merges {
+            options.push("MERGES".into());
+        }
+        format!(
+            "CREATE OPERATOR {}.{} (\n    {}\n);\n",
+            quote_ident(&self.schema),
+            self.name,
+            options.join(",\n    ")
+        )
+    }
+
+    fn drop_sql(&self) -> String {
+        format!("DROP OPERATOR {};\n", self.signature())
+    }
+
+    fn alter_sql(&self) -> String {
+        format!(
+            "ALTER OPERATOR {} SET (RESTRICT = {}, JOIN = {});\n",
+            self.signature(),
+            self.restrict.as_deref().unwrap_or("NONE"),
+            self.join.as_deref().unwrap_or("NONE"),
+        )
+    }
+
+    fn owner_sql(&self) -> Option<String> {
+        self.owner
+            .as_ref()
+            .map(|owner| owner_sql("OPERATOR", &self.signature(), owner))
+    }
+
+    fn comment_sql(&self) -> String {
+        comment_sql("OPERATOR", &self.signature(), self.comment.as_deref())
+    }
+}
+
+impl From<OperatorRow> for Operator {
+    fn from(row: OperatorRow) -> Self {
+        Self {
+            schema: row.schema,
+            name: row.name,
+            left_type: row.left_type,
+            right_type: row.right_type,
+            function_schema: row.function_schema,
+            function_name: row.function_name,
+            commutator: row.commutator,
+            negator: row.negator,
+            restrict: row.restrict,
+            join: row.join,
+            hashes: row.hashes,
+            merges: row.merges,
+            comment: row.comment,
+            owner: Some(row.owner),
+        }
+    }
+}
+
+impl DiffSql for Diff<'_, Operator> {
+    fn sql(&self) -> Vec<(ChangeType, String)> {
+        let mut v = Vec::new();
+        let mut creates = self.b_only.clone();
+        for a in &self.a_only {
+            v.push((ChangeType::DropOperator, a.drop_sql()));
+        }
+        for (a, b) in &self.a_and_b {
+            if !a.definition_eq(b) {
+                v.push((ChangeType::DropOperator, a.drop_sql()));
+                creates.push(b);
+                continue;
+            }
+            if a.restrict != b.restrict || a.join != b.join {
+                v.push((ChangeType::AlterOperator, b.alter_sql()));
+            }
+            if a.owner != b.owner {
+                v.extend(b.owner_sql().map(|sql| (ChangeType::AlterOwner, sql)));
+            }
+            if a.comment != b.comment {
+                v.push((ChangeType::Comment, b.comment_sql()));
+            }
+        }
+        for b in creates {
+            v.push((ChangeType::CreateOperator, b.create_sql()));
+            v.extend(b.owner_sql().map(|sql| (ChangeType::AlterOwner, sql)));
+            if b.comment.is_some() {
+                v.push((ChangeType::Comment, b.comment_sql()));
+            }
+        }
+        v
+    }
+}
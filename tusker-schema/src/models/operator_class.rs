@@ -0,0 +1,131 @@
+use crate::{
+    diff::{ChangeType, Diff, DiffSql},
+    queries::OperatorClassRow,
+    sql::{comment_sql, owner_sql, quote_ident},
+};
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct OperatorClass {
+    pub schema: String,
+    pub name: String,
+    pub method: String,
+    pub family_schema: String,
+    pub family_name: String,
+    pub input_type: String,
+    pub default: bool,
+    pub storage: Option<String>,
+    /// `OPERATOR` and `FUNCTION` items of the operator class
+    pub members: Vec<String>,
+    pub comment: Option<String>,
+    pub owner: Option<String>,
+}
+
+impl OperatorClass {
+    pub fn key(&self) -> (String, String) {
+        (self.name.clone(), self.method.clone())
+    }
+
+    fn qualified_name(&self) -> String {
+        format!(
+            "{}.{} USING {}",
+            quote_ident(&self.schema),
+            quote_ident(&self.name),
+            quote_ident(&self.method),
+        )
+    }
+
+    /// Compare everything but the owner and comment.
+    fn definition_eq(&self, other: &Self) -> bool {
+        self.family_schema == other.family_schema
+            && self.family_name == other.family_name
+            && self.input_type == other.input_type
+            && self.default == other.default
+            && self.storage == other.storage
+            && self.members == other.members
+    }
+
+    fn create_sql(&self) -> String {
+        let mut items = self.members.clone();
+        if let Some(storage) = &self.storage {
+            items.push(format!("STORAGE {}", storage));
+        }
+        format!(
+            "CREATE OPERATOR CLASS {}.{}{} FOR TYPE {} USING {} FAMILY {}.{} AS\n    {};\n",
+            quote_ident(&self.schema),
+            quote_ident(&self.name),
+            if self.default { " DEFAULT" } else { "" },
+            self.input_type,
+            quote_ident(&self.method),
+            quote_ident(&self.family_schema),
+            quote_ident(&self.family_name),
+            items.join(",\n    "),
+        )
+    }
+
+    fn drop_sql(&self) -> String {
+        format!("DROP OPERATOR CLASS {};\n", self.qualified_name())
+    }
+
+    fn owner_sql(&self) -> Option<String> {
+        self.owner
+            .as_ref()
+            .map(|owner| owner_sql("OPERATOR CLASS", &self.qualified_name(), owner))
+    }
+
+    fn comment_sql(&self) -> String {
+        comment_sql(
+            "OPERATOR CLASS",
+            &self.qualified_name(),
+            self.comment.as_deref(),
+        )
+    }
+}
+
+impl From<OperatorClassRow> for OperatorClass {
+    fn from(row: OperatorClassRow) -> Self {
+        Self {
+            schema: row.schema,
+            name: row.name,
+            method: row.method,
+            family_schema: row.family_schema,
+            family_name: row.family_name,
+            input_type: row.input_type,
+            default: row.default,
+            storage: row.storage,
+            members: row.members,
+            comment: row.comment,
+            owner: Some(row.owner),
+        }
+    }
+}
+
+impl DiffSql for Diff<'_, OperatorClass> {
+    fn sql(&self) -> Vec<(ChangeType, String)> {
+        let mut v = Vec::new();
+        let mut creates = self.b_only.clone();
+        for a in &self.a_only {
+            v.push((ChangeType::DropOperatorClass, a.drop_sql()));
+        }
+        for (a, b) in &self.a_and_b {
+            if !a.definition_eq(b) {
+                v.push((ChangeType::DropOperatorClass, a.drop_sql()));
+                creates.push(b);
+                continue;
+            }
+            if a.owner != b.owner {
+                v.extend(b.owner_sql().map(|sql| (ChangeType::AlterOwner, sql)));
+            }
+            if a.comment != b.comment {
+                v.push((ChangeType::Comment, b.comment_sql()));
+            }
+        }
+        for b in creates {
+            v.push((ChangeType::CreateOperatorClass, b.create_sql()));
+            v.extend(b.owner_sql().map(|sql| (ChangeType::AlterOwner, sql)));
+            if b.comment.is_some() {
+                v.push((ChangeType::Comment, b.comment_sql()));
+            }
+        }
+        v
+    }
+}
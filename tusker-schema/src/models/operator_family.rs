@@ -0,0 +1,176 @@
+use itertools::Itertools;
+use serde::Deserialize;
+
+use crate::{
+    diff::{diff, ChangeType, Diff, DiffSql},
+    queries::OperatorFamilyRow,
+    sql::{comment_sql, owner_sql, quote_ident},
+};
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct OperatorFamily {
+    pub schema: String,
+    pub name: String,
+    pub method: String,
+    /// Operators and functions added to the family via `ALTER OPERATOR
+    /// FAMILY`. Members of operator classes are part of the class.
+    pub members: Vec<OperatorFamilyMember>,
+    pub comment: Option<String>,
+    pub owner: Option<String>,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize)]
+pub struct OperatorFamilyMember {
+    /// `OPERATOR` or `FUNCTION`
+    pub kind: String,
+    /// Strategy or support function number
+    pub number: i16,
+    pub left_type: String,
+    pub right_type: String,
+    /// Member as used by `ADD`
+    pub definition: String,
+}
+
+impl OperatorFamilyMember {
+    /// Member as used by `DROP`
+    fn drop_definition(&self) -> String {
+        format!(
+            "{} {} ({}, {})",
+            self.kind, self.number, self.left_type, self.right_type
+        )
+    }
+}
+
+impl OperatorFamily {
+    pub fn key(&self) -> (String, String) {
+        (self.name.clone(), self.method.clone())
+    }
+
+    fn qualified_name(&self) -> String {
+        format!(
+            "{}.{} USING {}",
+            quote_ident(&self.schema),
+            quote_ident(&self.name),
+            quote_ident(&self.method),
+        )
+    }
+
+    fn create_sql(&self) -> String {
+        format!("CREATE OPERATOR FAMILY {};\n", self.qualified_name())
+    }
+
+    fn drop_sql(&self) -> String {
+        format!("DROP OPERATOR FAMILY {};\n", self.qualified_name())
+    }
+
+    fn add_sql<'a>(&self, members: impl Iterator<Item = &'a OperatorFamilyMember>) -> String {
+        format!(
+            "ALTER OPERATOR FAMILY {} ADD\n    {};\n",
+            self.qualified_name(),
+            members.map(|m| &m.definition).join(",\n    ")
+        )
+    }
+
+    fn drop_members_sql<'a>(
+        &self,
+        members: impl Iterator<Item = &'a OperatorFamilyMember>,
+    ) -> String {
+        format!(
+            "ALTER OPERATOR FAMILY {} DROP\n    {};\n",
+            self.qualified_name(),
+            members.map(|m| m.drop_definition()).join(",\n    ")
+        )
+    }
+
+    fn owner_sql(&self) -> Option<String> {
+        self.owner
+            .as_ref()
+            .map(|owner| owner_sql("OPERATOR FAMILY", &self.qualified_name(), owner))
+    }
+
+    fn comment_sql(&self) -> String {
+        comment_sql(
+            "OPERATOR FAMILY",
+            &self.qualified_name(),
+            self.comment.as_deref(),
+        )
+    }
+
+    fn member_changes(&self, previous: &Self) -> Vec<(ChangeType, String)> {
+        let mut v = Vec::new();
+        let members = diff(previous.members.iter(), self.members.iter(), |m| {
+            (&m.kind, m.number, &m.left_type, &m.right_type)
+        });
+        let changed = members
+            .a_and_b
+            .iter()
+            .filter(|(a, b)| a != b)
+            .collect::<Vec<_>>();
+        if !members.a_only.is_empty() || !changed.is_empty() {
+            v.push((
+                ChangeType::AlterOperatorFamily,
+                self.drop_members_sql(
+                    members
+                        .a_only
+                        .iter()
+                        .copied()
+                        .chain(changed.iter().map(|(a, _)| *a)),
+                ),
+            ));
+        }
+        if !members.b_only.is_empty() || !changed.is_empty() {
+            v.push((
+                ChangeType::AlterOperatorFamily,
+                self.add_sql(
+                    changed
+                        .iter()
+                        .map(|(_, b)| *b)
+                        .chain(members.b_only.iter().copied()),
+                ),
+            ));
+        }
+        v
+    }
+}
+
+impl From<OperatorFamilyRow> for OperatorFamily {
+    fn from(row: OperatorFamilyRow) -> Self {
+        Self {
+            schema: row.schema,
+            name: row.name,
+            method: row.method,
+            members: row.members.0,
+            comment: row.comment,
+            owner: Some(row.owner),
+        }
+    }
+}
+
+impl DiffSql for Diff<'_, OperatorFamily> {
+    fn sql(&self) -> Vec<(ChangeType, String)> {
+        let mut v = Vec::new();
+        for a in &self.a_only {
+            v.push((ChangeType::DropOperatorFamily, a.drop_sql()));
+        }
+        for (a, b) in &self.a_and_b {
+            v.extend(b.member_changes(a));
+            if a.owner != b.owner {
+                v.extend(b.owner_sql().map(|sql| (ChangeType::AlterOwner, sql)));
+            }
+            if a.comment != b.comment {
+                v.push((ChangeType::Comment, b.comment_sql()));
+            }
+        }
+        for b in &self.b_only {
+            v.push((ChangeType::CreateOperatorFamily, b.create_sql()));
+            if !b.members.is_empty() {
+                v.push((ChangeType::AlterOperatorFamily, b.add_sql(b.members.iter())));
+            }
+            v.extend(b.owner_sql().map(|sql| (ChangeType::AlterOwner, sql)));
+            if b.comment.is_some() {
+                v.push((ChangeType::Comment, b.comment_sql()));
+            }
+        }
+        v
+    }
+}
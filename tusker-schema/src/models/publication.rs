@@ -0,0 +1,281 @@
+use itertools::Itertools;
+use serde::Deserialize;
+
+use crate::{
+    diff::{diff, ChangeType, Diff, DiffSql},
+    queries::PublicationRow,
+    sql::{comment_sql, owner_sql, quote_ident, quote_literal},
+};
+
+/// Operations published when no `publish` parameter is given.
+const DEFAULT_PUBLISH: [&str; 4] = ["insert", "update", "delete", "truncate"];
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Publication {
+    pub name: String,
+    pub owner: Option<String>,
+    pub all_tables: bool,
+    pub tables: Vec<PublicationTable>,
+    pub schemas: Vec<String>,
+    pub publish: Vec<String>,
+    pub publish_via_partition_root: bool,
+    pub comment: Option<String>,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize)]
+pub struct PublicationTable {
+    pub schema: String,
+    pub name: String,
+    /// Published columns. `None` if all columns are published.
+    pub columns: Option<Vec<String>>,
+    pub row_filter: Option<String>,
+}
+
+impl PublicationTable {
+    fn qualified_name(&self) -> String {
+        format!("{}.{}", quote_ident(&self.schema), quote_ident(&self.name))
+    }
+
+    fn sql(&self) -> String {
+        let mut sql = format!("TABLE {}", self.qualified_name());
+        if let Some(columns) = &self.columns {
+            sql.push_str(&format!(
+                " ({})",
+                columns.iter().map(|c| quote_ident(c)).join(", ")
+            ));
+        }
+        if let Some(row_filter) = &self.row_filter {
+            sql.push_str(&format!(" WHERE ({})", row_filter));
+        }
+        sql
+    }
+}
+
+impl Publication {
+    fn objects_sql<'a>(
+        tables: impl Iterator<Item = String> + 'a,
+        schemas: impl Iterator<Item = &'a String> + 'a,
+    ) -> String {
+        tables
+            .chain(schemas.map(|s| format!("TABLES IN SCHEMA {}", quote_ident(s))))
+            .join(", ")
+    }
+
+    fn options_sql(&self) -> String {
+        format!(
+            "publish = {}, publish_via_partition_root = {}",
+            quote_literal(&self.publish.join(", ")),
+            self.publish_via_partition_root,
+        )
+    }
+
+    fn has_default_options(&self) -> bool {
+        self.publish == DEFAULT_PUBLISH && !self.publish_via_partition_root
+    }
+
+    fn create_sql(&self) -> String {
+        let mut sql = format!("CREATE PUBLICATION {}", quote_ident(&self.name));
+        if self.all_tables {
+            sql.push_str(" FOR ALL TABLES");
+        } else if !self.tables.is_empty() || !self.schemas.is_empty() {
+            sql.push_str(" FOR ");
+            sql.push_str(&Self::objects_sql(
+                self.tables.iter().map(PublicationTable::sql),
+                self.schemas.iter(),
+            ));
+        }
+        if !self.has_default_options() {
+            sql.push_str(&format!(" WITH ({})", self.options_sql()));
+        }
+        sql.push_str(";\n");
+        sql
+    }
+
+    fn drop_sql(&self) -> String {
+        format!("DROP PUBLICATION {};\n", quote_ident(&self.name))
+    }
+
+    fn owner_sql(&self) -> Option<String> {
+        self.owner
+            .as_ref()
+            .map(|owner| owner_sql("PUBLICATION", &quote_ident(&self.name), owner))
+    }
+
+    fn comment_sql(&self) -> String {
+        comment_sql(
+            "PUBLICATION",
+            &quote_ident(&self.name),
+            self.comment.as_deref(),
+        )
+    }
+
+    /// Tables and schemas are removed from the publication before any
+    /// tables or columns are dropped and added once all of them exist.
+    /// Tables with a changed column list or row filter are removed and
+    /// added again.
+    fn alter_sql(&self, previous: &Self) -> Vec<(ChangeType, String)> {
+        let mut v = Vec::new();
+        let name = quote_ident(&self.name);
+        let tables = diff(previous.tables.iter(), self.tables.iter(), |t| {
+            (&t.schema, &t.name)
+        });
+        let schemas = diff(previous.schemas.iter(), self.schemas.iter(), |s| s);
+        let changed_tables = tables
+            .a_and_b
+            .iter()
+            .filter(|(a, b)| a != b)
+            .map(|(_, b)| *b)
+            .collect::<Vec<_>>();
+        let dropped_tables = tables.a_only.iter().chain(changed_tables.iter());
+        if !tables.a_only.is_empty() || !changed_tables.is_empty() || !schemas.a_only.is_empty() {
+            v.push((
+                ChangeType::DropPublication,
+                format!(
+                    "ALTER PUBLICATION {} DROP {};\n",
+                    name,
+                    Self::objects_sql(
+                        dropped_tables.map(|t| format!("TABLE {}", t.qualified_name())),
+                        schemas.a_only.into_iter(),
+                    ),
+                ),
+            ));
+        }
+        if !tables.b_only.is_empty() || !changed_tables.is_empty() || !schemas.b_only.is_empty() {
+            v.push((
+                ChangeType::AlterPublication,
+                format!(
+                    "ALTER PUBLICATION {} ADD {};\n",
+                    name,
+                    Self::objects_sql(
+                        changed_tables
+                            .iter()
+                            .chain(tables.b_only.iter())
+                            .map(|t| t.sql()),
+                        schemas.b_only.into_iter(),
+                    ),
+                ),
+            ));
+        }
+        if self.publish != previous.publish
+            || self.publish_via_partition_root != previous.publish_via_partition_root
+        {
+            v.push((
+                ChangeType::AlterPublication,
+                format!("ALTER PUBLICATION {} SET ({});\n", name, self.options_sql()),
+            ));
+        }
+        v
+    }
+}
+
+impl From<PublicationRow> for Publication {
+    fn from(row: PublicationRow) -> Self {
+        Self {
+            name: row.name,
+            owner: Some(row.owner),
+            all_tables: row.all_tables,
+            tables: row.tables.0,
+            schemas: row.schemas,
+            publish: row.publish,
+            publish_via_partition_root: row.publish_via_partition_root,
+            comment: row.comment,
+        }
+    }
+}
+
+impl DiffSql for Diff<'_, Publication> {
+    fn sql(&self) -> Vec<(ChangeType, String)> {
+        let mut v = Vec::new();
+        for a in &self.a_only {
+            v.push((ChangeType::DropPublication, a.drop_sql()));
+        }
+        for (a, b) in &self.a_and_b {
+            if a.all_tables != b.all_tables {
+                v.push((ChangeType::DropPublication, a.drop_sql()));
+                v.push((ChangeType::CreatePublication, b.create_sql()));
+                v.extend(b.owner_sql().map(|sql| (ChangeType::AlterOwner, sql)));
+                if b.comment.is_some() {
+                    v.push((ChangeType::Comment, b.comment_sql()));
+                }
+                continue;
+            }
+            v.extend(b.alter_sql(a));
+            if a.owner != b.owner {
+                v.extend(b.owner_sql().map(|sql| (ChangeType::AlterOwner, sql)));
+            }
+            if a.comment != b.comment {
+                v.push((ChangeType::Comment, b.comment_sql()));
+            }
+        }
+        for b in &self.b_only {
+            v.push((ChangeType::CreatePublication, b.create_sql()));
+            v.extend(b.owner_sql().map(|sql| (ChangeType::AlterOwner, sql)));
+            if b.comment.is_some() {
+                v.push((ChangeType::Comment, b.comment_sql()));
+            }
+        }
+        v
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::diff::{diff, ChangeType, DiffSql};
+
+    use super::{Publication, PublicationTable};
+
+    fn publication(tables: Vec<PublicationTable>) -> Publication {
+        Publication {
+            name: "analytics".into(),
+            owner: None,
+            all_tables: false,
+            tables,
+            schemas: vec![],
+            publish: vec!["insert".into(), "update".into()],
+            publish_via_partition_root: false,
+            comment: None,
+        }
+    }
+
+    fn table(name: &str, row_filter: Option<&str>) -> PublicationTable {
+        PublicationTable {
+            schema: "public".into(),
+            name: name.into(),
+            columns: Some(vec!["id".into(), "total".into()]),
+            row_filter: row_filter.map(Into::into),
+        }
+    }
+
+    #[test]
+    fn creates_publication_with_column_list_and_row_filter() {
+        assert_eq!(
+            publication(vec![table("orders", Some("total > 0"))]).create_sql(),
+            "CREATE PUBLICATION \"analytics\" FOR TABLE \"public\".\"orders\" (\"id\", \"total\") WHERE (total > 0) WITH (publish = 'insert, update', publish_via_partition_root = false);\n"
+        );
+    }
+
+    #[test]
+    fn readds_tables_with_changed_row_filter() {
+        let a = [publication(vec![
+            table("orders", None),
+            table("refunds", None),
+        ])];
+        let b = [publication(vec![
+            table("orders", Some("total > 0")),
+            table("invoices", None),
+        ])];
+        assert_eq!(
+            diff(a.iter(), b.iter(), |p| &p.name).sql(),
+            vec![
+                (
+                    ChangeType::DropPublication,
+                    "ALTER PUBLICATION \"analytics\" DROP TABLE \"public\".\"refunds\", TABLE \"public\".\"orders\";\n".into()
+                ),
+                (
+                    ChangeType::AlterPublication,
+                    "ALTER PUBLICATION \"analytics\" ADD TABLE \"public\".\"orders\" (\"id\", \"total\") WHERE (total > 0), TABLE \"public\".\"invoices\" (\"id\", \"total\");\n".into()
+                ),
+            ]
+        );
+    }
+}
@@ -3,7 +3,7 @@ use std::collections::{BTreeSet, HashMap};
 use crate::{
     diff::{ChangeType, Diff, DiffSql},
     queries::{RoutineDependencyRow, RoutineKind, RoutineRow},
-    sql::quote_ident,
+    sql::{comment_sql, owner_sql, quote_ident},
 };
 
 #[derive(Debug, Clone, Eq, Hash, Ord, PartialEq, PartialOrd)]
@@ -31,6 +31,8 @@ pub struct Routine {
     pub identity_arguments: String,
     pub definition: String,
     dependencies: Vec<RoutineKey>,
+    pub comment: Option<String>,
+    pub owner: Option<String>,
 }
 
 impl Routine {
@@ -48,27 +50,55 @@ impl Routine {
         )
     }
 
-    fn drop_sql(&self) -> String {
-        let thing = match self.kind {
+    fn object_type(&self) -> &'static str {
+        match self.kind {
             RoutineKind::Function => "FUNCTION",
             RoutineKind::Procedure => "PROCEDURE",
             RoutineKind::Aggregate => "AGGREGATE",
             RoutineKind::Window => "FUNCTION",
-        };
+        }
+    }
+
+    fn signature(&self) -> String {
         format!(
-            "DROP {} {}.{}({});\n",
-            thing,
+            "{}.{}({})",
             quote_ident(&self.schema),
             quote_ident(&self.name),
             self.identity_arguments,
         )
     }
 
-    fn create_order<'a>(routines: Vec<&'a Routine>) -> Vec<&'a Routine> {
+    fn drop_sql(&self) -> String {
+        format!("DROP {} {};\n", self.object_type(), self.signature())
+    }
+
+    fn comment_sql(&self) -> String {
+        comment_sql(
+            self.object_type(),
+            &self.signature(),
+            self.comment.as_deref(),
+        )
+    }
+
+    fn owner_sql(&self) -> Option<String> {
+        self.owner
+            .as_ref()
+            .map(|owner| owner_sql(self.object_type(), &self.signature(), owner))
+    }
+
+    /// Compare everything but the comment and owner. Routines whose definition
+    /// differs need to be dropped and recreated.
+    pub(crate) fn definition_eq(&self, other: &Self) -> bool {
+        self.kind == other.kind
+            && self.definition == other.definition
+            && self.dependencies == other.dependencies
+    }
+
+    pub(crate) fn create_order(routines: Vec<&Routine>) -> Vec<&Routine> {
         topological_order(routines, false)
     }
 
-    fn drop_order<'a>(routines: Vec<&'a Routine>) -> Vec<&'a Routine> {
+    pub(crate) fn drop_order(routines: Vec<&Routine>) -> Vec<&Routine> {
         topological_order(routines, true)
     }
 }
@@ -91,6 +121,8 @@ impl From<RoutineRow> for Routine {
             identity_arguments: row.identity_arguments,
             definition: row.definition,
             dependencies,
+            comment: row.comment,
+            owner: Some(row.owner),
         }
     }
 }
@@ -103,9 +135,16 @@ impl DiffSql for Diff<'_, Routine> {
         let mut creates = self.b_only.clone();
 
         for (a, b) in &self.a_and_b {
-            if a != b {
+            if !a.definition_eq(b) {
                 drops.push(a);
                 creates.push(b);
+            } else {
+                if a.comment != b.comment {
+                    v.push((ChangeType::Comment, b.comment_sql()));
+                }
+                if a.owner != b.owner {
+                    v.extend(b.owner_sql().map(|sql| (ChangeType::AlterOwner, sql)));
+                }
             }
         }
 
@@ -115,6 +154,10 @@ impl DiffSql for Diff<'_, Routine> {
 
         for b in Routine::create_order(creates) {
             v.push((ChangeType::CreateRoutine, b.create_sql()));
+            v.extend(b.owner_sql().map(|sql| (ChangeType::AlterOwner, sql)));
+            if b.comment.is_some() {
+                v.push((ChangeType::Comment, b.comment_sql()));
+            }
         }
 
         v
@@ -127,7 +170,7 @@ impl From<RoutineDependencyRow> for RoutineKey {
     }
 }
 
-fn topological_order<'a>(mut routines: Vec<&'a Routine>, reverse: bool) -> Vec<&'a Routine> {
+fn topological_order(mut routines: Vec<&Routine>, reverse: bool) -> Vec<&Routine> {
     routines.sort_by_key(|routine| routine.key());
 
     let routines_by_key = routines
@@ -222,6 +265,8 @@ mod tests {
                     RoutineKey::new(schema, dep_name, identity_arguments)
                 })
                 .collect(),
+            comment: None,
+            owner: None,
         }
     }
 
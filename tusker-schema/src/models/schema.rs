@@ -2,24 +2,42 @@ use std::collections::HashMap;
 
 use itertools::Itertools;
 
-use crate::diff::{diff, ChangeType, Diff, DiffSql};
+use crate::{
+    diff::{diff, object_changes, Change, ChangeType, Diff, DiffOptions, DiffSql},
+    filter::ObjectKind,
+    sql::{comment_sql, owner_sql, quote_ident},
+};
 
 use super::{
-    constraint::Constraint, domain::Domain, extension::Extension, index::Index, r#enum::Enum,
-    routine::Routine, sequence::Sequence, table::Table, trigger::Trigger, view::View,
+    constraint::Constraint, domain::Domain, extension::Extension, index::Index, operator::Operator,
+    operator_class::OperatorClass, operator_family::OperatorFamily, r#enum::Enum, routine::Routine,
+    sequence::Sequence, statistics::Statistics, table::Table,
+    text_search_configuration::TextSearchConfiguration,
+    text_search_dictionary::TextSearchDictionary, text_search_parser::TextSearchParser,
+    text_search_template::TextSearchTemplate, trigger::Trigger, view::View,
 };
 
 #[derive(Debug, Default, Eq, PartialEq)]
 pub struct Schema {
     pub name: String,
+    pub comment: Option<String>,
+    pub owner: Option<String>,
     pub enums: HashMap<String, Enum>,
     pub domains: HashMap<String, Domain>,
     pub sequences: HashMap<String, Sequence>,
     pub extensions: HashMap<String, Extension>,
     pub indexes: HashMap<String, Index>,
+    pub statistics: HashMap<String, Statistics>,
     pub tables: HashMap<String, Table>,
     pub views: HashMap<String, View>,
     pub routines: HashMap<(String, String), Routine>,
+    pub operators: HashMap<(String, Option<String>, Option<String>), Operator>,
+    pub operator_families: HashMap<(String, String), OperatorFamily>,
+    pub operator_classes: HashMap<(String, String), OperatorClass>,
+    pub text_search_parsers: HashMap<String, TextSearchParser>,
+    pub text_search_templates: HashMap<String, TextSearchTemplate>,
+    pub text_search_dictionaries: HashMap<String, TextSearchDictionary>,
+    pub text_search_configurations: HashMap<String, TextSearchConfiguration>,
     pub triggers: HashMap<(String, String), Trigger>,
     pub constraints: HashMap<(String, String), Constraint>,
 }
@@ -31,11 +49,30 @@ impl Schema {
             ..Default::default()
         }
     }
+    pub fn comment_sql(&self) -> String {
+        comment_sql("SCHEMA", &quote_ident(&self.name), self.comment.as_deref())
+    }
+    pub fn owner_sql(&self) -> Option<String> {
+        self.owner
+            .as_ref()
+            .map(|owner| owner_sql("SCHEMA", &quote_ident(&self.name), owner))
+    }
+    /// Tables are dropped before and created after their parents
     pub fn diff_tables<'a>(&'a self, other: &'a Self) -> Diff<'a, Table> {
-        diff(
+        let mut tables = diff(
             self.tables.values().sorted_by(|a, b| a.name.cmp(&b.name)),
             other.tables.values().sorted_by(|a, b| a.name.cmp(&b.name)),
             |table| &table.name,
+        );
+        tables.a_only = Table::inheritance_order(tables.a_only, true);
+        tables.b_only = Table::inheritance_order(tables.b_only, false);
+        tables
+    }
+    pub fn diff_views<'a>(&'a self, other: &'a Self) -> Diff<'a, View> {
+        diff(
+            self.views.values().sorted_by(|a, b| a.name.cmp(&b.name)),
+            other.views.values().sorted_by(|a, b| a.name.cmp(&b.name)),
+            |view| &view.name,
         )
     }
     pub fn diff_enums<'a>(&'a self, other: &'a Self) -> Diff<'a, Enum> {
@@ -76,6 +113,119 @@ impl Schema {
             |e| &e.name,
         )
     }
+    pub fn diff_statistics<'a>(&'a self, other: &'a Self) -> Diff<'a, Statistics> {
+        diff(
+            self.statistics
+                .values()
+                .sorted_by(|a, b| a.name.cmp(&b.name)),
+            other
+                .statistics
+                .values()
+                .sorted_by(|a, b| a.name.cmp(&b.name)),
+            |s| &s.name,
+        )
+    }
+    /// Operators implemented by a routine which is recreated are
+    /// recreated, too.
+    pub fn diff_operators<'a>(&'a self, other: &'a Self) -> Diff<'a, Operator> {
+        let mut operators = diff(
+            self.operators.values().sorted_by_key(|o| o.key()),
+            other.operators.values().sorted_by_key(|o| o.key()),
+            |o| o.key(),
+        );
+        let routines = self.diff_routines(other);
+        let recreated_routines = routines
+            .b_only
+            .iter()
+            .filter(|b| {
+                routines
+                    .a_only
+                    .iter()
+                    .any(|a| a.name == b.name && a.identity_arguments == b.identity_arguments)
+            })
+            .map(|b| (&b.schema, &b.name))
+            .collect::<Vec<_>>();
+        let (recreated, unchanged) = operators.a_and_b.into_iter().partition(|(_, b)| {
+            recreated_routines.contains(&(&b.function_schema, &b.function_name))
+        });
+        operators.a_and_b = unchanged;
+        for (a, b) in recreated {
+            operators.a_only.push(a);
+            operators.b_only.push(b);
+        }
+        operators
+    }
+    pub fn diff_operator_families<'a>(&'a self, other: &'a Self) -> Diff<'a, OperatorFamily> {
+        diff(
+            self.operator_families.values().sorted_by_key(|f| f.key()),
+            other.operator_families.values().sorted_by_key(|f| f.key()),
+            |f| f.key(),
+        )
+    }
+    pub fn diff_operator_classes<'a>(&'a self, other: &'a Self) -> Diff<'a, OperatorClass> {
+        diff(
+            self.operator_classes.values().sorted_by_key(|c| c.key()),
+            other.operator_classes.values().sorted_by_key(|c| c.key()),
+            |c| c.key(),
+        )
+    }
+    pub fn diff_text_search_parsers<'a>(&'a self, other: &'a Self) -> Diff<'a, TextSearchParser> {
+        diff(
+            self.text_search_parsers
+                .values()
+                .sorted_by(|a, b| a.name.cmp(&b.name)),
+            other
+                .text_search_parsers
+                .values()
+                .sorted_by(|a, b| a.name.cmp(&b.name)),
+            |p| &p.name,
+        )
+    }
+    pub fn diff_text_search_templates<'a>(
+        &'a self,
+        other: &'a Self,
+    ) -> Diff<'a, TextSearchTemplate> {
+        diff(
+            self.text_search_templates
+                .values()
+                .sorted_by(|a, b| a.name.cmp(&b.name)),
+            other
+                .text_search_templates
+                .values()
+                .sorted_by(|a, b| a.name.cmp(&b.name)),
+            |t| &t.name,
+        )
+    }
+    pub fn diff_text_search_dictionaries<'a>(
+        &'a self,
+        other: &'a Self,
+    ) -> Diff<'a, TextSearchDictionary> {
+        diff(
+            self.text_search_dictionaries
+                .values()
+                .sorted_by(|a, b| a.name.cmp(&b.name)),
+            other
+                .text_search_dictionaries
+                .values()
+                .sorted_by(|a, b| a.name.cmp(&b.name)),
+            |d| &d.name,
+        )
+    }
+    pub fn diff_text_search_configurations<'a>(
+        &'a self,
+        other: &'a Self,
+    ) -> Diff<'a, TextSearchConfiguration> {
+        diff(
+            self.text_search_configurations
+                .values()
+                .sorted_by(|a, b| a.name.cmp(&b.name)),
+            other
+                .text_search_configurations
+                .values()
+                .sorted_by(|a, b| a.name.cmp(&b.name)),
+            |c| &c.name,
+        )
+    }
     pub fn diff_indexes<'a>(&'a self, other: &'a Self) -> Diff<'a, Index> {
         diff(
             self.indexes.values().sorted_by(|a, b| a.name.cmp(&b.name)),
@@ -84,7 +234,7 @@ impl Schema {
         )
     }
     pub fn diff_constraints<'a>(&'a self, other: &'a Self) -> Diff<'a, Constraint> {
-        diff(
+        let mut constraints = diff(
             self.constraints
                 .values()
                 .sorted_by(|a, b| (&a.table, &a.name).cmp(&(&b.table, &b.name))),
@@ -93,10 +243,43 @@ impl Schema {
                 .values()
                 .sorted_by(|a, b| (&a.table, &a.name).cmp(&(&b.table, &b.name))),
             |c| (&c.table, &c.name),
-        )
+        );
+        // Constraints are merged with inherited constraints by `INHERIT`
+        // and kept as regular constraints by `NO INHERIT`.
+        constraints
+            .a_only
+            .retain(|c| !other.is_inherited_constraint(c));
+        constraints
+            .b_only
+            .retain(|c| !self.is_inherited_constraint(c));
+        constraints
+    }
+    fn is_inherited_constraint(&self, constraint: &Constraint) -> bool {
+        self.tables
+            .get(&constraint.table)
+            .is_some_and(|t| t.inherited_constraints.contains(&constraint.name))
+    }
+    /// Constraints which were inherited from a parent table and are no
+    /// longer needed once `NO INHERIT` turned them into regular
+    /// constraints.
+    fn detached_constraints<'a>(&'a self, other: &'a Self) -> Vec<(&'a Table, &'a String)> {
+        let mut v = Vec::new();
+        for (a, b) in self.diff_tables(other).a_and_b {
+            if !Table::detaches(a, b) {
+                continue;
+            }
+            for name in &a.inherited_constraints {
+                let key = (b.name.clone(), name.clone());
+                if !b.inherited_constraints.contains(name) && !other.constraints.contains_key(&key)
+                {
+                    v.push((b, name));
+                }
+            }
+        }
+        v
     }
     pub fn diff_routines<'a>(&'a self, other: &'a Self) -> Diff<'a, Routine> {
-        diff(
+        let mut routines = diff(
             self.routines.values().sorted_by(|a, b| {
                 (&a.name, &a.identity_arguments).cmp(&(&b.name, &b.identity_arguments))
             }),
@@ -104,7 +287,19 @@ impl Schema {
                 (&a.name, &a.identity_arguments).cmp(&(&b.name, &b.identity_arguments))
             }),
             |f| (&f.name, &f.identity_arguments),
-        )
+        );
+        let (recreated, unchanged) = routines
+            .a_and_b
+            .into_iter()
+            .partition(|(a, b)| !a.definition_eq(b));
+        routines.a_and_b = unchanged;
+        for (a, b) in recreated {
+            routines.a_only.push(a);
+            routines.b_only.push(b);
+        }
+        routines.a_only = Routine::drop_order(routines.a_only);
+        routines.b_only = Routine::create_order(routines.b_only);
+        routines
     }
     pub fn diff_triggers<'a>(&'a self, other: &'a Self) -> Diff<'a, Trigger> {
         diff(
@@ -122,23 +317,203 @@ impl Schema {
 
 impl DiffSql for Diff<'_, Schema> {
     fn sql(&self) -> Vec<(ChangeType, String)> {
+        self.sql_with(&DiffOptions::default())
+    }
+    fn sql_with(&self, options: &DiffOptions) -> Vec<(ChangeType, String)> {
+        self.changes(options)
+            .into_iter()
+            .map(|change| (change.change_type, change.sql))
+            .collect()
+    }
+}
+
+impl Diff<'_, Schema> {
+    /// Statements of the diff attributed to the objects they belong to.
+    /// Objects are visited in the order their statements need to run
+    /// in, so sorting the changes by their type keeps dependencies
+    /// intact.
+    pub fn changes(&self, options: &DiffOptions) -> Vec<Change> {
         let mut v = Vec::new();
         if !self.a_only.is_empty() {
             todo!("Schema creation not supported, yet.")
         }
         for (a, b) in &self.a_and_b {
-            v.extend(a.diff_triggers(b).sql());
-            v.extend(a.diff_enums(b).sql());
-            v.extend(a.diff_domains(b).sql());
-            v.extend(a.diff_sequences(b).sql());
-            v.extend(a.diff_extensions(b).sql());
-            v.extend(a.diff_routines(b).sql());
-            v.extend(a.diff_tables(b).sql());
-            v.extend(a.diff_indexes(b).sql());
-            v.extend(a.diff_constraints(b).sql());
+            let schema = Some(b.name.as_str());
+            let change = |change_type: ChangeType, sql| Change {
+                kind: ObjectKind::Schema,
+                schema: None,
+                name: b.name.clone(),
+                change_type,
+                destructive: false,
+                sql,
+            };
+            if a.comment != b.comment {
+                v.push(change(ChangeType::Comment, b.comment_sql()));
+            }
+            if a.owner != b.owner {
+                v.extend(b.owner_sql().map(|sql| change(ChangeType::AlterOwner, sql)));
+            }
+            let table_name = |table: &str, name: &str| format!("{}.{}", table, name);
+            v.extend(object_changes(
+                &a.diff_triggers(b),
+                options,
+                ObjectKind::Trigger,
+                schema,
+                |t| table_name(&t.table_name, &t.name),
+            ));
+            v.extend(object_changes(
+                &a.diff_enums(b),
+                options,
+                ObjectKind::Type,
+                schema,
+                |e| e.name.clone(),
+            ));
+            v.extend(object_changes(
+                &a.diff_domains(b),
+                options,
+                ObjectKind::Type,
+                schema,
+                |d| d.name.clone(),
+            ));
+            v.extend(object_changes(
+                &a.diff_sequences(b),
+                options,
+                ObjectKind::Sequence,
+                schema,
+                |s| s.name.clone(),
+            ));
+            v.extend(object_changes(
+                &a.diff_extensions(b),
+                options,
+                ObjectKind::Extension,
+                schema,
+                |e| e.name.clone(),
+            ));
+            v.extend(object_changes(
+                &a.diff_routines(b),
+                options,
+                ObjectKind::Routine,
+                schema,
+                |r| format!("{}({})", r.name, r.identity_arguments),
+            ));
+            v.extend(object_changes(
+                &a.diff_operators(b),
+                options,
+                ObjectKind::Operator,
+                schema,
+                |o| {
+                    format!(
+                        "{}({}, {})",
+                        o.name,
+                        o.left_type.as_deref().unwrap_or("NONE"),
+                        o.right_type.as_deref().unwrap_or("NONE")
+                    )
+                },
+            ));
+            v.extend(object_changes(
+                &a.diff_operator_families(b),
+                options,
+                ObjectKind::OperatorFamily,
+                schema,
+                |f| format!("{} USING {}", f.name, f.method),
+            ));
+            v.extend(object_changes(
+                &a.diff_operator_classes(b),
+                options,
+                ObjectKind::OperatorClass,
+                schema,
+                |c| format!("{} USING {}", c.name, c.method),
+            ));
+            v.extend(object_changes(
+                &a.diff_text_search_parsers(b),
+                options,
+                ObjectKind::TextSearch,
+                schema,
+                |p| p.name.clone(),
+            ));
+            v.extend(object_changes(
+                &a.diff_text_search_templates(b),
+                options,
+                ObjectKind::TextSearch,
+                schema,
+                |t| t.name.clone(),
+            ));
+            v.extend(object_changes(
+                &a.diff_text_search_dictionaries(b),
+                options,
+                ObjectKind::TextSearch,
+                schema,
+                |d| d.name.clone(),
+            ));
+            v.extend(object_changes(
+                &a.diff_text_search_configurations(b),
+                options,
+                ObjectKind::TextSearch,
+                schema,
+                |c| c.name.clone(),
+            ));
+            let tables = a.diff_tables(b);
+            let mut table_changes =
+                object_changes(&tables, options, ObjectKind::Table, schema, |t| {
+                    t.name.clone()
+                });
+            // Dropped columns are part of the `ALTER TABLE` statement
+            for (old, new) in &tables.a_and_b {
+                if Table::drops_columns(old, new, options) {
+                    table_changes
+                        .iter_mut()
+                        .filter(|c| c.name == new.name && c.change_type == ChangeType::AlterColumn)
+                        .for_each(|c| c.destructive = true);
+                }
+            }
+            v.extend(table_changes);
+            v.extend(
+                a.detached_constraints(b)
+                    .into_iter()
+                    .map(|(table, name)| Change {
+                        kind: ObjectKind::Constraint,
+                        schema: schema.map(Into::into),
+                        name: table_name(&table.name, name),
+                        change_type: ChangeType::DropInherit,
+                        destructive: ChangeType::DropInherit.is_destructive(),
+                        sql: format!(
+                            "ALTER TABLE {}.{} DROP CONSTRAINT {};\n",
+                            quote_ident(&table.schema),
+                            quote_ident(&table.name),
+                            quote_ident(name),
+                        ),
+                    }),
+            );
+            v.extend(object_changes(
+                &a.diff_views(b),
+                options,
+                ObjectKind::View,
+                schema,
+                |v| v.name.clone(),
+            ));
+            v.extend(object_changes(
+                &a.diff_indexes(b),
+                options,
+                ObjectKind::Index,
+                schema,
+                |i| i.name.clone(),
+            ));
+            v.extend(object_changes(
+                &a.diff_statistics(b),
+                options,
+                ObjectKind::Statistics,
+                schema,
+                |s| s.name.clone(),
+            ));
+            v.extend(object_changes(
+                &a.diff_constraints(b),
+                options,
+                ObjectKind::Constraint,
+                schema,
+                |c| table_name(&c.table, &c.name),
+            ));
         }
         if !self.b_only.is_empty() {
-            println!("{:?}", self.b_only);
             todo!("Schema creation not supported, yet.")
         }
         v
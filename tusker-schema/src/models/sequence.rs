@@ -1,7 +1,7 @@
 use crate::{
     diff::{ChangeType, Diff, DiffSql},
     queries::SequenceRow,
-    sql::quote_ident,
+    sql::{owner_sql, quote_ident},
 };
 
 #[derive(Debug, Eq, PartialEq)]
@@ -15,6 +15,7 @@ pub struct Sequence {
     pub increment_by: i64,
     pub cycle: bool,
     pub cache_size: i64,
+    pub owner: Option<String>,
 }
 
 impl Sequence {
@@ -48,6 +49,23 @@ impl Sequence {
         format!("DROP SEQUENCE {};\n", self.qualified_name())
     }
 
+    fn owner_sql(&self) -> Option<String> {
+        self.owner
+            .as_ref()
+            .map(|owner| owner_sql("SEQUENCE", &self.qualified_name(), owner))
+    }
+
+    /// Compare everything but the owner.
+    fn definition_eq(&self, other: &Self) -> bool {
+        self.data_type == other.data_type
+            && self.start_value == other.start_value
+            && self.min_value == other.min_value
+            && self.max_value == other.max_value
+            && self.increment_by == other.increment_by
+            && self.cycle == other.cycle
+            && self.cache_size == other.cache_size
+    }
+
     fn alter_sql(&self, previous: &Self) -> String {
         let mut statements = Vec::new();
 
@@ -86,6 +104,7 @@ impl From<SequenceRow> for Sequence {
             increment_by: row.increment_by,
             cycle: row.cycle,
             cache_size: row.cache_size,
+            owner: Some(row.owner),
         }
     }
 }
@@ -97,12 +116,16 @@ impl DiffSql for Diff<'_, Sequence> {
             v.push((ChangeType::DropSequence, a.drop_sql()));
         }
         for (a, b) in &self.a_and_b {
-            if a != b {
+            if !a.definition_eq(b) {
                 v.push((ChangeType::AlterSequence, b.alter_sql(a)));
             }
+            if a.owner != b.owner {
+                v.extend(b.owner_sql().map(|sql| (ChangeType::AlterOwner, sql)));
+            }
         }
         for b in &self.b_only {
             v.push((ChangeType::CreateSequence, b.create_sql()));
+            v.extend(b.owner_sql().map(|sql| (ChangeType::AlterOwner, sql)));
         }
         v
     }
@@ -0,0 +1,109 @@
+use crate::{
+    diff::{ChangeType, Diff, DiffSql},
+    queries::StatisticsRow,
+    sql::{comment_sql, owner_sql, quote_ident},
+};
+
+/// Extended statistics object created via `CREATE STATISTICS`.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Statistics {
+    pub schema: String,
+    pub table_name: String,
+    pub name: String,
+    pub definition: String,
+    /// Statistics target. `None` if the system default is used.
+    pub target: Option<i16>,
+    pub comment: Option<String>,
+    pub owner: Option<String>,
+}
+
+impl Statistics {
+    fn qualified_name(&self) -> String {
+        format!("{}.{}", quote_ident(&self.schema), quote_ident(&self.name))
+    }
+
+    fn create_sql(&self) -> String {
+        format!("{};\n", self.definition)
+    }
+
+    fn drop_sql(&self) -> String {
+        format!("DROP STATISTICS {};\n", self.qualified_name())
+    }
+
+    fn target_sql(&self) -> String {
+        format!(
+            "ALTER STATISTICS {} SET STATISTICS {};\n",
+            self.qualified_name(),
+            self.target.unwrap_or(-1),
+        )
+    }
+
+    fn owner_sql(&self) -> Option<String> {
+        self.owner
+            .as_ref()
+            .map(|owner| owner_sql("STATISTICS", &self.qualified_name(), owner))
+    }
+
+    fn comment_sql(&self) -> String {
+        comment_sql(
+            "STATISTICS",
+            &self.qualified_name(),
+            self.comment.as_deref(),
+        )
+    }
+
+    fn create_changes(&self) -> Vec<(ChangeType, String)> {
+        let mut v = vec![(ChangeType::CreateStatistics, self.create_sql())];
+        if self.target.is_some() {
+            v.push((ChangeType::AlterStatistics, self.target_sql()));
+        }
+        v.extend(self.owner_sql().map(|sql| (ChangeType::AlterOwner, sql)));
+        if self.comment.is_some() {
+            v.push((ChangeType::Comment, self.comment_sql()));
+        }
+        v
+    }
+}
+
+impl From<StatisticsRow> for Statistics {
+    fn from(row: StatisticsRow) -> Self {
+        Self {
+            schema: row.schema,
+            table_name: row.table_name,
+            name: row.name,
+            definition: row.definition,
+            target: row.target,
+            comment: row.comment,
+            owner: Some(row.owner),
+        }
+    }
+}
+
+impl DiffSql for Diff<'_, Statistics> {
+    fn sql(&self) -> Vec<(ChangeType, String)> {
+        let mut v = Vec::new();
+        for a in &self.a_only {
+            v.push((ChangeType::DropStatistics, a.drop_sql()));
+        }
+        for (a, b) in &self.a_and_b {
+            if a.definition != b.definition {
+                v.push((ChangeType::DropStatistics, a.drop_sql()));
+                v.extend(b.create_changes());
+                continue;
+            }
+            if a.target != b.target {
+                v.push((ChangeType::AlterStatistics, b.target_sql()));
+            }
+            if a.owner != b.owner {
+                v.extend(b.owner_sql().map(|sql| (ChangeType::AlterOwner, sql)));
+            }
+            if a.comment != b.comment {
+                v.push((ChangeType::Comment, b.comment_sql()));
+            }
+        }
+        for b in &self.b_only {
+            v.extend(b.create_changes());
+        }
+        v
+    }
+}
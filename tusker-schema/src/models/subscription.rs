@@ -0,0 +1,164 @@
+use itertools::Itertools;
+
+use crate::{
+    diff::{ChangeType, Diff, DiffSql},
+    queries::SubscriptionRow,
+    sql::{comment_sql, owner_sql, quote_ident},
+};
+
+/// A logical replication subscription. The connection info is never
+/// inspected as it usually contains credentials. Subscriptions can
+/// therefore not be created by a migration and creating or dropping
+/// them results in a warning instead.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Subscription {
+    pub name: String,
+    pub owner: Option<String>,
+    pub enabled: bool,
+    pub publications: Vec<String>,
+    pub comment: Option<String>,
+}
+
+impl Subscription {
+    fn publications_sql(&self) -> String {
+        self.publications.iter().map(|p| quote_ident(p)).join(", ")
+    }
+
+    fn create_warning_sql(&self) -> String {
+        format!(
+            "-- WARNING: subscription {} must be created manually as its connection info is not inspected:\n\
+-- CREATE SUBSCRIPTION {} CONNECTION '...' PUBLICATION {}{};\n",
+            quote_ident(&self.name),
+            quote_ident(&self.name),
+            self.publications_sql(),
+            if self.enabled {
+                ""
+            } else {
+                " WITH (enabled = false)"
+            },
+        )
+    }
+
+    fn drop_warning_sql(&self) -> String {
+        format!(
+            "-- WARNING: subscription {} must be dropped manually as DROP SUBSCRIPTION cannot run inside a transaction:\n\
+-- DROP SUBSCRIPTION {};\n",
+            quote_ident(&self.name),
+            quote_ident(&self.name),
+        )
+    }
+
+    fn alter_sql(&self, previous: &Self) -> Vec<(ChangeType, String)> {
+        let mut v = Vec::new();
+        let name = quote_ident(&self.name);
+        if self.publications != previous.publications {
+            // Refreshing the publication cannot run inside a transaction
+            v.push((
+                ChangeType::AlterSubscription,
+                format!(
+                    "ALTER SUBSCRIPTION {} SET PUBLICATION {} WITH (refresh = false);\n",
+                    name,
+                    self.publications_sql(),
+                ),
+            ));
+        }
+        if self.enabled != previous.enabled {
+            v.push((
+                ChangeType::AlterSubscription,
+                format!(
+                    "ALTER SUBSCRIPTION {} {};\n",
+                    name,
+                    if self.enabled { "ENABLE" } else { "DISABLE" },
+                ),
+            ));
+        }
+        if self.owner != previous.owner {
+            v.extend(self.owner.as_ref().map(|owner| {
+                (
+                    ChangeType::AlterOwner,
+                    owner_sql("SUBSCRIPTION", &name, owner),
+                )
+            }));
+        }
+        if self.comment != previous.comment {
+            v.push((
+                ChangeType::Comment,
+                comment_sql("SUBSCRIPTION", &name, self.comment.as_deref()),
+            ));
+        }
+        v
+    }
+}
+
+impl From<SubscriptionRow> for Subscription {
+    fn from(row: SubscriptionRow) -> Self {
+        Self {
+            name: row.name,
+            owner: Some(row.owner),
+            enabled: row.enabled,
+            publications: row.publications,
+            comment: row.comment,
+        }
+    }
+}
+
+impl DiffSql for Diff<'_, Subscription> {
+    fn sql(&self) -> Vec<(ChangeType, String)> {
+        let mut v = Vec::new();
+        for a in &self.a_only {
+            v.push((ChangeType::Unsupported, a.drop_warning_sql()));
+        }
+        for (a, b) in &self.a_and_b {
+            v.extend(b.alter_sql(a));
+        }
+        for b in &self.b_only {
+            v.push((ChangeType::Unsupported, b.create_warning_sql()));
+        }
+        v
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::diff::{diff, ChangeType, DiffSql};
+
+    use super::Subscription;
+
+    fn subscription(enabled: bool, publications: &[&str]) -> Subscription {
+        Subscription {
+            name: "analytics".into(),
+            owner: None,
+            enabled,
+            publications: publications.iter().map(|p| p.to_string()).collect(),
+            comment: None,
+        }
+    }
+
+    #[test]
+    fn alters_publications_and_enabled_state() {
+        let a = [subscription(true, &["orders"])];
+        let b = [subscription(false, &["invoices", "orders"])];
+        assert_eq!(
+            diff(a.iter(), b.iter(), |s| &s.name).sql(),
+            vec![
+                (
+                    ChangeType::AlterSubscription,
+                    "ALTER SUBSCRIPTION \"analytics\" SET PUBLICATION \"invoices\", \"orders\" WITH (refresh = false);\n".into()
+                ),
+                (
+                    ChangeType::AlterSubscription,
+                    "ALTER SUBSCRIPTION \"analytics\" DISABLE;\n".into()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn warns_about_creating_subscriptions() {
+        let b = [subscription(true, &["orders"])];
+        let sql = diff([].iter(), b.iter(), |s| &s.name).sql();
+        assert_eq!(sql.len(), 1);
+        assert_eq!(sql[0].0, ChangeType::Unsupported);
+        assert!(sql[0].1.contains("CONNECTION '...'"));
+    }
+}
@@ -1,20 +1,86 @@
 use itertools::Itertools;
+use serde::Deserialize;
 use thiserror::Error;
 
 use crate::{
-    diff::{diff, ChangeType, Diff, DiffSql},
+    diff::{diff, ChangeType, Diff, DiffOptions, DiffSql},
     queries::{Class, Relkind},
-    sql::quote_ident,
+    sql::{comment_sql, owner_sql, quote_ident},
 };
 
 use super::column::Column;
 
-#[derive(Debug, Eq, PartialEq)]
+/// Tables are compared without taking the column order into account.
+/// See [`Table::column_order_differs`].
+#[derive(Debug, Eq)]
 pub struct Table {
     pub schema: String,
     pub name: String,
     pub kind: Relkind,
     pub columns: Vec<Column>,
+    pub comment: Option<String>,
+    pub owner: Option<String>,
+    pub unlogged: bool,
+    /// Storage parameters (`reloptions`) in `name=value` form
+    pub options: Vec<String>,
+    pub tablespace: Option<String>,
+    pub access_method: Option<String>,
+    /// Parent tables in `INHERITS` order
+    pub inherits: Vec<TableName>,
+    /// Parent table and bound of partitions
+    pub partition_of: Option<PartitionOf>,
+    /// Names of columns inherited from the parent tables. Only columns
+    /// which are declared locally, too, are part of `columns`.
+    pub inherited_columns: Vec<String>,
+    /// Names of constraints inherited from the parent tables. These are
+    /// not part of `Schema::constraints`.
+    pub inherited_constraints: Vec<String>,
+}
+
+impl PartialEq for Table {
+    fn eq(&self, other: &Self) -> bool {
+        self.schema == other.schema
+            && self.name == other.name
+            && self.kind == other.kind
+            && self
+                .comparable_columns(other)
+                .sorted_by(|a, b| a.name.cmp(&b.name))
+                .eq(other
+                    .comparable_columns(self)
+                    .sorted_by(|a, b| a.name.cmp(&b.name)))
+            && self.comment == other.comment
+            && self.owner == other.owner
+            && self.unlogged == other.unlogged
+            && self.options == other.options
+            && self.tablespace == other.tablespace
+            && self.access_method == other.access_method
+            && self.inherits == other.inherits
+            && self.partition_of == other.partition_of
+            && self.inherited_columns == other.inherited_columns
+            && self.inherited_constraints == other.inherited_constraints
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize)]
+pub struct TableName {
+    pub schema: String,
+    pub name: String,
+}
+
+impl TableName {
+    pub fn sql(&self) -> String {
+        format!("{}.{}", quote_ident(&self.schema), quote_ident(&self.name))
+    }
+}
+
+/// Partitions are created using `PARTITION OF` and take their columns
+/// from the parent table.
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize)]
+pub struct PartitionOf {
+    pub parent: TableName,
+    /// Partition bound as returned by `pg_get_expr`, e.g. `FOR VALUES
+    /// IN (1, 2)` or `DEFAULT`
+    pub bound: String,
 }
 
 impl TryFrom<Class> for Table {
@@ -25,6 +91,16 @@ impl TryFrom<Class> for Table {
             name: cls.name,
             kind: cls.relkind,
             columns: cls.columns.0,
+            comment: cls.comment,
+            owner: Some(cls.owner),
+            unlogged: cls.unlogged,
+            options: cls.options.into_iter().sorted().collect(),
+            tablespace: cls.tablespace,
+            access_method: cls.access_method,
+            inherits: cls.inherits.0,
+            partition_of: cls.partition_of.map(|p| p.0),
+            inherited_columns: cls.inherited_columns,
+            inherited_constraints: cls.inherited_constraints,
         })
     }
 }
@@ -34,14 +110,70 @@ impl TryFrom<Class> for Table {
 pub struct InvalidRelkind(Relkind);
 
 impl Table {
+    fn qualified_name(&self) -> String {
+        format!("{}.{}", quote_ident(&self.schema), quote_ident(&self.name))
+    }
+    /// Columns compared with `other`. `INHERIT` merges existing columns
+    /// with the inherited ones and keeps them local, so a local column is
+    /// considered equal to a column `other` only inherits.
+    fn comparable_columns<'a>(&'a self, other: &'a Self) -> impl Iterator<Item = &'a Column> {
+        self.columns.iter().filter(|c| {
+            other.columns.iter().any(|o| o.name == c.name)
+                || !other.inherited_columns.contains(&c.name)
+        })
+    }
     pub fn create(&self) -> String {
         let cols = self.columns.iter().map(|col| col.sql()).join(",\n    ");
-        format!(
-            "CREATE TABLE {}.{} (\n    {}\n);\n",
-            quote_ident(&self.schema),
-            quote_ident(&self.name),
-            cols
-        )
+        let mut storage = String::new();
+        if !self.inherits.is_empty() {
+            storage.push_str(&format!(
+                " INHERITS ({})",
+                self.inherits.iter().map(|p| p.sql()).join(", ")
+            ));
+        }
+        // The default access method is not spelled out so the generated
+        // SQL honors `default_table_access_method`.
+        if let Some(access_method) = self.access_method.as_ref().filter(|am| *am != "heap") {
+            storage.push_str(&format!(" USING {}", quote_ident(access_method)));
+        }
+        if !self.options.is_empty() {
+            storage.push_str(&format!(" WITH ({})", self.options.join(", ")));
+        }
+        if let Some(tablespace) = &self.tablespace {
+            storage.push_str(&format!(" TABLESPACE {}", quote_ident(tablespace)));
+        }
+        let mut sql = match &self.partition_of {
+            Some(partition_of) => format!(
+                "CREATE {}TABLE {}.{} PARTITION OF {}\n    {}{};\n",
+                if self.unlogged { "UNLOGGED " } else { "" },
+                quote_ident(&self.schema),
+                quote_ident(&self.name),
+                partition_of.parent.sql(),
+                partition_of.bound,
+                storage,
+            ),
+            None => format!(
+                "CREATE {}TABLE {}.{} (\n    {}\n){};\n",
+                if self.unlogged { "UNLOGGED " } else { "" },
+                quote_ident(&self.schema),
+                quote_ident(&self.name),
+                cols,
+                storage,
+            ),
+        };
+        // Column storage, compression and statistics target are not
+        // part of the column definition in all supported PostgreSQL
+        // versions and therefore set in a separate statement.
+        let attributes = self
+            .columns
+            .iter()
+            .flat_map(|col| col.attribute_changes(None))
+            .collect::<Vec<_>>();
+        if !attributes.is_empty() {
+            sql.push('\n');
+            sql.push_str(&self.alter_sql(attributes));
+        }
+        sql
     }
     pub fn drop(&self) -> String {
         format!(
@@ -84,6 +216,283 @@ $$;\n",
 
         output.join("\n")
     }
+    /// Names of the table attributes which differ from `other`. Columns
+    /// are not included.
+    pub fn changed_attributes(&self, other: &Self) -> Vec<&'static str> {
+        [
+            ("kind", self.kind != other.kind),
+            ("persistence", self.unlogged != other.unlogged),
+            ("storage parameters", self.options != other.options),
+            ("tablespace", self.tablespace != other.tablespace),
+            ("access method", self.access_method != other.access_method),
+            ("inheritance", self.inherits != other.inherits),
+            ("partition", self.partition_of != other.partition_of),
+            ("owner", self.owner != other.owner),
+            ("comment", self.comment != other.comment),
+        ]
+        .into_iter()
+        .filter_map(|(name, changed)| changed.then_some(name))
+        .collect()
+    }
+    pub fn owner_sql(&self) -> Option<String> {
+        self.owner
+            .as_ref()
+            .map(|owner| owner_sql("TABLE", &self.qualified_name(), owner))
+    }
+    /// `COMMENT ON` statements for the table and its columns. Only
+    /// comments which differ from `previous` are emitted. For newly
+    /// created tables `previous` is `None`.
+    pub fn comment_sql(&self, previous: Option<&Self>) -> Vec<(ChangeType, String)> {
+        let mut v = Vec::new();
+        if self.comment.as_deref() != previous.and_then(|p| p.comment.as_deref()) {
+            v.push((
+                ChangeType::Comment,
+                comment_sql("TABLE", &self.qualified_name(), self.comment.as_deref()),
+            ));
+        }
+        for column in &self.columns {
+            let previous_comment = previous
+                .and_then(|p| p.columns.iter().find(|c| c.name == column.name))
+                .and_then(|c| c.comment.as_deref());
+            if column.comment.as_deref() != previous_comment {
+                v.push((
+                    ChangeType::Comment,
+                    comment_sql(
+                        "COLUMN",
+                        &format!("{}.{}", self.qualified_name(), quote_ident(&column.name)),
+                        column.comment.as_deref(),
+                    ),
+                ));
+            }
+        }
+        v
+    }
+    /// `ALTER TABLE` clauses for changed storage properties.
+    pub fn storage_changes(old: &Self, new: &Self) -> Vec<(ChangeType, String)> {
+        let mut v = Vec::new();
+        if old.unlogged != new.unlogged {
+            v.push((
+                ChangeType::AlterTable,
+                if new.unlogged {
+                    "SET UNLOGGED".into()
+                } else {
+                    "SET LOGGED".into()
+                },
+            ));
+        }
+        let old_options = old
+            .options
+            .iter()
+            .filter_map(|o| o.split_once('='))
+            .collect::<Vec<_>>();
+        let new_options = new
+            .options
+            .iter()
+            .filter_map(|o| o.split_once('='))
+            .collect::<Vec<_>>();
+        let options = diff(old_options.iter(), new_options.iter(), |(k, _)| *k);
+        let reset = options.a_only.iter().map(|(k, _)| k).join(", ");
+        if !reset.is_empty() {
+            v.push((ChangeType::AlterTable, format!("RESET ({})", reset)));
+        }
+        let set = options
+            .a_and_b
+            .iter()
+            .filter(|(a, b)| a != b)
+            .map(|(_, b)| b)
+            .chain(options.b_only.iter())
+            .map(|(k, v)| format!("{}={}", k, v))
+            .join(", ");
+        if !set.is_empty() {
+            v.push((ChangeType::AlterTable, format!("SET ({})", set)));
+        }
+        if old.tablespace != new.tablespace {
+            v.push((
+                ChangeType::AlterTable,
+                format!(
+                    "SET TABLESPACE {}",
+                    quote_ident(new.tablespace.as_deref().unwrap_or("pg_default"))
+                ),
+            ));
+        }
+        if old.access_method != new.access_method {
+            if let Some(access_method) = &new.access_method {
+                v.push((
+                    ChangeType::AlterTable,
+                    format!("SET ACCESS METHOD {}", quote_ident(access_method)),
+                ));
+            }
+        }
+        v
+    }
+    /// `ALTER TABLE ... INHERIT/NO INHERIT` and `DETACH/ATTACH
+    /// PARTITION` statements. These are not combined with other `ALTER
+    /// TABLE` clauses as parents need to be detached before and attached
+    /// after all other changes.
+    pub fn inheritance_changes(old: &Self, new: &Self) -> Vec<(ChangeType, String)> {
+        let mut v = Vec::new();
+        for parent in old.inherits.iter().filter(|p| !new.inherits.contains(p)) {
+            v.push((
+                ChangeType::DropInherit,
+                format!(
+                    "ALTER TABLE {} NO INHERIT {};\n",
+                    new.qualified_name(),
+                    parent.sql()
+                ),
+            ));
+        }
+        for parent in new.inherits.iter().filter(|p| !old.inherits.contains(p)) {
+            v.push((
+                ChangeType::Inherit,
+                format!(
+                    "ALTER TABLE {} INHERIT {};\n",
+                    new.qualified_name(),
+                    parent.sql()
+                ),
+            ));
+        }
+        if old.partition_of != new.partition_of {
+            if let Some(partition_of) = &old.partition_of {
+                v.push((
+                    ChangeType::DropInherit,
+                    format!(
+                        "ALTER TABLE {} DETACH PARTITION {};\n",
+                        partition_of.parent.sql(),
+                        old.qualified_name()
+                    ),
+                ));
+            }
+            if let Some(partition_of) = &new.partition_of {
+                v.push((
+                    ChangeType::Inherit,
+                    format!(
+                        "ALTER TABLE {} ATTACH PARTITION {} {};\n",
+                        partition_of.parent.sql(),
+                        new.qualified_name(),
+                        partition_of.bound
+                    ),
+                ));
+            }
+        }
+        v
+    }
+    /// Returns `true` if `new` no longer inherits from one of the parents
+    /// of `old`.
+    pub fn detaches(old: &Self, new: &Self) -> bool {
+        old.inherits.iter().any(|p| !new.inherits.contains(p))
+    }
+    /// Column changes taking inheritance changes into account. PostgreSQL
+    /// merges existing columns when running `INHERIT` and keeps inherited
+    /// columns as regular columns when running `NO INHERIT`.
+    pub fn column_changes(
+        old: &Self,
+        new: &Self,
+        options: &DiffOptions,
+    ) -> Vec<(ChangeType, String)> {
+        let mut columns = old.diff_columns(new);
+        columns
+            .a_only
+            .retain(|c| !new.inherited_columns.contains(&c.name));
+        columns
+            .b_only
+            .retain(|c| !old.inherited_columns.contains(&c.name));
+        let mut v = columns.sql_with(options);
+        if Table::detaches(old, new) {
+            // Local columns are dropped by the column diff
+            for name in &old.inherited_columns {
+                if !new.inherited_columns.contains(name)
+                    && !old.columns.iter().any(|c| &c.name == name)
+                    && !new.columns.iter().any(|c| &c.name == name)
+                {
+                    v.push((
+                        ChangeType::DropColumn,
+                        format!("DROP COLUMN {}", quote_ident(name)),
+                    ));
+                }
+            }
+        }
+        v
+    }
+    /// Names of all columns. Columns inherited when creating the table
+    /// come before its own columns.
+    fn column_names(&self) -> impl Iterator<Item = &str> {
+        self.inherited_columns.iter().map(String::as_str).chain(
+            self.columns
+                .iter()
+                .map(|c| c.name.as_str())
+                .filter(|name| !self.inherited_columns.iter().any(|i| i == name)),
+        )
+    }
+    /// Column order after migrating `old` to `new`. PostgreSQL can't
+    /// reorder columns and always appends new ones.
+    fn migrated_column_order<'a>(old: &'a Self, new: &'a Self) -> Vec<&'a str> {
+        old.column_names()
+            .filter(|name| new.column_names().any(|n| n == *name))
+            .chain(
+                new.column_names()
+                    .filter(|name| !old.column_names().any(|o| o == *name)),
+            )
+            .collect()
+    }
+    /// Returns `true` if migrating `old` to `new` results in a column
+    /// order different from `new`. This happens if columns of `new` were
+    /// reordered or a column was added in the middle of the table.
+    pub fn column_order_differs(old: &Self, new: &Self) -> bool {
+        !Table::migrated_column_order(old, new)
+            .into_iter()
+            .eq(new.column_names())
+    }
+    /// Warning about the column order drift of `old` and `new`. This is
+    /// not part of the diff as there is no statement to fix it.
+    pub fn column_order_warning(old: &Self, new: &Self) -> String {
+        format!(
+            "columns of table {} can't be reordered and will differ from the target order\n\
+Resulting order: {}\n\
+Target order: {}",
+            new.qualified_name(),
+            Table::migrated_column_order(old, new).join(", "),
+            new.column_names().join(", "),
+        )
+    }
+    /// Returns `true` if migrating `old` to `new` drops any columns
+    pub fn drops_columns(old: &Self, new: &Self, options: &DiffOptions) -> bool {
+        Table::column_changes(old, new, options)
+            .iter()
+            .any(|(change_type, _)| *change_type == ChangeType::DropColumn)
+    }
+    fn is_parent_of(&self, other: &Self) -> bool {
+        other
+            .inherits
+            .iter()
+            .any(|p| p.schema == self.schema && p.name == self.name)
+    }
+    /// Order tables so that parent tables come before the tables
+    /// inheriting from them or, if `children_first` is set, after them.
+    /// Tables which are not related keep their order.
+    pub(crate) fn inheritance_order(tables: Vec<&Table>, children_first: bool) -> Vec<&Table> {
+        let mut ordered = Vec::with_capacity(tables.len());
+        let mut pending = tables;
+        while !pending.is_empty() {
+            let (ready, blocked): (Vec<_>, Vec<_>) = pending.iter().copied().partition(|table| {
+                !pending.iter().any(|other| {
+                    if children_first {
+                        table.is_parent_of(other)
+                    } else {
+                        other.is_parent_of(table)
+                    }
+                })
+            });
+            if ready.is_empty() {
+                // Inheritance cycles are rejected by PostgreSQL. This is
+                // merely a safeguard against looping forever.
+                ordered.extend(blocked);
+                break;
+            }
+            ordered.extend(ready);
+            pending = blocked;
+        }
+        ordered
+    }
     pub fn diff_columns<'a>(&'a self, other: &'a Self) -> Diff<'a, Column> {
         diff(self.columns.iter(), other.columns.iter(), |c| &c.name)
     }
@@ -91,19 +500,174 @@ $$;\n",
 
 impl DiffSql for Diff<'_, Table> {
     fn sql(&self) -> Vec<(ChangeType, String)> {
+        self.sql_with(&DiffOptions::default())
+    }
+    fn sql_with(&self, options: &DiffOptions) -> Vec<(ChangeType, String)> {
         let mut v = Vec::new();
         for a in &self.a_only {
             v.push((ChangeType::DropTable, a.drop()));
         }
         for (a, b) in &self.a_and_b {
-            let col_sql = a.diff_columns(b).sql();
-            if !col_sql.is_empty() {
-                v.push((ChangeType::AlterColumn, b.alter_sql(col_sql)));
+            v.extend(Table::inheritance_changes(a, b));
+            let storage_sql = Table::storage_changes(a, b);
+            if !storage_sql.is_empty() {
+                v.push((ChangeType::AlterTable, b.alter_sql(storage_sql)));
             }
+            let column_sql = Table::column_changes(a, b, options);
+            if !column_sql.is_empty() {
+                v.push((ChangeType::AlterColumn, b.alter_sql(column_sql)));
+            }
+            if a.owner != b.owner {
+                v.extend(b.owner_sql().map(|sql| (ChangeType::AlterOwner, sql)));
+            }
+            v.extend(b.comment_sql(Some(a)));
         }
         for b in &self.b_only {
             v.push((ChangeType::CreateTable, b.create()));
+            v.extend(b.owner_sql().map(|sql| (ChangeType::AlterOwner, sql)));
+            v.extend(b.comment_sql(None));
         }
         v
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        diff::{ChangeType, Diff, DiffSql},
+        models::column::{Column, Generated, Identity, Storage},
+        queries::Relkind,
+    };
+
+    use super::Table;
+
+    fn table(owner: Option<&str>) -> Table {
+        Table {
+            schema: "public".into(),
+            name: "fruit".into(),
+            kind: Relkind::OrdinaryTable,
+            columns: vec![],
+            comment: None,
+            owner: owner.map(Into::into),
+            unlogged: false,
+            options: vec![],
+            tablespace: None,
+            access_method: Some("heap".into()),
+            inherits: vec![],
+            partition_of: None,
+            inherited_columns: vec![],
+            inherited_constraints: vec![],
+        }
+    }
+
+    #[test]
+    fn alters_owner_of_existing_tables() {
+        let old = table(Some("postgres"));
+        let new = table(Some("migrator"));
+        let diff = Diff {
+            a_only: vec![],
+            a_and_b: vec![(&old, &new)],
+            b_only: vec![],
+        };
+
+        assert_eq!(
+            diff.sql(),
+            vec![(
+                ChangeType::AlterOwner,
+                "ALTER TABLE \"public\".\"fruit\" OWNER TO \"migrator\";\n".into()
+            )]
+        );
+    }
+
+    #[test]
+    fn ignores_owner_if_not_inspected() {
+        let new = table(None);
+        let diff = Diff {
+            a_only: vec![],
+            a_and_b: vec![],
+            b_only: vec![&new],
+        };
+
+        assert_eq!(
+            diff.sql(),
+            vec![(
+                ChangeType::CreateTable,
+                "CREATE TABLE \"public\".\"fruit\" (\n    \n);\n".into()
+            )]
+        );
+    }
+
+    #[test]
+    fn alters_tablespace_and_access_method() {
+        let old = table(None);
+        let new = Table {
+            tablespace: Some("fast".into()),
+            access_method: Some("columnar".into()),
+            ..table(None)
+        };
+
+        assert_eq!(
+            Table::storage_changes(&old, &new),
+            vec![
+                (ChangeType::AlterTable, "SET TABLESPACE \"fast\"".into()),
+                (
+                    ChangeType::AlterTable,
+                    "SET ACCESS METHOD \"columnar\"".into()
+                ),
+            ]
+        );
+        assert_eq!(
+            Table::storage_changes(&new, &old),
+            vec![
+                (
+                    ChangeType::AlterTable,
+                    "SET TABLESPACE \"pg_default\"".into()
+                ),
+                (ChangeType::AlterTable, "SET ACCESS METHOD \"heap\"".into()),
+            ]
+        );
+    }
+
+    fn column(name: &str) -> Column {
+        Column {
+            name: name.into(),
+            r#type: "text".into(),
+            base_type: "text".into(),
+            typmod: -1,
+            notnull: false,
+            identity: Identity::No,
+            generated: Generated::No,
+            default: None,
+            comment: None,
+            storage: Storage::Extended,
+            default_storage: Storage::Extended,
+            compression: None,
+            statistics: None,
+        }
+    }
+
+    #[test]
+    fn detects_column_order_drift() {
+        let with_columns = |names: &[&str]| Table {
+            columns: names.iter().map(|name| column(name)).collect(),
+            ..table(None)
+        };
+        let old = with_columns(&["id", "name", "email"]);
+
+        let reordered = with_columns(&["id", "email", "name"]);
+        assert_eq!(old, reordered);
+        assert!(Table::column_order_differs(&old, &reordered));
+
+        let appended = with_columns(&["id", "email", "phone"]);
+        assert!(!Table::column_order_differs(&old, &appended));
+
+        let inserted = with_columns(&["id", "phone", "name", "email"]);
+        assert!(Table::column_order_differs(&old, &inserted));
+        assert_eq!(
+            Table::column_order_warning(&old, &inserted),
+            "columns of table \"public\".\"fruit\" can't be reordered and will differ from the target order\n\
+Resulting order: id, name, email, phone\n\
+Target order: id, phone, name, email"
+        );
+    }
+}
@@ -0,0 +1,203 @@
+use std::collections::BTreeMap;
+
+use itertools::Itertools;
+
+use crate::{
+    diff::{ChangeType, Diff, DiffSql},
+    queries::TextSearchConfigurationRow,
+    sql::{comment_sql, owner_sql, quote_ident},
+};
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct TextSearchConfiguration {
+    pub schema: String,
+    pub name: String,
+    /// Already qualified and quoted parser name
+    pub parser: String,
+    /// Token types and the already qualified and quoted dictionaries
+    /// they are mapped to in the order they are consulted.
+    pub mappings: BTreeMap<String, Vec<String>>,
+    pub comment: Option<String>,
+    pub owner: Option<String>,
+}
+
+impl TextSearchConfiguration {
+    fn qualified_name(&self) -> String {
+        format!("{}.{}", quote_ident(&self.schema), quote_ident(&self.name))
+    }
+
+    fn create_sql(&self) -> String {
+        format!(
+            "CREATE TEXT SEARCH CONFIGURATION {} (PARSER = {});\n",
+            self.qualified_name(),
+            self.parser
+        )
+    }
+
+    fn drop_sql(&self) -> String {
+        format!(
+            "DROP TEXT SEARCH CONFIGURATION {};\n",
+            self.qualified_name()
+        )
+    }
+
+    fn mapping_sql(&self, action: &str, token: &str, dictionaries: Option<&[String]>) -> String {
+        let mut sql = format!(
+            "ALTER TEXT SEARCH CONFIGURATION {} {} MAPPING FOR {}",
+            self.qualified_name(),
+            action,
+            quote_ident(token)
+        );
+        if let Some(dictionaries) = dictionaries {
+            sql.push_str(&format!(" WITH {}", dictionaries.iter().join(", ")));
+        }
+        sql.push_str(";\n");
+        sql
+    }
+
+    /// Changed mappings are dropped and added again as the dictionaries
+    /// they used might be dropped and the new ones created in between.
+    fn mapping_changes(&self, previous: Option<&Self>) -> Vec<(ChangeType, String)> {
+        let empty = BTreeMap::new();
+        let old = previous.map(|p| &p.mappings).unwrap_or(&empty);
+        let mut v = Vec::new();
+        for (token, dictionaries) in old {
+            if self.mappings.get(token) != Some(dictionaries) {
+                v.push((
+                    ChangeType::DropTextSearchMapping,
+                    self.mapping_sql("DROP", token, None),
+                ));
+            }
+        }
+        for (token, dictionaries) in &self.mappings {
+            if old.get(token) != Some(dictionaries) {
+                v.push((
+                    ChangeType::AlterTextSearchConfiguration,
+                    self.mapping_sql("ADD", token, Some(dictionaries)),
+                ));
+            }
+        }
+        v
+    }
+
+    fn owner_sql(&self) -> Option<String> {
+        self.owner
+            .as_ref()
+            .map(|owner| owner_sql("TEXT SEARCH CONFIGURATION", &self.qualified_name(), owner))
+    }
+
+    fn comment_sql(&self) -> String {
+        comment_sql(
+            "TEXT SEARCH CONFIGURATION",
+            &self.qualified_name(),
+            self.comment.as_deref(),
+        )
+    }
+}
+
+impl From<TextSearchConfigurationRow> for TextSearchConfiguration {
+    fn from(row: TextSearchConfigurationRow) -> Self {
+        Self {
+            schema: row.schema,
+            name: row.name,
+            parser: row.parser,
+            mappings: row.mappings.0,
+            comment: row.comment,
+            owner: Some(row.owner),
+        }
+    }
+}
+
+impl DiffSql for Diff<'_, TextSearchConfiguration> {
+    fn sql(&self) -> Vec<(ChangeType, String)> {
+        let mut v = Vec::new();
+        let mut creates = self.b_only.clone();
+        for a in &self.a_only {
+            v.push((ChangeType::DropTextSearchConfiguration, a.drop_sql()));
+        }
+        for (a, b) in &self.a_and_b {
+            if a.parser != b.parser {
+                v.push((ChangeType::DropTextSearchConfiguration, a.drop_sql()));
+                creates.push(b);
+                continue;
+            }
+            v.extend(b.mapping_changes(Some(a)));
+            if a.owner != b.owner {
+                v.extend(b.owner_sql().map(|sql| (ChangeType::AlterOwner, sql)));
+            }
+            if a.comment != b.comment {
+                v.push((ChangeType::Comment, b.comment_sql()));
+            }
+        }
+        for b in creates {
+            v.push((ChangeType::CreateTextSearchConfiguration, b.create_sql()));
+            v.extend(b.mapping_changes(None));
+            v.extend(b.owner_sql().map(|sql| (ChangeType::AlterOwner, sql)));
+            if b.comment.is_some() {
+                v.push((ChangeType::Comment, b.comment_sql()));
+            }
+        }
+        v
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use crate::diff::{diff, ChangeType, DiffSql};
+
+    use super::TextSearchConfiguration;
+
+    fn configuration(mappings: &[(&str, &[&str])]) -> TextSearchConfiguration {
+        TextSearchConfiguration {
+            schema: "public".into(),
+            name: "search".into(),
+            parser: "pg_catalog.\"default\"".into(),
+            mappings: mappings
+                .iter()
+                .map(|(token, dicts)| {
+                    (
+                        token.to_string(),
+                        dicts.iter().map(|d| d.to_string()).collect(),
+                    )
+                })
+                .collect::<BTreeMap<_, _>>(),
+            comment: None,
+            owner: None,
+        }
+    }
+
+    #[test]
+    fn diffs_mappings() {
+        let a = [configuration(&[
+            ("asciiword", &["pg_catalog.english_stem"]),
+            ("email", &["pg_catalog.simple"]),
+        ])];
+        let b = [configuration(&[
+            ("asciiword", &["public.synonyms", "pg_catalog.english_stem"]),
+            ("word", &["pg_catalog.simple"]),
+        ])];
+        assert_eq!(
+            diff(a.iter(), b.iter(), |c| &c.name).sql(),
+            vec![
+                (
+                    ChangeType::DropTextSearchMapping,
+                    "ALTER TEXT SEARCH CONFIGURATION \"public\".\"search\" DROP MAPPING FOR \"asciiword\";\n".into()
+                ),
+                (
+                    ChangeType::DropTextSearchMapping,
+                    "ALTER TEXT SEARCH CONFIGURATION \"public\".\"search\" DROP MAPPING FOR \"email\";\n".into()
+                ),
+                (
+                    ChangeType::AlterTextSearchConfiguration,
+                    "ALTER TEXT SEARCH CONFIGURATION \"public\".\"search\" ADD MAPPING FOR \"asciiword\" WITH public.synonyms, pg_catalog.english_stem;\n".into()
+                ),
+                (
+                    ChangeType::AlterTextSearchConfiguration,
+                    "ALTER TEXT SEARCH CONFIGURATION \"public\".\"search\" ADD MAPPING FOR \"word\" WITH pg_catalog.simple;\n".into()
+                ),
+            ]
+        );
+    }
+}
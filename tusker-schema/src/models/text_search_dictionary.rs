@@ -0,0 +1,166 @@
+use itertools::Itertools;
+
+use crate::{
+    diff::{ChangeType, Diff, DiffSql},
+    queries::TextSearchDictionaryRow,
+    sql::{comment_sql, owner_sql, quote_ident},
+};
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct TextSearchDictionary {
+    pub schema: String,
+    pub name: String,
+    /// Already qualified and quoted template name
+    pub template: String,
+    /// Template specific options. Names are already quoted if needed and
+    /// values are SQL literals.
+    pub options: Vec<(String, String)>,
+    pub comment: Option<String>,
+    pub owner: Option<String>,
+}
+
+/// Split the options as returned in `pg_ts_dict.dictinitoption` (e.g.
+/// `language = 'english', stopwords = 'english'`) into name/value pairs.
+fn parse_options(options: &str) -> Vec<(String, String)> {
+    let mut items = Vec::new();
+    let mut item = String::new();
+    let mut quote = None;
+    for c in options.chars() {
+        match (c, quote) {
+            ('\'' | '"', None) => quote = Some(c),
+            (_, Some(q)) if c == q => quote = None,
+            (',', None) => {
+                items.push(std::mem::take(&mut item));
+                continue;
+            }
+            _ => {}
+        }
+        item.push(c);
+    }
+    items.push(item);
+    items
+        .into_iter()
+        .filter_map(|item| {
+            let (name, value) = item.split_once('=')?;
+            Some((name.trim().to_owned(), value.trim().to_owned()))
+        })
+        .collect()
+}
+
+impl TextSearchDictionary {
+    fn qualified_name(&self) -> String {
+        format!("{}.{}", quote_ident(&self.schema), quote_ident(&self.name))
+    }
+
+    fn create_sql(&self) -> String {
+        format!(
+            "CREATE TEXT SEARCH DICTIONARY {} (\n    {}\n);\n",
+            self.qualified_name(),
+            std::iter::once(format!("TEMPLATE = {}", self.template))
+                .chain(self.options.iter().map(|(k, v)| format!("{} = {}", k, v)))
+                .join(",\n    ")
+        )
+    }
+
+    fn drop_sql(&self) -> String {
+        format!("DROP TEXT SEARCH DICTIONARY {};\n", self.qualified_name())
+    }
+
+    /// Options which are no longer present are removed by listing them
+    /// without a value.
+    fn alter_sql(&self, previous: &Self) -> String {
+        let set = self.options.iter().map(|(k, v)| format!("{} = {}", k, v));
+        let reset = previous
+            .options
+            .iter()
+            .filter(|(k, _)| !self.options.iter().any(|(name, _)| name == k))
+            .map(|(k, _)| k.clone());
+        format!(
+            "ALTER TEXT SEARCH DICTIONARY {} (\n    {}\n);\n",
+            self.qualified_name(),
+            set.chain(reset).join(",\n    ")
+        )
+    }
+
+    fn owner_sql(&self) -> Option<String> {
+        self.owner
+            .as_ref()
+            .map(|owner| owner_sql("TEXT SEARCH DICTIONARY", &self.qualified_name(), owner))
+    }
+
+    fn comment_sql(&self) -> String {
+        comment_sql(
+            "TEXT SEARCH DICTIONARY",
+            &self.qualified_name(),
+            self.comment.as_deref(),
+        )
+    }
+}
+
+impl From<TextSearchDictionaryRow> for TextSearchDictionary {
+    fn from(row: TextSearchDictionaryRow) -> Self {
+        Self {
+            schema: row.schema,
+            name: row.name,
+            template: row.template,
+            options: row
+                .options
+                .as_deref()
+                .map(parse_options)
+                .unwrap_or_default(),
+            comment: row.comment,
+            owner: Some(row.owner),
+        }
+    }
+}
+
+impl DiffSql for Diff<'_, TextSearchDictionary> {
+    fn sql(&self) -> Vec<(ChangeType, String)> {
+        let mut v = Vec::new();
+        let mut creates = self.b_only.clone();
+        for a in &self.a_only {
+            v.push((ChangeType::DropTextSearchDictionary, a.drop_sql()));
+        }
+        for (a, b) in &self.a_and_b {
+            if a.template != b.template {
+                v.push((ChangeType::DropTextSearchDictionary, a.drop_sql()));
+                creates.push(b);
+                continue;
+            }
+            if a.options != b.options {
+                v.push((ChangeType::AlterTextSearchDictionary, b.alter_sql(a)));
+            }
+            if a.owner != b.owner {
+                v.extend(b.owner_sql().map(|sql| (ChangeType::AlterOwner, sql)));
+            }
+            if a.comment != b.comment {
+                v.push((ChangeType::Comment, b.comment_sql()));
+            }
+        }
+        for b in creates {
+            v.push((ChangeType::CreateTextSearchDictionary, b.create_sql()));
+            v.extend(b.owner_sql().map(|sql| (ChangeType::AlterOwner, sql)));
+            if b.comment.is_some() {
+                v.push((ChangeType::Comment, b.comment_sql()));
+            }
+        }
+        v
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_options;
+
+    #[test]
+    fn parses_options() {
+        assert_eq!(
+            parse_options("stopwords = 'english', accept = 'false', \"My Opt\" = 'a, b'"),
+            vec![
+                ("stopwords".into(), "'english'".into()),
+                ("accept".into(), "'false'".into()),
+                ("\"My Opt\"".into(), "'a, b'".into()),
+            ]
+        );
+    }
+}
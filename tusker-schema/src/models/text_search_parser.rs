@@ -0,0 +1,102 @@
+use crate::{
+    diff::{ChangeType, Diff, DiffSql},
+    queries::TextSearchParserRow,
+    sql::{comment_sql, quote_ident},
+};
+
+/// Text search parser. All functions are already qualified and quoted.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct TextSearchParser {
+    pub schema: String,
+    pub name: String,
+    pub start: String,
+    pub gettoken: String,
+    pub end: String,
+    pub lextypes: String,
+    pub headline: Option<String>,
+    pub comment: Option<String>,
+}
+
+impl TextSearchParser {
+    fn qualified_name(&self) -> String {
+        format!("{}.{}", quote_ident(&self.schema), quote_ident(&self.name))
+    }
+
+    /// Compare everything but the comment.
+    fn definition_eq(&self, other: &Self) -> bool {
+        self.start == other.start
+            && self.gettoken == other.gettoken
+            && self.end == other.end
+            && self.lextypes == other.lextypes
+            && self.headline == other.headline
+    }
+
+    fn create_sql(&self) -> String {
+        let mut options = vec![
+            format!("START = {}", self.start),
+            format!("GETTOKEN = {}", self.gettoken),
+            format!("END = {}", self.end),
+            format!("LEXTYPES = {}", self.lextypes),
+        ];
+        if let Some(headline) = &self.headline {
+            options.push(format!("HEADLINE = {}", headline));
+        }
+        format!(
+            "CREATE TEXT SEARCH PARSER {} (\n    {}\n);\n",
+            self.qualified_name(),
+            options.join(",\n    ")
+        )
+    }
+
+    fn drop_sql(&self) -> String {
+        format!("DROP TEXT SEARCH PARSER {};\n", self.qualified_name())
+    }
+
+    fn comment_sql(&self) -> String {
+        comment_sql(
+            "TEXT SEARCH PARSER",
+            &self.qualified_name(),
+            self.comment.as_deref(),
+        )
+    }
+}
+
+impl From<TextSearchParserRow> for TextSearchParser {
+    fn from(row: TextSearchParserRow) -> Self {
+        Self {
+            schema: row.schema,
+            name: row.name,
+            start: row.start,
+            gettoken: row.gettoken,
+            end: row.end,
+            lextypes: row.lextypes,
+            headline: row.headline,
+            comment: row.comment,
+        }
+    }
+}
+
+impl DiffSql for Diff<'_, TextSearchParser> {
+    fn sql(&self) -> Vec<(ChangeType, String)> {
+        let mut v = Vec::new();
+        let mut creates = self.b_only.clone();
+        for a in &self.a_only {
+            v.push((ChangeType::DropTextSearchParser, a.drop_sql()));
+        }
+        for (a, b) in &self.a_and_b {
+            if !a.definition_eq(b) {
+                v.push((ChangeType::DropTextSearchParser, a.drop_sql()));
+                creates.push(b);
+            } else if a.comment != b.comment {
+                v.push((ChangeType::Comment, b.comment_sql()));
+            }
+        }
+        for b in creates {
+            v.push((ChangeType::CreateTextSearchParser, b.create_sql()));
+            if b.comment.is_some() {
+                v.push((ChangeType::Comment, b.comment_sql()));
+            }
+        }
+        v
+    }
+}
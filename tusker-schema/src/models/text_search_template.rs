@@ -0,0 +1,83 @@
+use crate::{
+    diff::{ChangeType, Diff, DiffSql},
+    queries::TextSearchTemplateRow,
+    sql::{comment_sql, quote_ident},
+};
+
+/// Text search template. All functions are already qualified and quoted.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct TextSearchTemplate {
+    pub schema: String,
+    pub name: String,
+    pub init: Option<String>,
+    pub lexize: String,
+    pub comment: Option<String>,
+}
+
+impl TextSearchTemplate {
+    fn qualified_name(&self) -> String {
+        format!("{}.{}", quote_ident(&self.schema), quote_ident(&self.name))
+    }
+
+    fn create_sql(&self) -> String {
+        let mut options = Vec::new();
+        if let Some(init) = &self.init {
+            options.push(format!("INIT = {}", init));
+        }
+        options.push(format!("LEXIZE = {}", self.lexize));
+        format!(
+            "CREATE TEXT SEARCH TEMPLATE {} (\n    {}\n);\n",
+            self.qualified_name(),
+            options.join(",\n    ")
+        )
+    }
+
+    fn drop_sql(&self) -> String {
+        format!("DROP TEXT SEARCH TEMPLATE {};\n", self.qualified_name())
+    }
+
+    fn comment_sql(&self) -> String {
+        comment_sql(
+            "TEXT SEARCH TEMPLATE",
+            &self.qualified_name(),
+            self.comment.as_deref(),
+        )
+    }
+}
+
+impl From<TextSearchTemplateRow> for TextSearchTemplate {
+    fn from(row: TextSearchTemplateRow) -> Self {
+        Self {
+            schema: row.schema,
+            name: row.name,
+            init: row.init,
+            lexize: row.lexize,
+            comment: row.comment,
+        }
+    }
+}
+
+impl DiffSql for Diff<'_, TextSearchTemplate> {
+    fn sql(&self) -> Vec<(ChangeType, String)> {
+        let mut v = Vec::new();
+        let mut creates = self.b_only.clone();
+        for a in &self.a_only {
+            v.push((ChangeType::DropTextSearchTemplate, a.drop_sql()));
+        }
+        for (a, b) in &self.a_and_b {
+            if a.init != b.init || a.lexize != b.lexize {
+                v.push((ChangeType::DropTextSearchTemplate, a.drop_sql()));
+                creates.push(b);
+            } else if a.comment != b.comment {
+                v.push((ChangeType::Comment, b.comment_sql()));
+            }
+        }
+        for b in creates {
+            v.push((ChangeType::CreateTextSearchTemplate, b.create_sql()));
+            if b.comment.is_some() {
+                v.push((ChangeType::Comment, b.comment_sql()));
+            }
+        }
+        v
+    }
+}
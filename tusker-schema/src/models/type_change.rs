@@ -0,0 +1,219 @@
+use std::collections::HashMap;
+
+use crate::queries::TypeCastRow;
+
+use super::column::Column;
+
+/// Casts known to the server keyed by source and target type. This
+/// includes the built-in casts and is used to classify column type
+/// changes.
+#[derive(Debug, Clone, Default)]
+pub struct TypeCasts {
+    casts: HashMap<(String, String), TypeCast>,
+}
+
+#[derive(Debug, Clone)]
+struct TypeCast {
+    /// `e` = explicit, `a` = assignment, `i` = implicit
+    context: String,
+    /// `f` = function, `i` = inout, `b` = binary coercible
+    method: String,
+}
+
+impl TypeCasts {
+    pub fn is_empty(&self) -> bool {
+        self.casts.is_empty()
+    }
+}
+
+impl FromIterator<TypeCastRow> for TypeCasts {
+    fn from_iter<I: IntoIterator<Item = TypeCastRow>>(rows: I) -> Self {
+        Self {
+            casts: rows
+                .into_iter()
+                .map(|row| {
+                    (
+                        (row.source, row.target),
+                        TypeCast {
+                            context: row.context,
+                            method: row.method,
+                        },
+                    )
+                })
+                .collect(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum TypeChange {
+    /// Only the catalog is updated, e.g. `varchar(n)` to `text`
+    NoRewrite,
+    /// The table and its indexes are rewritten using an implicit or
+    /// assignment cast
+    Rewrite,
+    /// There is no implicit or assignment cast. A `USING` expression is
+    /// required and the table is rewritten.
+    ExplicitCast,
+    /// The cast catalog was not loaded
+    Unknown,
+}
+
+/// String types can be assigned from any type using I/O conversion.
+const STRING_TYPES: [&str; 3] = ["text", "character varying", "character"];
+
+impl TypeChange {
+    pub fn classify(old: &Column, new: &Column, casts: &TypeCasts) -> Self {
+        if old.base_type == new.base_type {
+            return match typmod_widens(&new.base_type, old.typmod, new.typmod) {
+                true => Self::NoRewrite,
+                false => Self::Rewrite,
+            };
+        }
+        if casts.is_empty() {
+            return Self::Unknown;
+        }
+        match casts
+            .casts
+            .get(&(old.base_type.clone(), new.base_type.clone()))
+        {
+            // Binary coercible casts to types with a length limit still
+            // need to check all values.
+            Some(cast) if cast.method == "b" && new.typmod == -1 => Self::NoRewrite,
+            Some(cast) if cast.context == "i" || cast.context == "a" => Self::Rewrite,
+            Some(cast) if cast.method == "b" => Self::Rewrite,
+            Some(_) => Self::ExplicitCast,
+            None if STRING_TYPES.contains(&new.base_type.as_str()) => Self::Rewrite,
+            None => Self::ExplicitCast,
+        }
+    }
+}
+
+/// Changes of the type modifier which PostgreSQL applies without
+/// rewriting the table. See the `*_support` functions of the types.
+fn typmod_widens(base_type: &str, old: i32, new: i32) -> bool {
+    if old == new || new == -1 {
+        return true;
+    }
+    if old == -1 {
+        return false;
+    }
+    match base_type {
+        "character varying" | "bit varying" => new >= old,
+        "timestamp without time zone"
+        | "timestamp with time zone"
+        | "time without time zone"
+        | "time with time zone" => new >= old,
+        // ((precision << 16) | scale) + 4
+        "numeric" => {
+            let (old, new) = (old - 4, new - 4);
+            (old & 0xffff) == (new & 0xffff) && (new >> 16) >= (old >> 16)
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        models::column::{Column, Generated, Identity, Storage},
+        queries::TypeCastRow,
+    };
+
+    use super::{TypeCasts, TypeChange};
+
+    fn column(r#type: &str, base_type: &str, typmod: i32) -> Column {
+        Column {
+            name: "value".into(),
+            r#type: r#type.into(),
+            base_type: base_type.into(),
+            typmod,
+            notnull: false,
+            identity: Identity::No,
+            generated: Generated::No,
+            default: None,
+            comment: None,
+            storage: Storage::Extended,
+            default_storage: Storage::Extended,
+            compression: None,
+            statistics: None,
+        }
+    }
+
+    fn casts() -> TypeCasts {
+        [
+            ("character varying", "text", "i", "b"),
+            ("integer", "bigint", "i", "f"),
+            ("text", "character varying", "i", "b"),
+            ("bigint", "boolean", "e", "f"),
+        ]
+        .into_iter()
+        .map(|(source, target, context, method)| TypeCastRow {
+            source: source.into(),
+            target: target.into(),
+            context: context.into(),
+            method: method.into(),
+        })
+        .collect()
+    }
+
+    #[test]
+    fn classifies_type_changes() {
+        let casts = casts();
+        let varchar = column("character varying(20)", "character varying", 24);
+        let text = column("text", "text", -1);
+        let int = column("integer", "integer", -1);
+        let bigint = column("bigint", "bigint", -1);
+        let boolean = column("boolean", "boolean", -1);
+        assert_eq!(
+            TypeChange::classify(&varchar, &text, &casts),
+            TypeChange::NoRewrite
+        );
+        assert_eq!(
+            TypeChange::classify(&text, &varchar, &casts),
+            TypeChange::Rewrite
+        );
+        assert_eq!(
+            TypeChange::classify(&int, &bigint, &casts),
+            TypeChange::Rewrite
+        );
+        assert_eq!(
+            TypeChange::classify(&bigint, &boolean, &casts),
+            TypeChange::ExplicitCast
+        );
+        assert_eq!(
+            TypeChange::classify(&text, &int, &casts),
+            TypeChange::ExplicitCast
+        );
+        assert_eq!(
+            TypeChange::classify(&int, &text, &casts),
+            TypeChange::Rewrite
+        );
+    }
+
+    #[test]
+    fn classifies_typmod_changes() {
+        let casts = TypeCasts::default();
+        let varchar_20 = column("character varying(20)", "character varying", 24);
+        let varchar_40 = column("character varying(40)", "character varying", 44);
+        let numeric_10_2 = column("numeric(10,2)", "numeric", (10 << 16 | 2) + 4);
+        let numeric_12_2 = column("numeric(12,2)", "numeric", (12 << 16 | 2) + 4);
+        let numeric_12_3 = column("numeric(12,3)", "numeric", (12 << 16 | 3) + 4);
+        assert_eq!(
+            TypeChange::classify(&varchar_20, &varchar_40, &casts),
+            TypeChange::NoRewrite
+        );
+        assert_eq!(
+            TypeChange::classify(&varchar_40, &varchar_20, &casts),
+            TypeChange::Rewrite
+        );
+        assert_eq!(
+            TypeChange::classify(&numeric_10_2, &numeric_12_2, &casts),
+            TypeChange::NoRewrite
+        );
+        assert_eq!(
+            TypeChange::classify(&numeric_10_2, &numeric_12_3, &casts),
+            TypeChange::Rewrite
+        );
+    }
+}
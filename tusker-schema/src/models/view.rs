@@ -1,8 +1,9 @@
 use thiserror::Error;
 
 use crate::{
+    diff::{ChangeType, Diff, DiffSql},
     queries::{Class, Relkind},
-    sql::quote_ident,
+    sql::{comment_sql, owner_sql, quote_ident},
 };
 
 #[derive(Debug, Eq, PartialEq)]
@@ -12,6 +13,8 @@ pub struct View {
     pub kind: Relkind,
     pub materialized: bool,
     pub viewdef: String,
+    pub comment: Option<String>,
+    pub owner: Option<String>,
 }
 
 impl View {
@@ -28,6 +31,28 @@ impl View {
             self.viewdef,
         )
     }
+    fn object_type(&self) -> &'static str {
+        if self.materialized {
+            "MATERIALIZED VIEW"
+        } else {
+            "VIEW"
+        }
+    }
+    fn qualified_name(&self) -> String {
+        format!("{}.{}", quote_ident(&self.schema), quote_ident(&self.name))
+    }
+    pub fn comment_sql(&self) -> String {
+        comment_sql(
+            self.object_type(),
+            &self.qualified_name(),
+            self.comment.as_deref(),
+        )
+    }
+    pub fn owner_sql(&self) -> Option<String> {
+        self.owner
+            .as_ref()
+            .map(|owner| owner_sql(self.object_type(), &self.qualified_name(), owner))
+    }
 }
 
 impl TryFrom<Class> for View {
@@ -44,6 +69,8 @@ impl TryFrom<Class> for View {
             kind: cls.relkind,
             materialized,
             viewdef: cls.viewdef.unwrap(),
+            comment: cls.comment,
+            owner: Some(cls.owner),
         })
     }
 }
@@ -51,3 +78,20 @@ impl TryFrom<Class> for View {
 #[derive(Debug, Error)]
 #[error("Unsupported table for view: {0}")]
 pub struct InvalidRelkind(Relkind);
+
+impl DiffSql for Diff<'_, View> {
+    fn sql(&self) -> Vec<(ChangeType, String)> {
+        // FIXME creating, dropping and replacing views is not supported,
+        // yet. Only comment and owner changes of existing views are diffed.
+        let mut v = Vec::new();
+        for (a, b) in &self.a_and_b {
+            if a.comment != b.comment {
+                v.push((ChangeType::Comment, b.comment_sql()));
+            }
+            if a.owner != b.owner {
+                v.extend(b.owner_sql().map(|sql| (ChangeType::AlterOwner, sql)));
+            }
+        }
+        v
+    }
+}
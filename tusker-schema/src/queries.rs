@@ -1,4 +1,4 @@
-use std::fmt;
+use std::{collections::BTreeMap, fmt};
 
 use postgres_types::FromSql;
 use serde::{Deserialize, Serialize};
@@ -6,7 +6,14 @@ use thiserror::Error;
 use tokio_postgres::types::Json;
 use tusker_query::{FromRow, Query};
 
-use crate::models::{column::Column, constraint::ConstraintType};
+use crate::models::{
+    column::Column,
+    constraint::{ConstraintType, ForeignKeyAction},
+    event_trigger::EventTriggerEnabled,
+    operator_family::OperatorFamilyMember,
+    publication::PublicationTable,
+    table::{PartitionOf, TableName},
+};
 
 #[derive(Query)]
 #[query(sql="schemas", row=Schema)]
@@ -15,6 +22,8 @@ pub struct Schemas {}
 #[derive(Debug, FromRow)]
 pub struct Schema {
     pub name: String,
+    pub comment: Option<String>,
+    pub owner: String,
 }
 
 #[derive(Query)]
@@ -30,6 +39,16 @@ pub struct Class {
     pub relkind: Relkind,
     pub columns: Json<Vec<Column>>,
     pub viewdef: Option<String>,
+    pub comment: Option<String>,
+    pub owner: String,
+    pub unlogged: bool,
+    pub options: Vec<String>,
+    pub tablespace: Option<String>,
+    pub access_method: Option<String>,
+    pub inherits: Json<Vec<TableName>>,
+    pub partition_of: Option<Json<PartitionOf>>,
+    pub inherited_columns: Vec<String>,
+    pub inherited_constraints: Vec<String>,
 }
 
 #[derive(Debug, Eq, PartialEq, Serialize, Deserialize)]
@@ -75,6 +94,7 @@ impl FromSql<'_> for Relkind {
             b"v" => Self::View,
             b"m" => Self::MaterializedView,
             b"c" => Self::CompositeType,
+            b"f" => Self::ForeignTable,
             b"p" => Self::PartitionedTable,
             b"I" => Self::PartitionedIndex,
             x => Err(UnsupportedRelkind(x.to_owned()))?,
@@ -101,6 +121,14 @@ pub struct Constraint {
     pub name: String,
     pub r#type: ConstraintType,
     pub def: String,
+    pub validated: bool,
+    pub deferrable: bool,
+    pub initially_deferred: bool,
+    pub enforced: bool,
+    pub on_update: Option<ForeignKeyAction>,
+    pub on_delete: Option<ForeignKeyAction>,
+    pub index_definition: Option<String>,
+    pub comment: Option<String>,
 }
 
 #[derive(Query)]
@@ -147,6 +175,8 @@ pub struct RoutineRow {
     pub identity_arguments: String,
     pub definition: String,
     pub dependencies: Json<Vec<RoutineDependencyRow>>,
+    pub comment: Option<String>,
+    pub owner: String,
 }
 
 #[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
@@ -167,6 +197,8 @@ pub struct EnumRow {
     pub schema: String,
     pub name: String,
     pub labels: Vec<String>,
+    pub comment: Option<String>,
+    pub owner: String,
 }
 
 #[derive(Query)]
@@ -184,6 +216,8 @@ pub struct DomainRow {
     pub notnull: bool,
     pub constraint_names: Vec<String>,
     pub constraint_definitions: Vec<String>,
+    pub comment: Option<String>,
+    pub owner: String,
 }
 
 #[derive(Query)]
@@ -203,6 +237,7 @@ pub struct SequenceRow {
     pub increment_by: i64,
     pub cycle: bool,
     pub cache_size: i64,
+    pub owner: String,
 }
 
 #[derive(Query)]
@@ -216,6 +251,7 @@ pub struct ExtensionRow {
     pub schema: String,
     pub name: String,
     pub version: String,
+    pub objects: Vec<String>,
 }
 
 #[derive(Query)]
@@ -230,6 +266,150 @@ pub struct IndexRow {
     pub table_name: String,
     pub name: String,
     pub definition: String,
+    pub comment: Option<String>,
+}
+
+#[derive(Query)]
+#[query(sql = "statistics", row = StatisticsRow)]
+pub struct Statistics {
+    pub schema: String,
+}
+
+#[derive(Debug, FromRow)]
+pub struct StatisticsRow {
+    pub schema: String,
+    pub table_name: String,
+    pub name: String,
+    pub definition: String,
+    pub target: Option<i16>,
+    pub comment: Option<String>,
+    pub owner: String,
+}
+
+#[derive(Query)]
+#[query(sql = "operators", row = OperatorRow)]
+pub struct Operators {
+    pub schema: String,
+}
+
+#[derive(Debug, FromRow)]
+pub struct OperatorRow {
+    pub schema: String,
+    pub name: String,
+    pub left_type: Option<String>,
+    pub right_type: Option<String>,
+    pub function_schema: String,
+    pub function_name: String,
+    pub commutator: Option<String>,
+    pub negator: Option<String>,
+    pub restrict: Option<String>,
+    pub join: Option<String>,
+    pub hashes: bool,
+    pub merges: bool,
+    pub comment: Option<String>,
+    pub owner: String,
+}
+
+#[derive(Query)]
+#[query(sql = "operator_families", row = OperatorFamilyRow)]
+pub struct OperatorFamilies {
+    pub schema: String,
+}
+
+#[derive(Debug, FromRow)]
+pub struct OperatorFamilyRow {
+    pub schema: String,
+    pub name: String,
+    pub method: String,
+    pub members: Json<Vec<OperatorFamilyMember>>,
+    pub comment: Option<String>,
+    pub owner: String,
+}
+
+#[derive(Query)]
+#[query(sql = "operator_classes", row = OperatorClassRow)]
+pub struct OperatorClasses {
+    pub schema: String,
+}
+
+#[derive(Debug, FromRow)]
+pub struct OperatorClassRow {
+    pub schema: String,
+    pub name: String,
+    pub method: String,
+    pub family_schema: String,
+    pub family_name: String,
+    pub input_type: String,
+    pub default: bool,
+    pub storage: Option<String>,
+    pub members: Vec<String>,
+    pub comment: Option<String>,
+    pub owner: String,
+}
+
+#[derive(Query)]
+#[query(sql = "text_search_parsers", row = TextSearchParserRow)]
+pub struct TextSearchParsers {
+    pub schema: String,
+}
+
+#[derive(Debug, FromRow)]
+pub struct TextSearchParserRow {
+    pub schema: String,
+    pub name: String,
+    pub start: String,
+    pub gettoken: String,
+    pub end: String,
+    pub lextypes: String,
+    pub headline: Option<String>,
+    pub comment: Option<String>,
+}
+
+#[derive(Query)]
+#[query(sql = "text_search_templates", row = TextSearchTemplateRow)]
+pub struct TextSearchTemplates {
+    pub schema: String,
+}
+
+#[derive(Debug, FromRow)]
+pub struct TextSearchTemplateRow {
+    pub schema: String,
+    pub name: String,
+    pub init: Option<String>,
+    pub lexize: String,
+    pub comment: Option<String>,
+}
+
+#[derive(Query)]
+#[query(sql = "text_search_dictionaries", row = TextSearchDictionaryRow)]
+pub struct TextSearchDictionaries {
+    pub schema: String,
+}
+
+#[derive(Debug, FromRow)]
+pub struct TextSearchDictionaryRow {
+    pub schema: String,
+    pub name: String,
+    pub template: String,
+    pub options: Option<String>,
+    pub comment: Option<String>,
+    pub owner: String,
+}
+
+#[derive(Query)]
+#[query(sql = "text_search_configurations", row = TextSearchConfigurationRow)]
+pub struct TextSearchConfigurations {
+    pub schema: String,
+}
+
+#[derive(Debug, FromRow)]
+pub struct TextSearchConfigurationRow {
+    pub schema: String,
+    pub name: String,
+    pub parser: String,
+    pub mappings: Json<BTreeMap<String, Vec<String>>>,
+    pub comment: Option<String>,
+    pub owner: String,
 }
 
 #[derive(Query)]
@@ -246,3 +426,76 @@ pub struct TriggerRow {
     pub definition: String,
     pub enabled: String,
 }
+
+#[derive(Query)]
+#[query(sql = "publications", row = PublicationRow)]
+pub struct Publications {}
+
+#[derive(Debug, FromRow)]
+pub struct PublicationRow {
+    pub name: String,
+    pub owner: String,
+    pub all_tables: bool,
+    pub publish: Vec<String>,
+    pub publish_via_partition_root: bool,
+    pub tables: Json<Vec<PublicationTable>>,
+    pub schemas: Vec<String>,
+    pub comment: Option<String>,
+}
+
+#[derive(Query)]
+#[query(sql = "subscriptions", row = SubscriptionRow)]
+pub struct Subscriptions {}
+
+#[derive(Debug, FromRow)]
+pub struct SubscriptionRow {
+    pub name: String,
+    pub owner: String,
+    pub enabled: bool,
+    pub publications: Vec<String>,
+    pub comment: Option<String>,
+}
+
+#[derive(Query)]
+#[query(sql = "event_triggers", row = EventTriggerRow)]
+pub struct EventTriggers {}
+
+#[derive(Debug, FromRow)]
+pub struct EventTriggerRow {
+    pub name: String,
+    pub event: String,
+    pub tags: Vec<String>,
+    pub function_schema: String,
+    pub function_name: String,
+    pub enabled: EventTriggerEnabled,
+    pub owner: String,
+    pub comment: Option<String>,
+}
+
+#[derive(Query)]
+#[query(sql = "casts", row = CastRow)]
+pub struct Casts {}
+
+#[derive(Debug, FromRow)]
+pub struct CastRow {
+    pub source: String,
+    pub target: String,
+    pub function_schema: Option<String>,
+    pub function_name: Option<String>,
+    pub function_arguments: Option<String>,
+    pub method: String,
+    pub context: String,
+    pub comment: Option<String>,
+}
+
+#[derive(Query)]
+#[query(sql = "type_casts", row = TypeCastRow)]
+pub struct TypeCasts {}
+
+#[derive(Debug, FromRow)]
+pub struct TypeCastRow {
+    pub source: String,
+    pub target: String,
+    pub context: String,
+    pub method: String,
+}
@@ -27,9 +27,36 @@ pub fn quote_ident(ident: &str) -> String {
     format!("\"{}\"", ident.replace('"', "\"\""))
 }
 
+pub fn quote_literal(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "''"))
+}
+
+/// Generate a `COMMENT ON` statement. `object` is the object type
+/// (e.g. `TABLE`) and `name` the already quoted object name. A missing
+/// comment is rendered as `IS NULL` which removes the comment.
+pub fn comment_sql(object: &str, name: &str, comment: Option<&str>) -> String {
+    format!(
+        "COMMENT ON {} {} IS {};\n",
+        object,
+        name,
+        comment.map(quote_literal).unwrap_or_else(|| "NULL".into())
+    )
+}
+
+/// Generate an `ALTER ... OWNER TO` statement. `object` is the object
+/// type (e.g. `TABLE`) and `name` the already quoted object name.
+pub fn owner_sql(object: &str, name: &str, owner: &str) -> String {
+    format!(
+        "ALTER {} {} OWNER TO {};\n",
+        object,
+        name,
+        quote_ident(owner)
+    )
+}
+
 #[cfg(test)]
 mod tests {
-    use super::quote_ident;
+    use super::{comment_sql, quote_ident};
 
     #[test]
     fn quote_ident_doubles_quotes_but_leaves_backslashes() {
@@ -37,4 +64,16 @@ mod tests {
         assert_eq!(quote_ident("a\"b"), "\"a\"\"b\"");
         assert_eq!(quote_ident(r"a\b"), r#""a\b""#);
     }
+
+    #[test]
+    fn comment_sql_quotes_literals_and_renders_null() {
+        assert_eq!(
+            comment_sql("TABLE", "\"public\".\"a\"", Some("it's")),
+            "COMMENT ON TABLE \"public\".\"a\" IS 'it''s';\n"
+        );
+        assert_eq!(
+            comment_sql("SCHEMA", "\"public\"", None),
+            "COMMENT ON SCHEMA \"public\" IS NULL;\n"
+        );
+    }
 }
@@ -0,0 +1,351 @@
+use std::{collections::HashMap, fmt};
+
+use itertools::Itertools;
+
+use crate::{
+    diff::{Change, ChangeType, Diff, DiffOptions},
+    filter::ObjectKind,
+    models::table::Table,
+    InspectionDiff,
+};
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd)]
+pub enum Action {
+    Create,
+    Alter,
+    Drop,
+}
+
+impl Action {
+    pub fn sign(&self) -> char {
+        match self {
+            Self::Create => '+',
+            Self::Alter => '~',
+            Self::Drop => '-',
+        }
+    }
+}
+
+/// Object which differs between two inspections
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct SummaryLine {
+    pub action: Action,
+    pub kind: ObjectKind,
+    /// Qualified name of the object
+    pub name: String,
+    /// What changed. Only set for altered objects.
+    pub reasons: Vec<String>,
+}
+
+impl fmt::Display for SummaryLine {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {} {}", self.action.sign(), self.kind, self.name)?;
+        if !self.reasons.is_empty() {
+            write!(f, " {}", self.reasons.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+/// Effect of a statement on the object it belongs to
+fn action(change: &Change) -> (Action, &'static str) {
+    match change.change_type {
+        ChangeType::AlterOwner => (Action::Alter, "owner"),
+        ChangeType::Comment => (Action::Alter, "comment"),
+        ChangeType::Inherit | ChangeType::DropInherit if change.kind == ObjectKind::Table => {
+            (Action::Alter, "inheritance")
+        }
+        // Constraints which were inherited are dropped after `NO INHERIT`
+        ChangeType::DropInherit => (Action::Drop, ""),
+        ChangeType::DropTextSearchMapping => (Action::Alter, "definition"),
+        ChangeType::ValidateConstraint | ChangeType::ValidateConstraintOnline => {
+            (Action::Alter, "validation")
+        }
+        ChangeType::CreateSchema
+        | ChangeType::CreateExtension
+        | ChangeType::CreateSequence
+        | ChangeType::CreateType
+        | ChangeType::CreateRoutine
+        | ChangeType::CreateOperator
+        | ChangeType::CreateCast
+        | ChangeType::CreateOperatorFamily
+        | ChangeType::CreateOperatorClass
+        | ChangeType::CreateTextSearchParser
+        | ChangeType::CreateTextSearchTemplate
+        | ChangeType::CreateTextSearchDictionary
+        | ChangeType::CreateTextSearchConfiguration
+        | ChangeType::CreateTable
+        | ChangeType::CreateIndex
+        | ChangeType::CreateIndexConcurrently
+        | ChangeType::CreateStatistics
+        | ChangeType::CreateConstraintIndexConcurrently
+        | ChangeType::CreateConstraint(_)
+        | ChangeType::CreateTrigger
+        | ChangeType::CreatePublication
+        | ChangeType::CreateEventTrigger => (Action::Create, ""),
+        change_type if change_type.is_destructive() => (Action::Drop, ""),
+        _ => (Action::Alter, "definition"),
+    }
+}
+
+/// Summarize the changes of a single object. Objects which are dropped
+/// and created again are reported as recreated.
+fn summarize(kind: ObjectKind, name: String, changes: &[&Change]) -> SummaryLine {
+    let actions = changes.iter().map(|c| action(c)).collect::<Vec<_>>();
+    let creates = actions.iter().any(|(a, _)| *a == Action::Create);
+    let drops = actions.iter().any(|(a, _)| *a == Action::Drop);
+    let (action, reasons) = match (creates, drops) {
+        (true, true) => (Action::Alter, vec!["recreated".into()]),
+        (true, false) => (Action::Create, vec![]),
+        (false, true) => (Action::Drop, vec![]),
+        (false, false) => (
+            Action::Alter,
+            actions
+                .into_iter()
+                .map(|(_, reason)| reason.into())
+                .unique()
+                .collect(),
+        ),
+    };
+    SummaryLine {
+        action,
+        kind,
+        name,
+        reasons,
+    }
+}
+
+/// Altered tables are summarized column by column
+fn summarize_table(old: &Table, new: &Table) -> Vec<SummaryLine> {
+    let qualified = format!("{}.{}", new.schema, new.name);
+    let mut v = Vec::new();
+    let mut attributes = old.changed_attributes(new);
+    if Table::column_order_differs(old, new) {
+        attributes.push("column order");
+    }
+    if !attributes.is_empty() {
+        v.push(SummaryLine {
+            action: Action::Alter,
+            kind: ObjectKind::Table,
+            name: qualified.clone(),
+            reasons: attributes.into_iter().map(Into::into).collect(),
+        });
+    }
+    let columns = old.diff_columns(new);
+    let column = |action, name: &str, reasons: Vec<&str>| SummaryLine {
+        action,
+        kind: ObjectKind::Column,
+        name: format!("{}.{}", qualified, name),
+        reasons: reasons.into_iter().map(Into::into).collect(),
+    };
+    v.extend(
+        columns
+            .a_only
+            .iter()
+            .filter(|c| !new.inherited_columns.contains(&c.name))
+            .map(|c| column(Action::Drop, &c.name, vec![])),
+    );
+    v.extend(
+        columns
+            .a_and_b
+            .iter()
+            .filter(|(old, new)| old != new)
+            .map(|(old, new)| column(Action::Alter, &new.name, old.changed_attributes(new))),
+    );
+    v.extend(
+        columns
+            .b_only
+            .iter()
+            .filter(|c| !old.inherited_columns.contains(&c.name))
+            .map(|c| column(Action::Create, &c.name, vec![])),
+    );
+    v
+}
+
+impl InspectionDiff<'_> {
+    /// Objects which differ grouped by their kind. The summary is built
+    /// from the changes of the diff. Unlike the SQL of the diff this also
+    /// works for schemas which only exist on one side.
+    pub fn summary(&self) -> Vec<SummaryLine> {
+        let mut v = Vec::new();
+        for a in &self.schemas.a_only {
+            v.push(SummaryLine {
+                action: Action::Drop,
+                kind: ObjectKind::Schema,
+                name: a.name.clone(),
+                reasons: vec![],
+            });
+        }
+        for b in &self.schemas.b_only {
+            v.push(SummaryLine {
+                action: Action::Create,
+                kind: ObjectKind::Schema,
+                name: b.name.clone(),
+                reasons: vec![],
+            });
+        }
+        let schemas = Diff {
+            a_only: vec![],
+            a_and_b: self.schemas.a_and_b.clone(),
+            b_only: vec![],
+        };
+        let options = DiffOptions::default();
+        let mut changes = schemas.changes(&options);
+        changes.extend(self.global_changes(&options));
+        let objects = changes.iter().into_group_map_by(|c| {
+            let name = match &c.schema {
+                Some(schema) => format!("{}.{}", schema, c.name),
+                None => c.name.clone(),
+            };
+            (c.kind, name)
+        });
+        let mut altered_tables = HashMap::new();
+        for ((kind, name), changes) in objects {
+            let line = summarize(kind, name.clone(), &changes);
+            if kind == ObjectKind::Table && line.action == Action::Alter {
+                altered_tables.insert(name, line);
+            } else {
+                v.push(line);
+            }
+        }
+        // Altered tables are listed column by column. Column order drift
+        // is not part of the changes as it can't be migrated.
+        for (a, b) in &self.schemas.a_and_b {
+            for (old, new) in a.diff_tables(b).a_and_b {
+                let line = altered_tables.remove(&format!("{}.{}", b.name, new.name));
+                if line.is_some() || Table::column_order_differs(old, new) {
+                    let lines = summarize_table(old, new);
+                    match (lines.is_empty(), line) {
+                        (true, Some(line)) => v.push(line),
+                        _ => v.extend(lines),
+                    }
+                }
+            }
+        }
+        v.into_iter()
+            .sorted_by(|a, b| (a.kind, &a.name).cmp(&(b.kind, &b.name)))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        diff::{Change, ChangeType},
+        filter::ObjectKind,
+        models::{
+            column::{Column, Generated, Identity, Storage},
+            table::Table,
+        },
+        queries::Relkind,
+    };
+
+    use super::{summarize, summarize_table};
+
+    fn column(name: &str, r#type: &str) -> Column {
+        Column {
+            name: name.into(),
+            r#type: r#type.into(),
+            base_type: r#type.into(),
+            typmod: -1,
+            notnull: false,
+            identity: Identity::No,
+            generated: Generated::No,
+            default: None,
+            comment: None,
+            storage: Storage::Plain,
+            default_storage: Storage::Plain,
+            compression: None,
+            statistics: None,
+        }
+    }
+
+    fn table(name: &str, columns: Vec<Column>) -> Table {
+        Table {
+            schema: "public".into(),
+            name: name.into(),
+            kind: Relkind::OrdinaryTable,
+            columns,
+            comment: None,
+            owner: None,
+            unlogged: false,
+            options: vec![],
+            tablespace: None,
+            access_method: None,
+            inherits: vec![],
+            partition_of: None,
+            inherited_columns: vec![],
+            inherited_constraints: vec![],
+        }
+    }
+
+    #[test]
+    fn summarizes_tables_by_column() {
+        let old = table(
+            "bar",
+            vec![
+                column("id", "integer"),
+                column("baz", "integer"),
+                column("name", "text"),
+            ],
+        );
+        let new = Table {
+            unlogged: true,
+            ..table(
+                "bar",
+                vec![
+                    column("id", "bigint"),
+                    column("qux", "text"),
+                    column("name", "text"),
+                ],
+            )
+        };
+
+        assert_eq!(
+            summarize_table(&old, &new)
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>(),
+            vec![
+                "~ table public.bar persistence, column order",
+                "- column public.bar.baz",
+                "~ column public.bar.id type",
+                "+ column public.bar.qux",
+            ]
+        );
+    }
+
+    #[test]
+    fn summarizes_changes_of_an_object() {
+        let change = |change_type| Change {
+            kind: ObjectKind::Routine,
+            schema: Some("public".into()),
+            name: "f()".into(),
+            change_type,
+            destructive: change_type.is_destructive(),
+            sql: String::new(),
+        };
+        let line = |change_types: &[ChangeType]| {
+            let changes = change_types.iter().map(|t| change(*t)).collect::<Vec<_>>();
+            summarize(
+                ObjectKind::Routine,
+                "public.f()".into(),
+                &changes.iter().collect::<Vec<_>>(),
+            )
+            .to_string()
+        };
+        assert_eq!(
+            line(&[ChangeType::CreateRoutine, ChangeType::Comment]),
+            "+ routine public.f()"
+        );
+        assert_eq!(line(&[ChangeType::DropRoutine]), "- routine public.f()");
+        assert_eq!(
+            line(&[ChangeType::DropRoutine, ChangeType::CreateRoutine]),
+            "~ routine public.f() recreated"
+        );
+        assert_eq!(
+            line(&[ChangeType::AlterOwner, ChangeType::Comment]),
+            "~ routine public.f() owner, comment"
+        );
+    }
+}
@@ -0,0 +1,4 @@
+CREATE TABLE person (
+    id integer NOT NULL,
+    name text NOT NULL
+);
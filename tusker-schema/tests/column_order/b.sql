@@ -0,0 +1,5 @@
+CREATE TABLE person (
+    id integer NOT NULL,
+    email text,
+    name text NOT NULL
+);
@@ -0,0 +1,2 @@
+ALTER TABLE "public"."person"
+    DROP COLUMN "email";
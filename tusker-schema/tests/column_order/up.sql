@@ -0,0 +1,2 @@
+ALTER TABLE "public"."person"
+    ADD COLUMN "email" text;
@@ -0,0 +1,6 @@
+CREATE TABLE "public"."documents" (
+    "id" bigint,
+    "title" text,
+    "body" jsonb
+);
+ALTER TABLE "public"."documents" ALTER COLUMN "title" SET STATISTICS 200;
@@ -0,0 +1,17 @@
+CREATE TABLE "public"."documents" (
+    "id" bigint,
+    "title" text,
+    "body" jsonb,
+    "raw" bytea
+);
+ALTER TABLE "public"."documents"
+    ALTER COLUMN "id" SET STATISTICS 1000,
+    ALTER COLUMN "body" SET STORAGE EXTERNAL,
+    ALTER COLUMN "body" SET COMPRESSION lz4,
+    ALTER COLUMN "raw" SET STORAGE MAIN;
+
+CREATE TABLE "public"."attachments" (
+    "id" bigint,
+    "data" bytea COMPRESSION lz4
+);
+ALTER TABLE "public"."attachments" ALTER COLUMN "data" SET STORAGE EXTERNAL;
@@ -0,0 +1,8 @@
+DROP TABLE "public"."attachments";
+
+ALTER TABLE "public"."documents"
+    DROP COLUMN "raw",
+    ALTER COLUMN "id" SET STATISTICS -1,
+    ALTER COLUMN "title" SET STATISTICS 200,
+    ALTER COLUMN "body" SET STORAGE EXTENDED,
+    ALTER COLUMN "body" SET COMPRESSION DEFAULT;
@@ -0,0 +1,16 @@
+ALTER TABLE "public"."documents"
+    ALTER COLUMN "id" SET STATISTICS 1000,
+    ALTER COLUMN "title" SET STATISTICS -1,
+    ALTER COLUMN "body" SET STORAGE EXTERNAL,
+    ALTER COLUMN "body" SET COMPRESSION lz4,
+    ADD COLUMN "raw" bytea,
+    ALTER COLUMN "raw" SET STORAGE MAIN;
+
+CREATE TABLE "public"."attachments" (
+    "id" bigint,
+    "data" bytea
+);
+
+ALTER TABLE "public"."attachments"
+    ALTER COLUMN "data" SET STORAGE EXTERNAL,
+    ALTER COLUMN "data" SET COMPRESSION lz4;
@@ -0,0 +1,6 @@
+CREATE TABLE product (
+    id integer NOT NULL,
+    name character varying(20) NOT NULL,
+    code text,
+    price numeric(10,2)
+);
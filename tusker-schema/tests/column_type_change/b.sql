@@ -0,0 +1,6 @@
+CREATE TABLE product (
+    id bigint NOT NULL,
+    name text NOT NULL,
+    code integer,
+    price numeric(12,2)
+);
@@ -0,0 +1,9 @@
+ALTER TABLE "public"."product"
+    -- WARNING: changing the type of column "id" from bigint to integer rewrites the table and its indexes while holding an ACCESS EXCLUSIVE lock
+    ALTER COLUMN "id" TYPE integer,
+    -- WARNING: changing the type of column "name" from text to character varying(20) rewrites the table and its indexes while holding an ACCESS EXCLUSIVE lock
+    ALTER COLUMN "name" TYPE character varying(20),
+    -- WARNING: changing the type of column "code" from integer to text rewrites the table and its indexes while holding an ACCESS EXCLUSIVE lock
+    ALTER COLUMN "code" TYPE text,
+    -- WARNING: changing the type of column "price" from numeric(12,2) to numeric(10,2) rewrites the table and its indexes while holding an ACCESS EXCLUSIVE lock
+    ALTER COLUMN "price" TYPE numeric(10,2);
@@ -0,0 +1,7 @@
+ALTER TABLE "public"."product"
+    -- WARNING: changing the type of column "id" from integer to bigint rewrites the table and its indexes while holding an ACCESS EXCLUSIVE lock
+    ALTER COLUMN "id" TYPE bigint,
+    ALTER COLUMN "name" TYPE text,
+    -- WARNING: changing the type of column "code" from text to integer rewrites the table and its indexes while holding an ACCESS EXCLUSIVE lock
+    ALTER COLUMN "code" TYPE integer USING "code"::integer,
+    ALTER COLUMN "price" TYPE numeric(12,2);
@@ -0,0 +1,16 @@
+CREATE TYPE "public"."mood" AS ENUM ('sad', 'happy');
+CREATE DOMAIN "public"."positive" AS integer CHECK (VALUE > 0);
+
+CREATE TABLE "public"."a" (
+    "id" bigint PRIMARY KEY,
+    "name" text NOT NULL,
+    "mood" "public"."mood"
+);
+COMMENT ON TABLE "public"."a" IS 'Old table comment';
+COMMENT ON COLUMN "public"."a"."name" IS 'Old column comment';
+
+CREATE INDEX "a_name_idx" ON "public"."a" ("name");
+
+CREATE VIEW "public"."a_names" AS SELECT "name" FROM "public"."a";
+
+CREATE FUNCTION "public"."one"() RETURNS integer LANGUAGE sql AS $$ SELECT 1 $$;
@@ -0,0 +1,24 @@
+CREATE TYPE "public"."mood" AS ENUM ('sad', 'happy');
+COMMENT ON TYPE "public"."mood" IS 'How it''s going';
+CREATE DOMAIN "public"."positive" AS integer CHECK (VALUE > 0);
+COMMENT ON DOMAIN "public"."positive" IS 'Strictly positive integer';
+
+CREATE TABLE "public"."a" (
+    "id" bigint PRIMARY KEY,
+    "name" text NOT NULL,
+    "mood" "public"."mood"
+);
+COMMENT ON COLUMN "public"."a"."id" IS 'Primary key';
+COMMENT ON COLUMN "public"."a"."name" IS 'New column comment';
+COMMENT ON CONSTRAINT "a_pkey" ON "public"."a" IS 'Primary key constraint';
+
+CREATE INDEX "a_name_idx" ON "public"."a" ("name");
+COMMENT ON INDEX "public"."a_name_idx" IS 'Lookup by name';
+
+CREATE VIEW "public"."a_names" AS SELECT "name" FROM "public"."a";
+COMMENT ON VIEW "public"."a_names" IS 'All names';
+
+CREATE FUNCTION "public"."one"() RETURNS integer LANGUAGE sql AS $$ SELECT 1 $$;
+COMMENT ON FUNCTION "public"."one"() IS 'Returns one';
+
+COMMENT ON SCHEMA "public" IS 'Application schema';
@@ -0,0 +1,19 @@
+COMMENT ON SCHEMA "public" IS 'standard public schema';
+
+COMMENT ON TYPE "public"."mood" IS NULL;
+
+COMMENT ON DOMAIN "public"."positive" IS NULL;
+
+COMMENT ON FUNCTION "public"."one"() IS NULL;
+
+COMMENT ON TABLE "public"."a" IS 'Old table comment';
+
+COMMENT ON COLUMN "public"."a"."id" IS NULL;
+
+COMMENT ON COLUMN "public"."a"."name" IS 'Old column comment';
+
+COMMENT ON VIEW "public"."a_names" IS NULL;
+
+COMMENT ON INDEX "public"."a_name_idx" IS NULL;
+
+COMMENT ON CONSTRAINT "a_pkey" ON "public"."a" IS NULL;
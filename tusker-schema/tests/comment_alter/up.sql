@@ -0,0 +1,19 @@
+COMMENT ON SCHEMA "public" IS 'Application schema';
+
+COMMENT ON TYPE "public"."mood" IS 'How it''s going';
+
+COMMENT ON DOMAIN "public"."positive" IS 'Strictly positive integer';
+
+COMMENT ON FUNCTION "public"."one"() IS 'Returns one';
+
+COMMENT ON TABLE "public"."a" IS NULL;
+
+COMMENT ON COLUMN "public"."a"."id" IS 'Primary key';
+
+COMMENT ON COLUMN "public"."a"."name" IS 'New column comment';
+
+COMMENT ON VIEW "public"."a_names" IS 'All names';
+
+COMMENT ON INDEX "public"."a_name_idx" IS 'Lookup by name';
+
+COMMENT ON CONSTRAINT "a_pkey" ON "public"."a" IS 'Primary key constraint';
@@ -0,0 +1,15 @@
+CREATE TABLE "public"."author" (
+    "id" integer NOT NULL
+);
+
+ALTER TABLE "public"."author" ADD CONSTRAINT "author_pkey" PRIMARY KEY (id);
+
+CREATE TABLE "public"."book" (
+    "id" integer NOT NULL,
+    "author_id" integer NOT NULL,
+    "isbn" text NOT NULL
+);
+
+ALTER TABLE "public"."book" ADD CONSTRAINT "book_isbn_key" UNIQUE (isbn) DEFERRABLE;
+
+ALTER TABLE "public"."book" ADD CONSTRAINT "book_author_id_fkey" FOREIGN KEY (author_id) REFERENCES author(id) ON DELETE CASCADE DEFERRABLE INITIALLY DEFERRED;
@@ -0,0 +1,5 @@
+ALTER TABLE "public"."book" DROP CONSTRAINT "book_isbn_key";
+
+ALTER TABLE "public"."book" ADD CONSTRAINT "book_isbn_key" UNIQUE (isbn) DEFERRABLE;
+
+ALTER TABLE "public"."book" ALTER CONSTRAINT "book_author_id_fkey" DEFERRABLE INITIALLY DEFERRED;
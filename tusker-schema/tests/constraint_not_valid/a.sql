@@ -0,0 +1,6 @@
+CREATE TABLE "public"."order" (
+    "id" integer NOT NULL,
+    "total" integer NOT NULL
+);
+
+ALTER TABLE "public"."order" ADD CONSTRAINT "order_total_check" CHECK ((total > 0)) NOT VALID;
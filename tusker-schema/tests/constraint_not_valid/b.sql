@@ -0,0 +1,8 @@
+CREATE TABLE "public"."order" (
+    "id" integer NOT NULL,
+    "total" integer NOT NULL
+);
+
+ALTER TABLE "public"."order" ADD CONSTRAINT "order_total_check" CHECK ((total > 0));
+
+ALTER TABLE "public"."order" ADD CONSTRAINT "order_id_check" CHECK ((id > 0)) NOT VALID;
@@ -0,0 +1 @@
+ALTER TABLE "public"."order" DROP CONSTRAINT "order_id_check";
@@ -0,0 +1,3 @@
+ALTER TABLE "public"."order" ADD CONSTRAINT "order_id_check" CHECK ((id > 0)) NOT VALID;
+
+ALTER TABLE "public"."order" VALIDATE CONSTRAINT "order_total_check";
@@ -8,7 +8,12 @@ use rstest::rstest;
 use tokio::fs;
 use tokio::task::JoinHandle;
 use tokio_postgres::{Client, Config, NoTls};
-use tusker_schema::{diff::DiffSql, inspect, models::schema::join_sql, Inspection};
+use tusker_schema::{
+    diff::{DiffOptions, DiffSql},
+    inspect, inspect_type_casts,
+    models::schema::join_sql,
+    Inspection, InspectionDiff,
+};
 
 static NEXT_DB_ID: AtomicU64 = AtomicU64::new(0);
 
@@ -68,7 +73,7 @@ impl TestDatabase {
 async fn inspect_sql(client: &mut Client, sql: &str) -> Result<Inspection> {
     let txn = client.transaction().await.unwrap();
     txn.simple_query(sql).await?;
-    let inspection = inspect(&txn.client()).await.unwrap();
+    let inspection = inspect(txn.client()).await.unwrap();
     txn.rollback().await?;
     Ok(inspection)
 }
@@ -83,7 +88,7 @@ async fn apply_and_inspect(
     if !diff_sql.trim().is_empty() {
         txn.simple_query(diff_sql).await?;
     }
-    let inspection = inspect(&txn.client()).await.unwrap();
+    let inspection = inspect(txn.client()).await.unwrap();
     txn.rollback().await?;
     Ok(inspection)
 }
@@ -96,6 +101,16 @@ fn is_runnable_migration(sql: &str) -> bool {
         && !sql.contains("RAISE EXCEPTION 'Unsupported schema change for table")
 }
 
+/// Join the SQL of all changes which must be the same as the output of
+/// `join_sql`.
+fn changes_sql(diff: &InspectionDiff, options: &DiffOptions) -> String {
+    diff.changes(options)
+        .into_iter()
+        .map(|change| change.sql)
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
 /*
 #[tokio::test]
 async fn test_basic() {
@@ -185,14 +200,21 @@ async fn diff(
     let a = inspect_sql(client, &a_sql).await.unwrap();
     let b = inspect_sql(client, &b_sql).await.unwrap();
 
+    let options = DiffOptions {
+        type_casts: inspect_type_casts(client).await.unwrap(),
+        ..Default::default()
+    };
+
     // test up migration
     let up_diff = a.diff(&b);
-    let up_diff_sql = join_sql(up_diff.sql());
+    let up_diff_sql = join_sql(up_diff.sql_with(&options));
     assert_eq!(up_diff_sql, up_sql);
+    assert_eq!(changes_sql(&up_diff, &options), up_sql);
 
     let down_diff = b.diff(&a);
-    let down_diff_sql = join_sql(down_diff.sql());
+    let down_diff_sql = join_sql(down_diff.sql_with(&options));
     assert_eq!(down_diff_sql, down_sql);
+    assert_eq!(changes_sql(&down_diff, &options), down_sql);
 
     let a_a_diff = a.diff(&a);
     assert!(a_a_diff.sql().is_empty());
@@ -0,0 +1,21 @@
+CREATE FUNCTION "public"."log_ddl"() RETURNS event_trigger LANGUAGE plpgsql AS $$
+BEGIN
+    RAISE NOTICE 'DDL: %', tg_tag;
+END;
+$$;
+
+CREATE FUNCTION "public"."guard_ddl"() RETURNS event_trigger LANGUAGE plpgsql AS $$
+BEGIN
+    RAISE NOTICE 'guard: %', tg_tag;
+END;
+$$;
+
+CREATE EVENT TRIGGER "log_ddl" ON ddl_command_end
+    WHEN TAG IN ('CREATE TABLE')
+    EXECUTE FUNCTION "public"."log_ddl"();
+
+CREATE EVENT TRIGGER "guard_ddl" ON ddl_command_start
+    EXECUTE FUNCTION "public"."guard_ddl"();
+
+CREATE EVENT TRIGGER "old_ddl" ON sql_drop
+    EXECUTE FUNCTION "public"."guard_ddl"();
@@ -0,0 +1,22 @@
+CREATE FUNCTION "public"."log_ddl"() RETURNS event_trigger LANGUAGE plpgsql AS $$
+BEGIN
+    RAISE NOTICE 'DDL command: %', tg_tag;
+END;
+$$;
+
+CREATE FUNCTION "public"."guard_ddl"() RETURNS event_trigger LANGUAGE plpgsql AS $$
+BEGIN
+    RAISE NOTICE 'guard: %', tg_tag;
+END;
+$$;
+
+CREATE EVENT TRIGGER "log_ddl" ON ddl_command_end
+    WHEN TAG IN ('CREATE TABLE', 'ALTER TABLE')
+    EXECUTE FUNCTION "public"."log_ddl"();
+
+CREATE EVENT TRIGGER "guard_ddl" ON ddl_command_start
+    EXECUTE FUNCTION "public"."guard_ddl"();
+ALTER EVENT TRIGGER "guard_ddl" DISABLE;
+
+CREATE EVENT TRIGGER "new_ddl" ON ddl_command_end
+    EXECUTE FUNCTION "public"."log_ddl"();
@@ -0,0 +1,23 @@
+DROP EVENT TRIGGER "old_ddl";
+
+DROP EVENT TRIGGER "log_ddl";
+
+DROP FUNCTION "public"."log_ddl"();
+
+CREATE OR REPLACE FUNCTION public.log_ddl()
+ RETURNS event_trigger
+ LANGUAGE plpgsql
+AS $function$
+BEGIN
+    RAISE NOTICE 'DDL command: %', tg_tag;
+END;
+$function$;
+
+CREATE EVENT TRIGGER "new_ddl" ON "ddl_command_end"
+    EXECUTE FUNCTION "public"."log_ddl"();
+
+CREATE EVENT TRIGGER "log_ddl" ON "ddl_command_end"
+    WHEN TAG IN ('CREATE TABLE', 'ALTER TABLE')
+    EXECUTE FUNCTION "public"."log_ddl"();
+
+ALTER EVENT TRIGGER "guard_ddl" DISABLE;
@@ -0,0 +1,9 @@
+DROP INDEX "public"."document_title_trgm_idx";
+
+DROP TABLE "public"."document";
+
+DROP EXTENSION IF EXISTS "dict_int";
+
+DROP EXTENSION IF EXISTS "hstore";
+
+DROP EXTENSION IF EXISTS "pg_trgm";
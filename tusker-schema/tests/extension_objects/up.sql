@@ -0,0 +1,13 @@
+CREATE EXTENSION IF NOT EXISTS "dict_int" WITH SCHEMA "public" VERSION '1.0';
+
+CREATE EXTENSION IF NOT EXISTS "hstore" WITH SCHEMA "public" VERSION '1.8';
+
+CREATE EXTENSION IF NOT EXISTS "pg_trgm" WITH SCHEMA "public" VERSION '1.6';
+
+CREATE TABLE "public"."document" (
+    "id" integer NOT NULL,
+    "title" text NOT NULL,
+    "attributes" hstore
+);
+
+CREATE INDEX document_title_trgm_idx ON public.document USING gin (title gin_trgm_ops);
@@ -0,0 +1,40 @@
+CREATE TYPE "public"."mood" AS ENUM ('sad', 'ok');
+
+CREATE FUNCTION "public"."mood_text"("public"."mood") RETURNS text LANGUAGE sql IMMUTABLE AS 'SELECT $1::text';
+
+CREATE FUNCTION "public"."int_eq_abs"(integer, integer) RETURNS boolean LANGUAGE sql IMMUTABLE AS 'SELECT abs($1) = abs($2)';
+
+CREATE FUNCTION "public"."int_lt_abs"(integer, integer) RETURNS boolean LANGUAGE sql IMMUTABLE AS 'SELECT abs($1) < abs($2)';
+
+CREATE FUNCTION "public"."int_cmp_abs"(integer, integer) RETURNS integer LANGUAGE sql IMMUTABLE AS 'SELECT CASE WHEN abs($1) < abs($2) THEN -1 WHEN abs($1) > abs($2) THEN 1 ELSE 0 END';
+
+CREATE FUNCTION "public"."int_gt_abs"(integer, integer) RETURNS boolean LANGUAGE sql IMMUTABLE AS 'SELECT abs($1) > abs($2)';
+
+CREATE OPERATOR "public".=== (FUNCTION = "public"."int_eq_abs", LEFTARG = integer, RIGHTARG = integer, COMMUTATOR = ===, RESTRICT = eqsel, JOIN = eqjoinsel);
+
+CREATE OPERATOR "public".<<< (FUNCTION = "public"."int_lt_abs", LEFTARG = integer, RIGHTARG = integer);
+
+CREATE OPERATOR "public".>>> (FUNCTION = "public"."int_gt_abs", LEFTARG = integer, RIGHTARG = integer);
+
+CREATE OPERATOR FAMILY "public"."abs_family" USING btree;
+ALTER OPERATOR FAMILY "public"."abs_family" USING btree ADD OPERATOR 1 "public".<<< (integer, integer), OPERATOR 3 "public".=== (integer, integer);
+
+CREATE OPERATOR CLASS "public"."abs_ops" FOR TYPE integer USING btree AS
+    OPERATOR 1 "public".<<<,
+    OPERATOR 3 "public".===,
+    FUNCTION 1 "public"."int_cmp_abs"(integer, integer);
+
+CREATE OPERATOR CLASS "public"."abs_desc_ops" FOR TYPE integer USING btree AS
+    OPERATOR 1 "public".>>>,
+    OPERATOR 3 "public".===,
+    FUNCTION 1 "public"."int_cmp_abs"(integer, integer);
+
+CREATE CAST ("public"."mood" AS text) WITH FUNCTION "public"."mood_text"("public"."mood") AS IMPLICIT;
+
+CREATE TABLE "public"."numbers" (
+    "value" integer
+);
+
+CREATE INDEX "numbers_value_abs" ON "public"."numbers" ("value" "public"."abs_ops");
+
+CREATE INDEX "numbers_value_abs_desc" ON "public"."numbers" ("value" "public"."abs_desc_ops");
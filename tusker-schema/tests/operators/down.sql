@@ -0,0 +1,24 @@
+DROP INDEX "public"."numbers_value_abs_desc";
+
+DROP OPERATOR CLASS "public"."abs_desc_ops" USING "btree";
+
+DROP OPERATOR FAMILY "public"."abs_desc_ops" USING "btree";
+
+DROP OPERATOR "public".>>> (integer, integer);
+
+DROP CAST (mood AS text);
+
+DROP FUNCTION "public"."int_gt_abs"(integer, integer);
+
+CREATE OPERATOR "public".!!! (
+    FUNCTION = "public"."int_lt_abs",
+    LEFTARG = integer,
+    RIGHTARG = integer
+);
+
+ALTER OPERATOR "public".=== (integer, integer) SET (RESTRICT = NONE, JOIN = NONE);
+
+CREATE CAST (mood AS text) WITH FUNCTION "public"."mood_text"(mood) AS ASSIGNMENT;
+
+ALTER OPERATOR FAMILY "public"."abs_family" USING "btree" DROP
+    OPERATOR 3 (integer, integer);
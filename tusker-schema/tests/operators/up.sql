@@ -0,0 +1,31 @@
+DROP OPERATOR "public".!!! (integer, integer);
+
+DROP CAST (mood AS text);
+
+CREATE OR REPLACE FUNCTION public.int_gt_abs(integer, integer)
+ RETURNS boolean
+ LANGUAGE sql
+ IMMUTABLE
+AS $function$SELECT abs($1) > abs($2)$function$;
+
+CREATE OPERATOR "public".>>> (
+    FUNCTION = "public"."int_gt_abs",
+    LEFTARG = integer,
+    RIGHTARG = integer
+);
+
+ALTER OPERATOR "public".=== (integer, integer) SET (RESTRICT = eqsel, JOIN = eqjoinsel);
+
+CREATE CAST (mood AS text) WITH FUNCTION "public"."mood_text"(mood) AS IMPLICIT;
+
+CREATE OPERATOR FAMILY "public"."abs_desc_ops" USING "btree";
+
+ALTER OPERATOR FAMILY "public"."abs_family" USING "btree" ADD
+    OPERATOR 3 public.===(integer, integer);
+
+CREATE OPERATOR CLASS "public"."abs_desc_ops" FOR TYPE integer USING "btree" FAMILY "public"."abs_desc_ops" AS
+    OPERATOR 1 public.>>>(integer, integer),
+    OPERATOR 3 public.===(integer, integer),
+    FUNCTION 1 (integer, integer) public.int_cmp_abs(integer, integer);
+
+CREATE INDEX numbers_value_abs_desc ON public.numbers USING btree (value abs_desc_ops);
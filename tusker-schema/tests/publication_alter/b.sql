@@ -0,0 +1,17 @@
+CREATE TABLE "public"."orders" (
+    "id" bigint PRIMARY KEY,
+    "total" numeric NOT NULL,
+    "note" text
+);
+
+CREATE TABLE "public"."refunds" (
+    "id" bigint PRIMARY KEY
+);
+
+CREATE TABLE "public"."invoices" (
+    "id" bigint PRIMARY KEY
+);
+
+CREATE PUBLICATION "analytics" FOR TABLE "public"."orders" ("id", "total") WHERE (total > 0), TABLE "public"."invoices" WITH (publish = 'insert, update');
+
+CREATE PUBLICATION "everything" FOR TABLES IN SCHEMA "public";
@@ -0,0 +1,9 @@
+DROP PUBLICATION "everything";
+
+ALTER PUBLICATION "analytics" DROP TABLE "public"."invoices", TABLE "public"."orders";
+
+CREATE PUBLICATION "legacy" FOR TABLE "public"."invoices";
+
+ALTER PUBLICATION "analytics" ADD TABLE "public"."orders" ("id", "total"), TABLE "public"."refunds";
+
+ALTER PUBLICATION "analytics" SET (publish = 'insert, update, delete, truncate', publish_via_partition_root = false);
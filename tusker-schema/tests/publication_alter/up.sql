@@ -0,0 +1,9 @@
+DROP PUBLICATION "legacy";
+
+ALTER PUBLICATION "analytics" DROP TABLE "public"."refunds", TABLE "public"."orders";
+
+CREATE PUBLICATION "everything" FOR TABLES IN SCHEMA "public";
+
+ALTER PUBLICATION "analytics" ADD TABLE "public"."orders" ("id", "total") WHERE ((total > (0)::numeric)), TABLE "public"."invoices";
+
+ALTER PUBLICATION "analytics" SET (publish = 'insert, update', publish_via_partition_root = false);
@@ -0,0 +1,12 @@
+CREATE TABLE "public"."addresses" (
+    "zip" text,
+    "city" text,
+    "street" text
+);
+
+CREATE STATISTICS "public"."addresses_zip_city" (dependencies) ON "zip", "city" FROM "public"."addresses";
+
+CREATE STATISTICS "public"."addresses_city_street" (ndistinct) ON "city", "street" FROM "public"."addresses";
+ALTER STATISTICS "public"."addresses_city_street" SET STATISTICS 500;
+
+CREATE STATISTICS "public"."addresses_old" (mcv) ON "zip", "street" FROM "public"."addresses";
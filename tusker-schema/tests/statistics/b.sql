@@ -0,0 +1,11 @@
+CREATE TABLE "public"."addresses" (
+    "zip" text,
+    "city" text,
+    "street" text
+);
+
+CREATE STATISTICS "public"."addresses_zip_city" (dependencies, mcv) ON "zip", "city" FROM "public"."addresses";
+
+CREATE STATISTICS "public"."addresses_city_street" (ndistinct) ON "city", "street" FROM "public"."addresses";
+
+CREATE STATISTICS "public"."addresses_lower_city" ON lower("city"), "zip" FROM "public"."addresses";
@@ -0,0 +1,9 @@
+DROP STATISTICS "public"."addresses_lower_city";
+
+DROP STATISTICS "public"."addresses_zip_city";
+
+CREATE STATISTICS public.addresses_zip_city (dependencies) ON zip, city FROM addresses;
+
+CREATE STATISTICS public.addresses_old (mcv) ON zip, street FROM addresses;
+
+ALTER STATISTICS "public"."addresses_city_street" SET STATISTICS 500;
@@ -0,0 +1,9 @@
+DROP STATISTICS "public"."addresses_old";
+
+DROP STATISTICS "public"."addresses_zip_city";
+
+CREATE STATISTICS public.addresses_zip_city (dependencies, mcv) ON zip, city FROM addresses;
+
+CREATE STATISTICS public.addresses_lower_city ON zip, lower(city) FROM addresses;
+
+ALTER STATISTICS "public"."addresses_city_street" SET STATISTICS -1;
@@ -0,0 +1,16 @@
+CREATE TABLE "public"."z_base" (
+    "id" bigint NOT NULL,
+    "created_at" timestamptz NOT NULL DEFAULT now(),
+    CHECK ("id" > 0)
+);
+
+CREATE TABLE "public"."a_log" (
+    "message" text
+) INHERITS ("public"."z_base");
+
+CREATE TABLE "public"."m_audit" (
+    "id" bigint NOT NULL,
+    "created_at" timestamptz NOT NULL DEFAULT now(),
+    "action" text,
+    CONSTRAINT "z_base_id_check" CHECK ("id" > 0)
+);
@@ -0,0 +1,29 @@
+CREATE TABLE "public"."z_base" (
+    "id" bigint NOT NULL,
+    "created_at" timestamptz NOT NULL DEFAULT now(),
+    CHECK ("id" > 0)
+);
+
+CREATE TABLE "public"."a_log" (
+    "id" bigint NOT NULL,
+    "created_at" timestamptz NOT NULL DEFAULT now(),
+    "message" text,
+    CONSTRAINT "z_base_id_check" CHECK ("id" > 0)
+);
+
+CREATE TABLE "public"."m_audit" (
+    "id" bigint NOT NULL,
+    "created_at" timestamptz NOT NULL DEFAULT now(),
+    "action" text,
+    CONSTRAINT "z_base_id_check" CHECK ("id" > 0)
+);
+ALTER TABLE "public"."m_audit" INHERIT "public"."z_base";
+
+CREATE TABLE "public"."y_parent" (
+    "id" bigint
+);
+
+CREATE TABLE "public"."b_child" (
+    "id" bigint DEFAULT 1,
+    "name" text
+) INHERITS ("public"."y_parent");
@@ -0,0 +1,7 @@
+ALTER TABLE "public"."m_audit" NO INHERIT "public"."z_base";
+
+DROP TABLE "public"."b_child";
+
+DROP TABLE "public"."y_parent";
+
+ALTER TABLE "public"."a_log" INHERIT "public"."z_base";
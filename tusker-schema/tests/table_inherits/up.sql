@@ -0,0 +1,12 @@
+ALTER TABLE "public"."a_log" NO INHERIT "public"."z_base";
+
+CREATE TABLE "public"."y_parent" (
+    "id" bigint
+);
+
+CREATE TABLE "public"."b_child" (
+    "id" bigint DEFAULT 1,
+    "name" text
+) INHERITS ("public"."y_parent");
+
+ALTER TABLE "public"."m_audit" INHERIT "public"."z_base";
@@ -0,0 +1,4 @@
+CREATE TABLE "public"."m" (
+    "id" bigint NOT NULL,
+    "created_at" date NOT NULL
+) PARTITION BY RANGE ("created_at");
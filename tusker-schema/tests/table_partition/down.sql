@@ -0,0 +1 @@
+DROP TABLE "public"."m_2024";
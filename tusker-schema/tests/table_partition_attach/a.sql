@@ -0,0 +1,9 @@
+CREATE TABLE "public"."m" (
+    "id" bigint NOT NULL,
+    "created_at" date NOT NULL
+) PARTITION BY RANGE ("created_at");
+
+CREATE TABLE "public"."m_2024" (
+    "id" bigint NOT NULL,
+    "created_at" date NOT NULL
+);
@@ -0,0 +1,7 @@
+CREATE TABLE "public"."m" (
+    "id" bigint NOT NULL,
+    "created_at" date NOT NULL
+) PARTITION BY RANGE ("created_at");
+
+CREATE TABLE "public"."m_2024" PARTITION OF "public"."m"
+    FOR VALUES FROM ('2024-01-01') TO ('2025-01-01');
@@ -0,0 +1 @@
+ALTER TABLE "public"."m" DETACH PARTITION "public"."m_2024";
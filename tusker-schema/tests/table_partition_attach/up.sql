@@ -0,0 +1 @@
+ALTER TABLE "public"."m" ATTACH PARTITION "public"."m_2024" FOR VALUES FROM ('2024-01-01') TO ('2025-01-01');
@@ -0,0 +1,4 @@
+CREATE TABLE "public"."events" (
+    "id" bigint,
+    "payload" jsonb
+) WITH (fillfactor = 70, autovacuum_enabled = false, toast.autovacuum_enabled = false);
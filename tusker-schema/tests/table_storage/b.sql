@@ -0,0 +1,10 @@
+CREATE UNLOGGED TABLE "public"."events" (
+    "id" bigint,
+    "payload" jsonb,
+    "created_at" timestamptz
+) WITH (fillfactor = 50, autovacuum_vacuum_scale_factor = 0.01);
+
+CREATE UNLOGGED TABLE "public"."cache" (
+    "key" text,
+    "value" text
+) WITH (fillfactor = 90, toast.autovacuum_enabled = false);
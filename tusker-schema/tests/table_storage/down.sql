@@ -0,0 +1,9 @@
+DROP TABLE "public"."cache";
+
+ALTER TABLE "public"."events"
+    SET LOGGED,
+    RESET (autovacuum_vacuum_scale_factor),
+    SET (fillfactor=70, autovacuum_enabled=false, toast.autovacuum_enabled=false);
+
+ALTER TABLE "public"."events"
+    DROP COLUMN "created_at";
@@ -0,0 +1,12 @@
+ALTER TABLE "public"."events"
+    SET UNLOGGED,
+    RESET (autovacuum_enabled, toast.autovacuum_enabled),
+    SET (fillfactor=50, autovacuum_vacuum_scale_factor=0.01);
+
+ALTER TABLE "public"."events"
+    ADD COLUMN "created_at" timestamp with time zone;
+
+CREATE UNLOGGED TABLE "public"."cache" (
+    "key" text,
+    "value" text
+) WITH (fillfactor=90, toast.autovacuum_enabled=false);
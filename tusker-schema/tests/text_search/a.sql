@@ -0,0 +1,15 @@
+CREATE TEXT SEARCH DICTIONARY "public"."english_simple" (
+    TEMPLATE = pg_catalog.simple,
+    stopwords = 'english'
+);
+
+CREATE TEXT SEARCH DICTIONARY "public"."old_stem" (
+    TEMPLATE = pg_catalog.snowball,
+    language = 'english'
+);
+
+CREATE TEXT SEARCH CONFIGURATION "public"."search" (PARSER = pg_catalog."default");
+ALTER TEXT SEARCH CONFIGURATION "public"."search" ADD MAPPING FOR asciiword WITH "public"."old_stem";
+ALTER TEXT SEARCH CONFIGURATION "public"."search" ADD MAPPING FOR email WITH pg_catalog.simple;
+
+CREATE TEXT SEARCH CONFIGURATION "public"."legacy" (PARSER = pg_catalog."default");
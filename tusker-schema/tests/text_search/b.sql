@@ -0,0 +1,10 @@
+CREATE TEXT SEARCH DICTIONARY "public"."english_simple" (
+    TEMPLATE = pg_catalog.simple,
+    stopwords = 'english',
+    accept = false
+);
+
+CREATE TEXT SEARCH CONFIGURATION "public"."search" (PARSER = pg_catalog."default");
+ALTER TEXT SEARCH CONFIGURATION "public"."search" ADD MAPPING FOR asciiword WITH "public"."english_simple", pg_catalog.english_stem;
+ALTER TEXT SEARCH CONFIGURATION "public"."search" ADD MAPPING FOR word WITH pg_catalog.simple;
+COMMENT ON TEXT SEARCH CONFIGURATION "public"."search" IS 'Site search';
@@ -0,0 +1,21 @@
+ALTER TEXT SEARCH CONFIGURATION "public"."search" DROP MAPPING FOR "asciiword";
+
+ALTER TEXT SEARCH CONFIGURATION "public"."search" DROP MAPPING FOR "word";
+
+CREATE TEXT SEARCH DICTIONARY "public"."old_stem" (
+    TEMPLATE = pg_catalog.snowball,
+    language = 'english'
+);
+
+ALTER TEXT SEARCH DICTIONARY "public"."english_simple" (
+    stopwords = 'english',
+    accept
+);
+
+CREATE TEXT SEARCH CONFIGURATION "public"."legacy" (PARSER = pg_catalog."default");
+
+ALTER TEXT SEARCH CONFIGURATION "public"."search" ADD MAPPING FOR "asciiword" WITH public.old_stem;
+
+ALTER TEXT SEARCH CONFIGURATION "public"."search" ADD MAPPING FOR "email" WITH pg_catalog.simple;
+
+COMMENT ON TEXT SEARCH CONFIGURATION "public"."search" IS NULL;
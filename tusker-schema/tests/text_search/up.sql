@@ -0,0 +1,18 @@
+ALTER TEXT SEARCH CONFIGURATION "public"."search" DROP MAPPING FOR "asciiword";
+
+ALTER TEXT SEARCH CONFIGURATION "public"."search" DROP MAPPING FOR "email";
+
+DROP TEXT SEARCH CONFIGURATION "public"."legacy";
+
+DROP TEXT SEARCH DICTIONARY "public"."old_stem";
+
+ALTER TEXT SEARCH DICTIONARY "public"."english_simple" (
+    stopwords = 'english',
+    accept = 'false'
+);
+
+ALTER TEXT SEARCH CONFIGURATION "public"."search" ADD MAPPING FOR "asciiword" WITH public.english_simple, pg_catalog.english_stem;
+
+ALTER TEXT SEARCH CONFIGURATION "public"."search" ADD MAPPING FOR "word" WITH pg_catalog.simple;
+
+COMMENT ON TEXT SEARCH CONFIGURATION "public"."search" IS 'Site search';